@@ -1,6 +1,6 @@
 use crate::{
-    First, Main, MainSchedulePlugin, PlaceholderPlugin, Plugin, Plugins, PluginsState, SubApp,
-    SubApps,
+    First, Last, Main, MainSchedulePlugin, PlaceholderPlugin, Plugin, Plugins, PluginsState,
+    SubApp, SubApps,
 };
 use alloc::{
     boxed::Box,
@@ -12,6 +12,7 @@ use bevy_ecs::{
     component::RequiredComponentsError,
     event::{event_update_system, EventCursor},
     intern::Interned,
+    observer::MutationTriggerSchedule,
     prelude::*,
     result::{Error, SystemErrorContext},
     schedule::{ScheduleBuildSettings, ScheduleLabel},
@@ -121,6 +122,7 @@ impl Default for App {
                 .in_set(bevy_ecs::event::EventUpdates)
                 .run_if(bevy_ecs::event::event_update_condition),
         );
+        app.insert_resource(MutationTriggerSchedule::new(Last));
         app.add_event::<AppExit>();
 
         app
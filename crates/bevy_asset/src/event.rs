@@ -44,6 +44,17 @@ impl<A: Asset> From<&AssetLoadFailedEvent<A>> for UntypedAssetLoadFailedEvent {
     }
 }
 
+/// An event emitted when an [`AssetResidency`](crate::AssetResidency) evicts an unused [`Asset`] to
+/// stay within its memory budget.
+#[derive(Event, Clone, Debug)]
+pub struct AssetEvicted<A: Asset> {
+    /// The stable identifier of the evicted asset.
+    pub id: AssetId<A>,
+    /// The path of the evicted asset, if it was loaded from one. Loading this path again reloads
+    /// the asset.
+    pub path: Option<AssetPath<'static>>,
+}
+
 /// Events that occur for a specific loaded [`Asset`], such as "value changed" events and "dependency" events.
 #[expect(missing_docs, reason = "Documenting the id fields is unhelpful.")]
 #[derive(Event, Reflect)]
@@ -0,0 +1,440 @@
+use crate::io::{
+    get_meta_path, AssetReader, AssetReaderError, EmptyPathStream, PathStream, Reader, VecReader,
+};
+use alloc::{borrow::ToOwned, boxed::Box, format, string::String, sync::Arc, vec::Vec};
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Reader implementation for loading assets over HTTP(S) on native platforms.
+///
+/// Asset paths are appended to the base URL given to [`HttpAssetReader::new`]. Reading directories
+/// isn't supported, as HTTP has no standard way to list them.
+///
+/// With [`HttpAssetReader::with_cache`], downloaded assets are cached on disk along with their
+/// `ETag` and `Last-Modified` headers. Cached assets are revalidated with a conditional request
+/// each time they are read, so unchanged assets aren't downloaded again, and are used as is when
+/// the server can't be reached or responds with a server error.
+///
+/// Responses larger than [`HttpAssetReader::DEFAULT_MAX_BODY_SIZE`] fail to load, which can be
+/// changed with [`HttpAssetReader::with_max_body_size`].
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_asset::{io::{http::HttpAssetReader, AssetSource}, AssetApp};
+/// App::new().register_asset_source(
+///     "remote",
+///     AssetSource::build().with_reader(|| {
+///         Box::new(HttpAssetReader::new("https://example.com/assets").with_cache("web_cache"))
+///     }),
+/// );
+/// ```
+pub struct HttpAssetReader {
+    base_url: String,
+    agent: ureq::Agent,
+    cache: Option<PathBuf>,
+    max_body_size: u64,
+    warned_about_directories: AtomicBool,
+}
+
+impl HttpAssetReader {
+    /// The default maximum size of a downloaded asset, in bytes: 1 GiB.
+    pub const DEFAULT_MAX_BODY_SIZE: u64 = 1 << 30;
+
+    /// Creates a new [`HttpAssetReader`] fetching the assets under the given `base_url`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        let mut base_url = base_url.into();
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+        Self {
+            base_url,
+            agent: ureq::Agent::config_builder()
+                .http_status_as_error(false)
+                .build()
+                .into(),
+            cache: None,
+            max_body_size: Self::DEFAULT_MAX_BODY_SIZE,
+            warned_about_directories: AtomicBool::new(false),
+        }
+    }
+
+    /// Caches the downloaded assets in the `cache_dir` directory.
+    pub fn with_cache(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache = Some(cache_dir.into());
+        self
+    }
+
+    /// Fails to load the assets whose response is larger than `max_body_size` bytes.
+    pub fn with_max_body_size(mut self, max_body_size: u64) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Returns the URL the asset at `path` is fetched from.
+    pub fn url(&self, path: &Path) -> String {
+        let mut url = self.base_url.clone();
+        for component in path.components() {
+            url.push('/');
+            for byte in component.as_os_str().to_string_lossy().bytes() {
+                if byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&byte) {
+                    url.push(byte as char);
+                } else {
+                    url.push_str(&format!("%{byte:02X}"));
+                }
+            }
+        }
+        url
+    }
+
+    async fn fetch_bytes(&self, path: PathBuf) -> Result<impl Reader, AssetReaderError> {
+        let url = self.url(&path);
+        let agent = self.agent.clone();
+        let cache = self.cache.clone();
+        let max_body_size = self.max_body_size;
+        let bytes = blocking::unblock(move || {
+            fetch_blocking(&agent, &url, cache.as_deref(), max_body_size, path)
+        })
+        .await?;
+        Ok(VecReader::new(bytes))
+    }
+
+    /// Warns that directories can't be read, the first time it's called.
+    fn warn_about_directories(&self) {
+        if !self.warned_about_directories.swap(true, Ordering::Relaxed) {
+            warn!("Reading directories is not supported with the HttpAssetReader");
+        }
+    }
+}
+
+fn fetch_blocking(
+    agent: &ureq::Agent,
+    url: &str,
+    cache: Option<&Path>,
+    max_body_size: u64,
+    path: PathBuf,
+) -> Result<Vec<u8>, AssetReaderError> {
+    let cache = cache.map(|dir| CacheEntry::new(dir, url));
+    let mut validators = cache.as_ref().and_then(CacheEntry::read_validators);
+    // Cached assets are only used if their validators were written, which means they're complete.
+    let cached = |validators: &Option<Validators>| {
+        validators
+            .as_ref()
+            .and(cache.as_ref())
+            .and_then(CacheEntry::read_bytes)
+    };
+
+    loop {
+        let mut request = agent.get(url);
+        if let Some(validators) = &validators {
+            if let Some(etag) = &validators.etag {
+                request = request.header("If-None-Match", etag);
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.header("If-Modified-Since", last_modified);
+            }
+        }
+
+        let mut response = match request.call() {
+            Ok(response) => response,
+            Err(err) => {
+                return match cached(&validators) {
+                    Some(bytes) => {
+                        warn!("Failed to fetch {url}, using the cached asset instead: {err}");
+                        Ok(bytes)
+                    }
+                    None => Err(AssetReaderError::Io(Arc::new(err.into_io()))),
+                };
+            }
+        };
+        return match response.status().as_u16() {
+            200 => {
+                let bytes = response
+                    .body_mut()
+                    .with_config()
+                    .limit(max_body_size)
+                    .read_to_vec()
+                    .map_err(|err| AssetReaderError::Io(Arc::new(err.into_io())))?;
+                if let Some(cache) = &cache {
+                    let header = |name| {
+                        response
+                            .headers()
+                            .get(name)
+                            .and_then(|value| value.to_str().ok())
+                    };
+                    if let Err(err) = cache.write(header("ETag"), header("Last-Modified"), &bytes) {
+                        warn!("Failed to cache {url}: {err}");
+                    }
+                }
+                Ok(bytes)
+            }
+            304 => match cached(&validators) {
+                Some(bytes) => Ok(bytes),
+                // The cached asset can't be read anymore, so it has to be downloaded again.
+                None if validators.take().is_some() => continue,
+                None => Err(AssetReaderError::HttpError(304)),
+            },
+            404 => {
+                if let Some(cache) = &cache {
+                    cache.remove();
+                }
+                Err(AssetReaderError::NotFound(path))
+            }
+            status @ 500..=599 => match cached(&validators) {
+                Some(bytes) => {
+                    warn!(
+                        "Fetching {url} failed with status {status}, using the cached asset instead"
+                    );
+                    Ok(bytes)
+                }
+                None => Err(AssetReaderError::HttpError(status)),
+            },
+            status => Err(AssetReaderError::HttpError(status)),
+        };
+    }
+}
+
+/// The files caching the asset downloaded from a URL.
+struct CacheEntry {
+    bytes_path: PathBuf,
+    validators_path: PathBuf,
+}
+
+/// The headers used to revalidate a cached asset.
+struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl CacheEntry {
+    fn new(dir: &Path, url: &str) -> Self {
+        let name = blake3::hash(url.as_bytes()).to_hex();
+        Self {
+            bytes_path: dir.join(name.as_str()),
+            validators_path: dir.join(format!("{name}.validators")),
+        }
+    }
+
+    fn read_validators(&self) -> Option<Validators> {
+        let file = std::fs::read_to_string(&self.validators_path).ok()?;
+        let mut validators = Validators {
+            etag: None,
+            last_modified: None,
+        };
+        for line in file.lines() {
+            match line.split_once(": ") {
+                Some(("ETag", etag)) => validators.etag = Some(etag.to_owned()),
+                Some(("Last-Modified", date)) => validators.last_modified = Some(date.to_owned()),
+                _ => {}
+            }
+        }
+        Some(validators)
+    }
+
+    fn read_bytes(&self) -> Option<Vec<u8>> {
+        std::fs::read(&self.bytes_path).ok()
+    }
+
+    fn write(
+        &self,
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+        bytes: &[u8],
+    ) -> std::io::Result<()> {
+        // The validators are written last, so that an interrupted write is never read back.
+        self.remove();
+        if let Some(parent) = self.bytes_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.bytes_path, bytes)?;
+        let mut validators = String::new();
+        if let Some(etag) = etag {
+            validators.push_str(&format!("ETag: {etag}\n"));
+        }
+        if let Some(last_modified) = last_modified {
+            validators.push_str(&format!("Last-Modified: {last_modified}\n"));
+        }
+        std::fs::write(&self.validators_path, validators)
+    }
+
+    fn remove(&self) {
+        let _ = std::fs::remove_file(&self.validators_path);
+        let _ = std::fs::remove_file(&self.bytes_path);
+    }
+}
+
+impl AssetReader for HttpAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.fetch_bytes(path.to_owned()).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.fetch_bytes(get_meta_path(path)).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let stream: Box<PathStream> = Box::new(EmptyPathStream);
+        self.warn_about_directories();
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, _path: &'a Path) -> Result<bool, AssetReaderError> {
+        self.warn_about_directories();
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use bevy_tasks::block_on;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::Mutex,
+    };
+
+    /// Serves a few assets on a local port, and returns its URL and the requests it received.
+    fn serve() -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+                let mut reader = BufReader::new(&stream);
+                while reader.read_line(&mut request).unwrap() > 2 {}
+                let request = request.to_lowercase();
+                let target = request.split(' ').nth(1).unwrap().to_string();
+                let response = match target.as_str() {
+                    "/a%20b.txt" if request.contains("if-none-match: \"v1\"") => {
+                        "304 Not Modified\r\n".to_string()
+                    }
+                    "/a%20b.txt" => "200 OK\r\nETag: \"v1\"\r\nContent-Length: 1\r\n\r\na".into(),
+                    "/broken.txt" => "500 Internal Server Error\r\n".into(),
+                    // Fails once it's cached.
+                    "/flaky.txt" if request.contains("if-none-match: \"v2\"") => {
+                        "503 Service Unavailable\r\n".to_string()
+                    }
+                    "/flaky.txt" => "200 OK\r\nETag: \"v2\"\r\nContent-Length: 1\r\n\r\nf".into(),
+                    "/gone.txt" if request.contains("if-none-match: \"v3\"") => {
+                        "304 Not Modified\r\n".to_string()
+                    }
+                    "/gone.txt" => "200 OK\r\nETag: \"v3\"\r\nContent-Length: 1\r\n\r\ng".into(),
+                    _ => "404 Not Found\r\n".into(),
+                };
+                received.lock().unwrap().push(request);
+                let response = if response.contains("\r\n\r\n") {
+                    response
+                } else {
+                    format!("{response}Content-Length: 0\r\n\r\n")
+                };
+                stream
+                    .write_all(format!("HTTP/1.1 {response}").as_bytes())
+                    .unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    fn read(reader: &HttpAssetReader, path: &str) -> Result<Vec<u8>, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            AssetReader::read(reader, Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(bytes)
+        })
+    }
+
+    #[test]
+    fn fetches_and_revalidates_cache() {
+        let (url, requests) = serve();
+        let cache = std::env::temp_dir().join(format!("bevy_asset_http_{}", std::process::id()));
+        let reader = HttpAssetReader::new(format!("{url}/")).with_cache(&cache);
+        assert_eq!(reader.url(Path::new("a b.txt")), format!("{url}/a%20b.txt"));
+
+        assert_eq!(read(&reader, "a b.txt").unwrap(), b"a");
+        assert_eq!(read(&reader, "a b.txt").unwrap(), b"a");
+        let requests = requests.lock().unwrap();
+        assert!(!requests[0].contains("if-none-match"));
+        assert!(requests[1].contains("if-none-match: \"v1\""));
+
+        std::fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn falls_back_to_cache_on_server_errors() {
+        let (url, requests) = serve();
+        let cache =
+            std::env::temp_dir().join(format!("bevy_asset_http_5xx_{}", std::process::id()));
+        let reader = HttpAssetReader::new(url).with_cache(&cache);
+
+        assert_eq!(read(&reader, "flaky.txt").unwrap(), b"f");
+        assert_eq!(read(&reader, "flaky.txt").unwrap(), b"f");
+        assert_eq!(requests.lock().unwrap().len(), 2);
+        // Server errors are still reported for assets that aren't cached.
+        assert_eq!(
+            read(&reader, "broken.txt"),
+            Err(AssetReaderError::HttpError(500))
+        );
+
+        std::fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn downloads_again_when_the_cached_asset_is_gone() {
+        let (url, requests) = serve();
+        let cache =
+            std::env::temp_dir().join(format!("bevy_asset_http_304_{}", std::process::id()));
+        let reader = HttpAssetReader::new(&url).with_cache(&cache);
+
+        assert_eq!(read(&reader, "gone.txt").unwrap(), b"g");
+        let entry = CacheEntry::new(&cache, &format!("{url}/gone.txt"));
+        std::fs::remove_file(&entry.bytes_path).unwrap();
+        assert_eq!(read(&reader, "gone.txt").unwrap(), b"g");
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[1].contains("if-none-match: \"v3\""));
+        assert!(!requests[2].contains("if-none-match"));
+
+        std::fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn limits_the_body_size() {
+        let (url, _) = serve();
+        let reader = HttpAssetReader::new(url).with_max_body_size(0);
+
+        assert!(matches!(
+            read(&reader, "a b.txt"),
+            Err(AssetReaderError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn reports_status_codes() {
+        let (url, _) = serve();
+        let reader = HttpAssetReader::new(url);
+
+        assert_eq!(
+            read(&reader, "missing.txt"),
+            Err(AssetReaderError::NotFound(PathBuf::from("missing.txt")))
+        );
+        assert_eq!(
+            read(&reader, "broken.txt"),
+            Err(AssetReaderError::HttpError(500))
+        );
+        let meta = block_on(AssetReader::read_meta(&reader, Path::new("a b.txt")));
+        assert!(
+            matches!(meta, Err(AssetReaderError::NotFound(path)) if path == Path::new("a b.txt.meta"))
+        );
+    }
+}
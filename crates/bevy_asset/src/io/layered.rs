@@ -0,0 +1,326 @@
+use crate::io::{
+    AssetReader, AssetReaderError, AssetWatcher, ErasedAssetReader, PathStream, Reader,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bevy_platform_support::collections::{HashMap, HashSet};
+use futures_lite::StreamExt;
+use parking_lot::RwLock;
+use std::path::{Path, PathBuf};
+
+/// An [`AssetReader`] composed of several "layers" of readers, in priority order.
+///
+/// Assets are read from the first layer that contains them, so earlier layers override the
+/// assets of later ones. This is useful for mods and DLCs: with the layers `mods/foo`, `dlc` and
+/// `assets`, loading `textures/hero.png` picks up the version of the mod if there is one, then the
+/// version of the DLC, and finally falls back to the base asset.
+///
+/// Meta files are read from the layer that supplied their asset, so an overridden asset never
+/// picks up the meta file of the asset it replaces. The layer of each asset is remembered when it
+/// is read, so reading its meta file afterwards doesn't search the layers again. Directory
+/// listings are merged across all layers.
+///
+/// See [`AssetSourceBuilder::layered`](crate::io::AssetSourceBuilder::layered) to build an
+/// [`AssetSource`](crate::io::AssetSource) from the sources of each layer, including their
+/// watchers.
+pub struct LayeredAssetReader {
+    layers: Vec<Box<dyn ErasedAssetReader>>,
+    /// The index of the layer each asset was last read from.
+    asset_layers: RwLock<HashMap<PathBuf, usize>>,
+}
+
+impl LayeredAssetReader {
+    /// Creates a new [`LayeredAssetReader`] reading from the given `layers`, from the highest
+    /// priority to the lowest.
+    pub fn new(layers: Vec<Box<dyn ErasedAssetReader>>) -> Self {
+        Self {
+            layers,
+            asset_layers: RwLock::new(HashMap::default()),
+        }
+    }
+
+    /// Adds a layer with a lower priority than the current ones.
+    pub fn with_layer(mut self, layer: impl ErasedAssetReader) -> Self {
+        self.layers.push(Box::new(layer));
+        self
+    }
+
+    /// Returns the layers of this reader, from the highest priority to the lowest.
+    pub fn layers(&self) -> &[Box<dyn ErasedAssetReader>] {
+        &self.layers
+    }
+}
+
+impl AssetReader for LayeredAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        for (index, layer) in self.layers.iter().enumerate() {
+            match layer.read(path).await {
+                Err(AssetReaderError::NotFound(_)) => continue,
+                Err(err) => return Err(err),
+                Ok(reader) => {
+                    self.asset_layers.write().insert(path.to_path_buf(), index);
+                    return Ok(reader);
+                }
+            }
+        }
+        self.asset_layers.write().remove(path);
+        Err(AssetReaderError::NotFound(path.to_path_buf()))
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        // The asset server reads assets before their meta file, so their layer is usually known.
+        // Otherwise, it's the first layer that contains the asset.
+        let known_layer = self.asset_layers.read().get(path).copied();
+        if let Some(index) = known_layer {
+            return self.layers[index].read_meta(path).await;
+        }
+        for (index, layer) in self.layers.iter().enumerate() {
+            match layer.read(path).await {
+                Err(AssetReaderError::NotFound(_)) => continue,
+                Err(err) => return Err(err),
+                Ok(_) => {
+                    self.asset_layers.write().insert(path.to_path_buf(), index);
+                    return layer.read_meta(path).await;
+                }
+            }
+        }
+        Err(AssetReaderError::NotFound(path.to_path_buf()))
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let mut found = false;
+        let mut seen = HashSet::<PathBuf>::default();
+        let mut entries = Vec::new();
+        for layer in &self.layers {
+            let mut layer_entries = match layer.read_directory(path).await {
+                Ok(layer_entries) => layer_entries,
+                Err(AssetReaderError::NotFound(_)) => continue,
+                Err(err) => return Err(err),
+            };
+            found = true;
+            while let Some(entry) = layer_entries.next().await {
+                if seen.insert(entry.clone()) {
+                    entries.push(entry);
+                }
+            }
+        }
+        if !found {
+            return Err(AssetReaderError::NotFound(path.to_path_buf()));
+        }
+        let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(entries));
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        for layer in &self.layers {
+            match layer.is_directory(path).await {
+                Ok(false) | Err(AssetReaderError::NotFound(_)) => continue,
+                result => return result,
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// An [`AssetWatcher`] keeping the watchers of every layer of a [`LayeredAssetReader`] alive.
+pub struct LayeredAssetWatcher {
+    _watchers: Vec<Box<dyn AssetWatcher>>,
+}
+
+impl LayeredAssetWatcher {
+    /// Creates a new [`LayeredAssetWatcher`] from the watchers of each layer.
+    pub fn new(watchers: Vec<Box<dyn AssetWatcher>>) -> Self {
+        Self {
+            _watchers: watchers,
+        }
+    }
+}
+
+impl AssetWatcher for LayeredAssetWatcher {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{
+        memory::{Dir, MemoryAssetReader},
+        AssetSource, AssetSourceBuilder, AssetSourceEvent, AssetSourceId,
+    };
+    use alloc::{string::String, sync::Arc, vec};
+    use bevy_tasks::block_on;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    fn layer(assets: &[(&str, &str)]) -> MemoryAssetReader {
+        let root = Dir::default();
+        for (path, text) in assets {
+            root.insert_asset_text(Path::new(path), text);
+        }
+        MemoryAssetReader { root }
+    }
+
+    fn read(reader: &LayeredAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            AssetReader::read(reader, Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(String::from_utf8(bytes).unwrap())
+        })
+    }
+
+    fn read_meta(reader: &LayeredAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            AssetReader::read_meta(reader, Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(String::from_utf8(bytes).unwrap())
+        })
+    }
+
+    #[test]
+    fn layers_override_and_merge() {
+        let base = layer(&[("hero.png", "base"), ("ui/a.png", "a"), ("ui/b.png", "b")]);
+        base.root
+            .insert_meta_text(Path::new("hero.png"), "base meta");
+        base.root.insert_meta_text(Path::new("ui/a.png"), "a meta");
+        base.root.insert_meta_text(Path::new("ui/b.png"), "b meta");
+        let dlc = layer(&[("ui/b.png", "dlc")]);
+        dlc.root.insert_meta_text(Path::new("ui/b.png"), "dlc meta");
+        let reader = LayeredAssetReader::new(vec![])
+            .with_layer(layer(&[("hero.png", "mod"), ("ui/c.png", "c")]))
+            .with_layer(dlc)
+            .with_layer(base);
+
+        assert_eq!(read(&reader, "hero.png").unwrap(), "mod");
+        assert_eq!(read(&reader, "ui/b.png").unwrap(), "dlc");
+        assert_eq!(read(&reader, "ui/a.png").unwrap(), "a");
+        assert_eq!(
+            read(&reader, "missing.png"),
+            Err(AssetReaderError::NotFound(PathBuf::from("missing.png")))
+        );
+
+        // Meta files come from the layer of their asset, even if it has none.
+        assert_eq!(
+            read_meta(&reader, "hero.png"),
+            Err(AssetReaderError::NotFound(PathBuf::from("hero.png")))
+        );
+        assert_eq!(read_meta(&reader, "ui/b.png").unwrap(), "dlc meta");
+        assert_eq!(read_meta(&reader, "ui/a.png").unwrap(), "a meta");
+
+        block_on(async {
+            let mut entries = AssetReader::read_directory(&reader, Path::new("ui"))
+                .await
+                .unwrap()
+                .collect::<Vec<_>>()
+                .await;
+            entries.sort();
+            assert_eq!(
+                entries,
+                ["ui/a.png", "ui/b.png", "ui/c.png"].map(PathBuf::from)
+            );
+            assert!(AssetReader::is_directory(&reader, Path::new("ui"))
+                .await
+                .unwrap());
+            assert!(!AssetReader::is_directory(&reader, Path::new("hero.png"))
+                .await
+                .unwrap());
+            assert!(AssetReader::read_directory(&reader, Path::new("nope"))
+                .await
+                .is_err());
+        });
+    }
+
+    /// Counts the assets read from a layer.
+    struct CountingReader {
+        inner: MemoryAssetReader,
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl AssetReader for CountingReader {
+        async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            AssetReader::read(&self.inner, path).await
+        }
+
+        async fn read_meta<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> Result<impl Reader + 'a, AssetReaderError> {
+            AssetReader::read_meta(&self.inner, path).await
+        }
+
+        async fn read_directory<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> Result<Box<PathStream>, AssetReaderError> {
+            AssetReader::read_directory(&self.inner, path).await
+        }
+
+        async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+            AssetReader::is_directory(&self.inner, path).await
+        }
+    }
+
+    #[test]
+    fn meta_files_are_read_from_the_remembered_layer() {
+        let counting_layer = |assets: &[(&str, &str)]| {
+            let reads = Arc::new(AtomicUsize::new(0));
+            let reader = CountingReader {
+                inner: layer(assets),
+                reads: reads.clone(),
+            };
+            (reader, reads)
+        };
+        let (top, top_reads) = counting_layer(&[]);
+        let (base, base_reads) = counting_layer(&[("hero.png", "base")]);
+        base.inner
+            .root
+            .insert_meta_text(Path::new("hero.png"), "base meta");
+        let reader = LayeredAssetReader::new(vec![])
+            .with_layer(top)
+            .with_layer(base);
+
+        assert_eq!(read(&reader, "hero.png").unwrap(), "base");
+        assert_eq!(read_meta(&reader, "hero.png").unwrap(), "base meta");
+        assert_eq!(top_reads.load(Ordering::Relaxed), 1);
+        assert_eq!(base_reads.load(Ordering::Relaxed), 1);
+    }
+
+    struct NoopWatcher;
+
+    impl AssetWatcher for NoopWatcher {}
+
+    #[test]
+    fn forwards_watcher_events() {
+        let watched_layer = |name: &'static str| {
+            AssetSource::build()
+                .with_reader(|| Box::new(layer(&[])))
+                .with_watcher(move |sender| {
+                    sender
+                        .send(AssetSourceEvent::ModifiedAsset(PathBuf::from(name)))
+                        .unwrap();
+                    Some(Box::new(NoopWatcher))
+                })
+        };
+        let mut builder =
+            AssetSourceBuilder::layered([watched_layer("a.png"), watched_layer("b.png")]);
+        let source = builder.build(AssetSourceId::Default, true, false).unwrap();
+
+        let events = source
+            .event_receiver()
+            .unwrap()
+            .try_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 2);
+        assert!(
+            matches!(&events[0], AssetSourceEvent::ModifiedAsset(path) if path == Path::new("a.png"))
+        );
+        assert!(
+            matches!(&events[1], AssetSourceEvent::ModifiedAsset(path) if path == Path::new("b.png"))
+        );
+    }
+}
@@ -0,0 +1,590 @@
+//! A single-file asset archive ("pack"), read with [`PackAssetReader`] and written with [`PackWriter`].
+//!
+//! Shipping thousands of loose files is slow to install and to scan, so processed assets can
+//! instead be packed into one file and read back from an [`AssetSource`](crate::io::AssetSource).
+//!
+//! A pack starts with a header, followed by the data of every asset and meta file it contains,
+//! and ends with their index, so that it can be written while the entries are added:
+//!
+//! | Field        | Type                                                        |
+//! |--------------|-------------------------------------------------------------|
+//! | magic        | `b"BPAK"`                                                   |
+//! | version      | little-endian `u32`, currently [`PACK_VERSION`]             |
+//! | index offset | little-endian `u64`, from the start of the pack             |
+//! | data         | the bytes of every entry                                    |
+//! | entry count  | little-endian `u32`                                         |
+//! | entries      | `u8` kind (0 for assets, 1 for metas), `u32` path length, `/`-separated UTF-8 path, `u64` data offset from the start of the pack, `u64` data length |
+
+use crate::io::{
+    AssetReader, AssetReaderError, ErasedAssetReader, PathStream, Reader, SliceReader, VecReader,
+};
+use alloc::{
+    borrow::ToOwned, boxed::Box, collections::BTreeMap, string::String, sync::Arc, vec, vec::Vec,
+};
+use bevy_platform_support::collections::HashMap;
+use core::ops::Range;
+use futures_lite::{AsyncReadExt, AsyncSeekExt, StreamExt};
+use std::{
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// The bytes every pack starts with.
+pub const PACK_MAGIC: [u8; 4] = *b"BPAK";
+
+/// The version of the pack format written by [`PackWriter`].
+pub const PACK_VERSION: u32 = 1;
+
+const ASSET_ENTRY: u8 = 0;
+const META_ENTRY: u8 = 1;
+
+/// The position of the index offset in the header.
+const INDEX_OFFSET_POSITION: u64 = 8;
+/// The length of the header, where the data of the entries starts.
+const HEADER_LEN: u64 = 16;
+
+/// The size of the chunks in which [`PackWriter::add_directory`] copies entries to the pack.
+const COPY_CHUNK_LEN: usize = 64 * 1024;
+
+/// Errors that occur while opening a pack.
+#[derive(Error, Debug)]
+pub enum PackError {
+    /// Encountered an I/O error while reading the pack file.
+    #[error("Encountered an I/O error while reading pack: {0}")]
+    Io(#[from] std::io::Error),
+    /// The data doesn't start with [`PACK_MAGIC`].
+    #[error("Data is not an asset pack")]
+    InvalidMagic,
+    /// The pack was written with an unsupported version of the format.
+    #[error("Unsupported asset pack version {0}, expected version {PACK_VERSION}")]
+    UnsupportedVersion(u32),
+    /// The index is truncated, or points outside of the pack.
+    #[error("Asset pack is corrupted")]
+    Corrupted,
+}
+
+/// An [`AssetReader`] for the assets stored in a pack written by [`PackWriter`].
+///
+/// When opened from a file, only the index of the pack is kept in memory, and the data of each
+/// entry is read from the file when it's requested, so concurrent reads don't wait on each other.
+/// Cloning the reader is cheap, so it can be
+/// registered as an asset source:
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_asset::{io::{pack::PackAssetReader, AssetSource}, AssetApp};
+/// let reader = PackAssetReader::open("assets.pack").unwrap();
+/// App::new().register_asset_source(
+///     "pack",
+///     AssetSource::build().with_reader(move || Box::new(reader.clone())),
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct PackAssetReader {
+    pack: Arc<Pack>,
+}
+
+#[derive(Debug)]
+struct Pack {
+    data: PackData,
+    /// The range of each asset within the pack.
+    assets: HashMap<PathBuf, Range<u64>>,
+    /// The range of each meta file within the pack.
+    metas: HashMap<PathBuf, Range<u64>>,
+    dirs: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+/// Where the data of a pack is read from.
+#[derive(Debug)]
+enum PackData {
+    /// The whole pack is in memory.
+    Bytes(Arc<[u8]>),
+    /// The entries are read from the pack file at this path on demand, opening it for each read.
+    File(PathBuf),
+}
+
+impl PackAssetReader {
+    /// Opens the pack file at the given `path`, only reading its index into memory.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PackError> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        let (assets, metas, dirs) = read_index(file, len)?;
+        Ok(Self {
+            pack: Arc::new(Pack {
+                data: PackData::File(path.to_owned()),
+                assets,
+                metas,
+                dirs,
+            }),
+        })
+    }
+
+    /// Reads a pack from its `bytes`.
+    pub fn from_bytes(bytes: impl Into<Arc<[u8]>>) -> Result<Self, PackError> {
+        let bytes = bytes.into();
+        let (assets, metas, dirs) = read_index(std::io::Cursor::new(&*bytes), bytes.len() as u64)?;
+        Ok(Self {
+            pack: Arc::new(Pack {
+                data: PackData::Bytes(bytes),
+                assets,
+                metas,
+                dirs,
+            }),
+        })
+    }
+
+    /// Returns the paths of all assets in the pack.
+    pub fn asset_paths(&self) -> impl Iterator<Item = &Path> {
+        self.pack.assets.keys().map(PathBuf::as_path)
+    }
+}
+
+/// The ranges of the assets and meta files of a pack, and its directories.
+type PackIndex = (
+    HashMap<PathBuf, Range<u64>>,
+    HashMap<PathBuf, Range<u64>>,
+    HashMap<PathBuf, Vec<PathBuf>>,
+);
+
+/// Reads the header and the index of a pack of `len` bytes.
+fn read_index(reader: impl Read + Seek, len: u64) -> Result<PackIndex, PackError> {
+    let mut index = Index {
+        reader: BufReader::new(reader),
+    };
+    if index.take(PACK_MAGIC.len())? != PACK_MAGIC {
+        return Err(PackError::InvalidMagic);
+    }
+    let version = index.u32()?;
+    if version != PACK_VERSION {
+        return Err(PackError::UnsupportedVersion(version));
+    }
+    let index_start = index.u64()?;
+    if !(HEADER_LEN..=len).contains(&index_start) {
+        return Err(PackError::Corrupted);
+    }
+    index.reader.seek(SeekFrom::Start(index_start))?;
+
+    let count = index.u32()?;
+    let mut assets = HashMap::default();
+    let mut metas = HashMap::default();
+    let mut dirs = HashMap::default();
+    dirs.insert(PathBuf::new(), Vec::new());
+    for _ in 0..count {
+        let kind = index.u8()?;
+        let path_len = index.u32()? as usize;
+        let path = String::from_utf8(index.take(path_len)?).map_err(|_| PackError::Corrupted)?;
+        let path = PathBuf::from(path);
+        let start = index.u64()?;
+        let end = start
+            .checked_add(index.u64()?)
+            .filter(|&end| HEADER_LEN <= start && end <= index_start)
+            .ok_or(PackError::Corrupted)?;
+        match kind {
+            ASSET_ENTRY => {
+                add_to_dirs(&mut dirs, &path);
+                assets.insert(path, start..end);
+            }
+            META_ENTRY => {
+                metas.insert(path, start..end);
+            }
+            _ => return Err(PackError::Corrupted),
+        }
+    }
+    Ok((assets, metas, dirs))
+}
+
+/// Registers the `path` of an asset in the directories containing it.
+fn add_to_dirs(dirs: &mut HashMap<PathBuf, Vec<PathBuf>>, path: &Path) {
+    let mut child = path.to_owned();
+    while let Some(parent) = child.parent() {
+        let parent = parent.to_owned();
+        let is_new = !dirs.contains_key(&parent);
+        dirs.entry(parent.clone()).or_default().push(child);
+        if !is_new {
+            break;
+        }
+        child = parent;
+    }
+}
+
+/// Reads the header and the index of a pack.
+struct Index<R> {
+    reader: R,
+}
+
+impl<R: Read> Index<R> {
+    fn take(&mut self, len: usize) -> Result<Vec<u8>, PackError> {
+        let mut bytes = Vec::new();
+        (&mut self.reader)
+            .take(len as u64)
+            .read_to_end(&mut bytes)?;
+        if bytes.len() != len {
+            return Err(PackError::Corrupted);
+        }
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, PackError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, PackError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, PackError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+impl Pack {
+    /// Reads the entry at `path` in the given `entries`.
+    async fn read_entry<'a>(
+        &'a self,
+        entries: &HashMap<PathBuf, Range<u64>>,
+        path: &Path,
+    ) -> Result<Box<dyn Reader + 'a>, AssetReaderError> {
+        let range = entries
+            .get(path)
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))?;
+        match &self.data {
+            // The ranges were checked against the length of the pack when reading the index.
+            PackData::Bytes(bytes) => Ok(Box::new(SliceReader::new(
+                &bytes[range.start as usize..range.end as usize],
+            ))),
+            PackData::File(path) => {
+                // Each read opens its own handle, so reads don't share a file position.
+                let mut file = async_fs::File::open(path).await?;
+                file.seek(SeekFrom::Start(range.start)).await?;
+                let mut bytes = vec![0; (range.end - range.start) as usize];
+                file.read_exact(&mut bytes).await?;
+                Ok(Box::new(VecReader::new(bytes)))
+            }
+        }
+    }
+}
+
+impl AssetReader for PackAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.pack.read_entry(&self.pack.assets, path).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.pack.read_entry(&self.pack.metas, path).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        self.pack
+            .dirs
+            .get(path)
+            .map(|entries| {
+                let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(entries.clone()));
+                stream
+            })
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        Ok(self.pack.dirs.contains_key(path))
+    }
+}
+
+/// Writes a pack that can be read with [`PackAssetReader`].
+///
+/// The data of each entry is written as soon as it's added, and only the index is kept in memory
+/// until [`PackWriter::finish`] writes it. A whole processed asset directory can be packed at
+/// once with [`PackWriter::add_directory`]:
+///
+/// ```no_run
+/// # use bevy_asset::io::{file::FileAssetReader, pack::PackWriter};
+/// # use std::{fs::File, io::BufWriter, path::Path};
+/// # bevy_tasks::block_on(async {
+/// let file = BufWriter::new(File::create("assets.pack").unwrap());
+/// let mut writer = PackWriter::new(file).unwrap();
+/// writer
+///     .add_directory(&FileAssetReader::new("imported_assets/Default"), Path::new(""))
+///     .await
+///     .unwrap();
+/// writer.finish().unwrap();
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct PackWriter<W: Write + Seek> {
+    writer: W,
+    /// The position of the start of the pack in the `writer`.
+    start: u64,
+    /// The position the data of the next entry is written at.
+    position: u64,
+    assets: BTreeMap<String, Range<u64>>,
+    metas: BTreeMap<String, Range<u64>>,
+}
+
+impl<W: Write + Seek> PackWriter<W> {
+    /// Starts writing a pack to the given `writer`, from its current position onwards.
+    pub fn new(mut writer: W) -> std::io::Result<Self> {
+        let start = writer.stream_position()?;
+        writer.write_all(&PACK_MAGIC)?;
+        writer.write_all(&PACK_VERSION.to_le_bytes())?;
+        // Filled in with the offset of the index once all entries are written.
+        writer.write_all(&0u64.to_le_bytes())?;
+        Ok(Self {
+            writer,
+            start,
+            position: HEADER_LEN,
+            assets: BTreeMap::new(),
+            metas: BTreeMap::new(),
+        })
+    }
+
+    /// Adds the asset at `path` with the given `bytes`, replacing any previous asset at this path.
+    /// The data of a replaced asset is left unused in the pack.
+    pub fn add_asset(&mut self, path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+        let range = self.write_data(bytes)?;
+        self.assets.insert(pack_path(path), range);
+        Ok(())
+    }
+
+    /// Adds the meta file of the asset at `path` with the given `bytes`, replacing any previous meta
+    /// file for this asset. The data of a replaced meta file is left unused in the pack.
+    pub fn add_meta(&mut self, path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+        let range = self.write_data(bytes)?;
+        self.metas.insert(pack_path(path), range);
+        Ok(())
+    }
+
+    /// Adds every asset in the directory at `path` of the `reader`, its subdirectories, and their
+    /// meta files. The assets are added relative to `path`, and copied to the pack in chunks.
+    pub async fn add_directory(
+        &mut self,
+        reader: &dyn ErasedAssetReader,
+        path: &Path,
+    ) -> Result<(), AssetReaderError> {
+        let mut pending = vec![path.to_owned()];
+        let mut chunk = vec![0; COPY_CHUNK_LEN];
+        while let Some(dir) = pending.pop() {
+            let mut entries = reader.read_directory(&dir).await?;
+            while let Some(entry) = entries.next().await {
+                if reader.is_directory(&entry).await? {
+                    pending.push(entry);
+                    continue;
+                }
+                let mut asset = reader.read(&entry).await?;
+                let start = self.position;
+                loop {
+                    let read = AsyncReadExt::read(&mut asset, &mut chunk).await?;
+                    if read == 0 {
+                        break;
+                    }
+                    self.write_data(&chunk[..read])?;
+                }
+                let range = start..self.position;
+                let meta = match reader.read_meta_bytes(&entry).await {
+                    Ok(meta) => Some(meta),
+                    Err(AssetReaderError::NotFound(_)) => None,
+                    Err(err) => return Err(err),
+                };
+
+                let packed_path = entry.strip_prefix(path).unwrap_or(&entry);
+                self.assets.insert(pack_path(packed_path), range);
+                if let Some(meta) = meta {
+                    self.add_meta(packed_path, &meta)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes `bytes` after the data written so far, returning their range within the pack.
+    fn write_data(&mut self, bytes: &[u8]) -> std::io::Result<Range<u64>> {
+        self.writer.write_all(bytes)?;
+        let start = self.position;
+        self.position += bytes.len() as u64;
+        Ok(start..self.position)
+    }
+
+    /// Writes the index of the pack, completing it, and returns the underlying writer.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        let index_start = self.position;
+        let count = self.assets.len() + self.metas.len();
+        let entries = self
+            .assets
+            .iter()
+            .map(|(path, range)| (ASSET_ENTRY, path, range))
+            .chain(
+                self.metas
+                    .iter()
+                    .map(|(path, range)| (META_ENTRY, path, range)),
+            );
+
+        let writer = &mut self.writer;
+        writer.write_all(&index_u32(count)?.to_le_bytes())?;
+        for (kind, path, range) in entries {
+            writer.write_all(&[kind])?;
+            writer.write_all(&index_u32(path.len())?.to_le_bytes())?;
+            writer.write_all(path.as_bytes())?;
+            writer.write_all(&range.start.to_le_bytes())?;
+            writer.write_all(&(range.end - range.start).to_le_bytes())?;
+        }
+        let end = writer.stream_position()?;
+        writer.seek(SeekFrom::Start(self.start + INDEX_OFFSET_POSITION))?;
+        writer.write_all(&index_start.to_le_bytes())?;
+        writer.seek(SeekFrom::Start(end))?;
+        writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Converts `path` to the `/`-separated form stored in packs.
+fn pack_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn index_u32(value: usize) -> std::io::Result<u32> {
+    u32::try_from(value).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "too many entries or path too long for an asset pack",
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::memory::{Dir, MemoryAssetReader};
+    use bevy_tasks::block_on;
+    use std::io::Cursor;
+
+    async fn read_string(mut reader: impl Reader) -> String {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await.unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn pack_directory() {
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new("a.txt"), "a");
+        dir.insert_meta_text(Path::new("a.txt"), "a meta");
+        dir.insert_asset_text(Path::new("x/y/b.txt"), "b");
+        dir.insert_asset_text(Path::new("x/c.txt"), "c");
+
+        let mut writer = PackWriter::new(Cursor::new(Vec::new())).unwrap();
+        block_on(writer.add_directory(&MemoryAssetReader { root: dir }, Path::new(""))).unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+        let reader = PackAssetReader::from_bytes(bytes).unwrap();
+        let reader: &dyn ErasedAssetReader = &reader;
+
+        block_on(async {
+            assert_eq!(
+                read_string(reader.read(Path::new("a.txt")).await.unwrap()).await,
+                "a"
+            );
+            assert_eq!(
+                read_string(reader.read_meta(Path::new("a.txt")).await.unwrap()).await,
+                "a meta"
+            );
+            assert_eq!(
+                read_string(reader.read(Path::new("x/y/b.txt")).await.unwrap()).await,
+                "b"
+            );
+            assert!(matches!(
+                reader.read_meta(Path::new("x/c.txt")).await,
+                Err(AssetReaderError::NotFound(_))
+            ));
+            assert!(matches!(
+                reader.read(Path::new("x")).await,
+                Err(AssetReaderError::NotFound(_))
+            ));
+
+            assert!(reader.is_directory(Path::new("")).await.unwrap());
+            assert!(reader.is_directory(Path::new("x/y")).await.unwrap());
+            assert!(!reader.is_directory(Path::new("a.txt")).await.unwrap());
+
+            let mut entries = reader
+                .read_directory(Path::new("x"))
+                .await
+                .unwrap()
+                .collect::<Vec<_>>()
+                .await;
+            entries.sort();
+            assert_eq!(entries, [PathBuf::from("x/c.txt"), PathBuf::from("x/y")]);
+            let entries = reader
+                .read_directory(Path::new(""))
+                .await
+                .unwrap()
+                .collect::<Vec<_>>()
+                .await;
+            assert_eq!(entries.len(), 2);
+        });
+    }
+
+    #[test]
+    fn open_pack_file() {
+        let path = std::env::temp_dir().join(alloc::format!(
+            "bevy_asset_pack_{}.pack",
+            std::process::id()
+        ));
+        let mut writer = PackWriter::new(File::create(&path).unwrap()).unwrap();
+        writer.add_asset(Path::new("a.txt"), b"a").unwrap();
+        writer.add_asset(Path::new("x/b.txt"), b"b").unwrap();
+        writer.add_meta(Path::new("x/b.txt"), b"b meta").unwrap();
+        writer.finish().unwrap();
+
+        let reader = PackAssetReader::open(&path).unwrap();
+        // Only the index is read up-front: the data of `a.txt`, written first right after the
+        // header, is read from the file when requested.
+        let mut file = std::fs::OpenOptions::new().write(true).open(&path).unwrap();
+        file.seek(SeekFrom::Start(HEADER_LEN)).unwrap();
+        file.write_all(b"z").unwrap();
+        drop(file);
+        let reader: &dyn ErasedAssetReader = &reader;
+        block_on(async {
+            assert_eq!(
+                read_string(reader.read(Path::new("x/b.txt")).await.unwrap()).await,
+                "b"
+            );
+            assert_eq!(
+                read_string(reader.read_meta(Path::new("x/b.txt")).await.unwrap()).await,
+                "b meta"
+            );
+            assert_eq!(
+                read_string(reader.read(Path::new("a.txt")).await.unwrap()).await,
+                "z"
+            );
+        });
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn invalid_packs() {
+        assert!(matches!(
+            PackAssetReader::from_bytes(b"nope".to_vec()),
+            Err(PackError::InvalidMagic)
+        ));
+
+        let mut writer = PackWriter::new(Cursor::new(Vec::new())).unwrap();
+        writer.add_asset(Path::new("a.txt"), b"a").unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+        assert!(matches!(
+            PackAssetReader::from_bytes(bytes[..bytes.len() - 1].to_vec()),
+            Err(PackError::Corrupted)
+        ));
+
+        let mut bytes = bytes;
+        bytes[4] = 2;
+        assert!(matches!(
+            PackAssetReader::from_bytes(bytes),
+            Err(PackError::UnsupportedVersion(2))
+        ));
+    }
+}
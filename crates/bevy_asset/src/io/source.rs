@@ -1,11 +1,16 @@
 use crate::{
-    io::{processor_gated::ProcessorGatedReader, AssetSourceEvent, AssetWatcher},
+    io::{
+        layered::{LayeredAssetReader, LayeredAssetWatcher},
+        processor_gated::ProcessorGatedReader,
+        AssetSourceEvent, AssetWatcher,
+    },
     processor::AssetProcessorData,
 };
 use alloc::{
     boxed::Box,
     string::{String, ToString},
     sync::Arc,
+    vec::Vec,
 };
 use atomicow::CowArc;
 use bevy_ecs::resource::Resource;
@@ -316,6 +321,90 @@ impl AssetSourceBuilder {
             default
         }
     }
+
+    /// Returns a builder for a source composed of the given `layers`, from the highest priority to
+    /// the lowest. This is useful to let mods or DLCs override the assets of a base source.
+    ///
+    /// The readers of the layers are combined in a [`LayeredAssetReader`], and the events of all
+    /// their watchers are forwarded to the source. The writers are those of the first layer that
+    /// has one.
+    ///
+    /// ```
+    /// # use bevy_asset::io::AssetSourceBuilder;
+    /// let builder = AssetSourceBuilder::layered([
+    ///     AssetSourceBuilder::platform_default("mods/foo", None),
+    ///     AssetSourceBuilder::platform_default("dlc", None),
+    ///     AssetSourceBuilder::platform_default("assets", None),
+    /// ]);
+    /// ```
+    pub fn layered(layers: impl IntoIterator<Item = AssetSourceBuilder>) -> Self {
+        let mut builder = Self::default();
+        let mut readers = Vec::new();
+        let mut watchers = Vec::new();
+        let mut processed_readers = Vec::new();
+        let mut processed_watchers = Vec::new();
+        for layer in layers {
+            readers.extend(layer.reader);
+            watchers.extend(layer.watcher);
+            processed_readers.extend(layer.processed_reader);
+            processed_watchers.extend(layer.processed_watcher);
+            builder.writer = builder.writer.or(layer.writer);
+            builder.processed_writer = builder.processed_writer.or(layer.processed_writer);
+            builder.watch_warning = builder.watch_warning.or(layer.watch_warning);
+            builder.processed_watch_warning = builder
+                .processed_watch_warning
+                .or(layer.processed_watch_warning);
+        }
+
+        builder = builder.with_reader(layered_reader(readers));
+        if !watchers.is_empty() {
+            builder = builder.with_watcher(layered_watcher(watchers));
+        }
+        if !processed_readers.is_empty() {
+            builder = builder.with_processed_reader(layered_reader(processed_readers));
+        }
+        if !processed_watchers.is_empty() {
+            builder = builder.with_processed_watcher(layered_watcher(processed_watchers));
+        }
+        builder
+    }
+}
+
+type ReaderFn = Box<dyn FnMut() -> Box<dyn ErasedAssetReader> + Send + Sync>;
+
+type WatcherFn = Box<
+    dyn FnMut(crossbeam_channel::Sender<AssetSourceEvent>) -> Option<Box<dyn AssetWatcher>>
+        + Send
+        + Sync,
+>;
+
+/// Combines the reader functions of the layers of [`AssetSourceBuilder::layered`].
+fn layered_reader(
+    mut readers: Vec<ReaderFn>,
+) -> impl FnMut() -> Box<dyn ErasedAssetReader> + Send + Sync + 'static {
+    move || {
+        let layers = readers.iter_mut().map(|reader| reader()).collect();
+        Box::new(LayeredAssetReader::new(layers))
+    }
+}
+
+/// Combines the watcher functions of the layers of [`AssetSourceBuilder::layered`].
+fn layered_watcher(
+    mut watchers: Vec<WatcherFn>,
+) -> impl FnMut(crossbeam_channel::Sender<AssetSourceEvent>) -> Option<Box<dyn AssetWatcher>>
+       + Send
+       + Sync
+       + 'static {
+    move |sender| {
+        let watchers = watchers
+            .iter_mut()
+            .filter_map(|watcher| watcher(sender.clone()))
+            .collect::<Vec<_>>();
+        (!watchers.is_empty()).then(|| {
+            let watcher: Box<dyn AssetWatcher> = Box::new(LayeredAssetWatcher::new(watchers));
+            watcher
+        })
+    }
 }
 
 /// A [`Resource`] that hold (repeatable) functions capable of producing new [`AssetReader`](crate::io::AssetReader) and [`AssetWriter`](crate::io::AssetWriter) instances
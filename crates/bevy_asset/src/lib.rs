@@ -184,6 +184,7 @@ mod loader_builders;
 mod path;
 mod reflect;
 mod render_asset;
+mod residency;
 mod server;
 
 pub use assets::*;
@@ -201,6 +202,7 @@ pub use loader_builders::{
 pub use path::*;
 pub use reflect::*;
 pub use render_asset::*;
+pub use residency::*;
 pub use server::*;
 
 /// Rusty Object Notation, a crate used to serialize and deserialize bevy assets.
@@ -258,6 +260,15 @@ pub struct AssetPlugin {
     pub mode: AssetMode,
     /// How/If asset meta files should be checked.
     pub meta_check: AssetMetaCheck,
+    /// If set, limits the number of assets loaded at the same time. The loads over the limit are
+    /// queued, and start by [`LoadPriority`] as earlier loads complete. Loads whose handles are all
+    /// dropped while queued never start. Loads that already started stop once the asset has been
+    /// read, or as soon as their loader checks [`LoadContext::is_cancelled`], and their result
+    /// is discarded.
+    ///
+    /// Defaults to [`None`], which doesn't limit concurrent loads: every load starts as soon as
+    /// it's requested, so [`LoadPriority`] has no effect. Set a limit to make use of it.
+    pub max_concurrent_loads: Option<usize>,
 }
 
 /// Controls whether or not assets are pre-processed before being loaded.
@@ -311,6 +322,7 @@ impl Default for AssetPlugin {
             processed_file_path: Self::DEFAULT_PROCESSED_FILE_PATH.to_string(),
             watch_for_changes_override: None,
             meta_check: AssetMetaCheck::default(),
+            max_concurrent_loads: None,
         }
     }
 }
@@ -385,6 +397,9 @@ impl Plugin for AssetPlugin {
                 }
             }
         }
+        app.world()
+            .resource::<AssetServer>()
+            .set_max_concurrent_loads(self.max_concurrent_loads);
         app.insert_resource(embedded)
             .init_asset::<LoadedFolder>()
             .init_asset::<LoadedUntypedAsset>()
@@ -512,6 +527,14 @@ pub trait AssetApp {
     /// Preregisters a loader for the given extensions, that will block asset loads until a real loader
     /// is registered.
     fn preregister_asset_loader<L: AssetLoader>(&mut self, extensions: &[&str]) -> &mut Self;
+    /// Keeps the assets of type `A` resident within a memory budget, by:
+    /// * Inserting an [`AssetResidency`] resource with the given `budget` in bytes, and the `size`
+    ///   of each asset in bytes
+    /// * Initializing the [`AssetEvicted`] event resource for the [`Asset`]
+    /// * Adding a system evicting the least recently used assets only held by the [`AssetResidency`]
+    ///   when the assets take more bytes than the budget
+    fn init_asset_residency<A: Asset>(&mut self, budget: usize, size: fn(&A) -> usize)
+        -> &mut Self;
 }
 
 impl AssetApp for App {
@@ -615,6 +638,19 @@ impl AssetApp for App {
             .preregister_loader::<L>(extensions);
         self
     }
+
+    fn init_asset_residency<A: Asset>(
+        &mut self,
+        budget: usize,
+        size: fn(&A) -> usize,
+    ) -> &mut Self {
+        self.insert_resource(AssetResidency::new(budget, size))
+            .add_event::<AssetEvicted<A>>()
+            .add_systems(
+                PostUpdate,
+                AssetResidency::<A>::evict_unused_assets.after(AssetEvents),
+            )
+    }
 }
 
 /// A system set that holds all "track asset" operations.
@@ -638,8 +674,9 @@ mod tests {
             AssetReader, AssetReaderError, AssetSource, AssetSourceId, Reader,
         },
         loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, DuplicateLabelAssetError, LoadState,
+        Asset, AssetApp, AssetEvent, AssetEvicted, AssetId, AssetLoadError, AssetLoadFailedEvent,
+        AssetPath, AssetPlugin, AssetResidency, AssetServer, Assets, DuplicateLabelAssetError,
+        LoadPriority, LoadState,
     };
     use alloc::{
         boxed::Box,
@@ -1823,6 +1860,173 @@ mod tests {
         });
     }
 
+    #[test]
+    fn evicts_unused_assets_over_budget() {
+        let mut app = App::new();
+
+        let dir = Dir::default();
+        for name in ["a", "b", "c"] {
+            dir.insert_asset_text(
+                Path::new(&format!("{name}.cool.ron")),
+                &format!(
+                    r#"(
+    text: "{name}",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#
+                ),
+            );
+        }
+
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader)
+        .init_asset_residency::<CoolText>(2, |text| text.text.len());
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let load = |app: &mut App, path: &'static str| {
+            let handle = app
+                .world_mut()
+                .resource_mut::<AssetResidency<CoolText>>()
+                .load(&asset_server, path);
+            let id = handle.id();
+            run_app_until(app, |world| get::<CoolText>(world, id).map(|_| ()));
+            handle
+        };
+        let evicted = |app: &mut App| {
+            app.world_mut()
+                .resource_mut::<Events<AssetEvicted<CoolText>>>()
+                .drain()
+                .map(|event| event.path.unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // Assets in use are never evicted, even over budget.
+        let a = load(&mut app, "a.cool.ron");
+        let b = load(&mut app, "b.cool.ron");
+        let c = load(&mut app, "c.cool.ron");
+        let (a_id, b_id, c_id) = (a.id(), b.id(), c.id());
+        app.update();
+        assert!(evicted(&mut app).is_empty());
+
+        // Once unused, the least recently used asset is evicted until the budget is met.
+        drop(a);
+        app.update();
+        assert_eq!(evicted(&mut app), ["a.cool.ron"]);
+        drop((b, c));
+        app.update();
+        app.update();
+        assert!(evicted(&mut app).is_empty());
+        let residency = app.world().resource::<AssetResidency<CoolText>>();
+        assert!(!residency.contains(a_id) && residency.contains(b_id) && residency.contains(c_id));
+        assert_eq!(residency.resident_bytes(), 2);
+        assert!(get::<CoolText>(app.world(), a_id).is_none());
+        assert_eq!(app.world().resource::<Assets<CoolText>>().len(), 2);
+
+        // Evicted assets are reloaded on demand, evicting other unused assets.
+        app.world_mut()
+            .resource_mut::<AssetResidency<CoolText>>()
+            .touch(b_id);
+        let a = load(&mut app, "a.cool.ron");
+        app.update();
+        assert_eq!(evicted(&mut app), ["c.cool.ron"]);
+        assert_eq!(get::<CoolText>(app.world(), a.id()).unwrap().text, "a");
+        assert!(!app
+            .world()
+            .resource::<AssetResidency<CoolText>>()
+            .contains(c_id));
+    }
+
+    #[test]
+    fn loads_by_priority_and_cancels_dropped_loads() {
+        let mut app = App::new();
+
+        let dir = Dir::default();
+        for name in ["a", "b", "c", "d"] {
+            dir.insert_asset_text(
+                Path::new(&format!("{name}.cool.ron")),
+                &format!(
+                    r#"(
+    text: "{name}",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#
+                ),
+            );
+        }
+
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin {
+                max_concurrent_loads: Some(0),
+                ..Default::default()
+            },
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader)
+        .init_resource::<StoredEvents>()
+        .add_systems(Update, store_asset_events);
+
+        // No load can start until the limit is raised, so they are all queued.
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a = asset_server.load_with_priority::<CoolText>("a.cool.ron", LoadPriority::Low);
+        let b = asset_server.load::<CoolText>("b.cool.ron");
+        let c = asset_server.load_with_settings_and_priority::<CoolText, ()>(
+            "c.cool.ron",
+            |_| {},
+            LoadPriority::High,
+        );
+        let d = asset_server.load_with_priority::<CoolText>("d.cool.ron", LoadPriority::High);
+        let d_id = d.id();
+        drop(d);
+        // Requesting a queued asset again with a higher priority moves it up the queue.
+        let _ = asset_server.load_with_priority::<CoolText>("a.cool.ron", LoadPriority::High);
+        app.update();
+        asset_server.set_max_concurrent_loads(Some(1));
+
+        run_app_until(&mut app, |world| {
+            [&a, &b, &c]
+                .iter()
+                .all(|handle| get::<CoolText>(world, handle.id()).is_some())
+                .then_some(())
+        });
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(get::<CoolText>(app.world(), d_id).is_none());
+
+        let loaded = app
+            .world()
+            .resource::<StoredEvents>()
+            .0
+            .iter()
+            .filter_map(|event| match event {
+                AssetEvent::LoadedWithDependencies { id } => Some(*id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(loaded, [c.id(), a.id(), b.id()]);
+    }
+
     // validate the Asset derive macro for various asset types
     #[derive(Asset, TypePath)]
     pub struct TestAsset;
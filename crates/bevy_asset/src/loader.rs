@@ -3,12 +3,13 @@ use crate::{
     loader_builders::{Deferred, NestedLoader, StaticTyped},
     meta::{AssetHash, AssetMeta, AssetMetaDyn, ProcessedInfoMinimal, Settings},
     path::AssetPath,
-    Asset, AssetLoadError, AssetServer, AssetServerMode, Assets, Handle, UntypedAssetId,
-    UntypedHandle,
+    Asset, AssetLoadError, AssetServer, AssetServerMode, Assets, Handle, StrongHandle,
+    UntypedAssetId, UntypedHandle,
 };
 use alloc::{
     boxed::Box,
     string::{String, ToString},
+    sync::Weak,
     vec::Vec,
 };
 use atomicow::CowArc;
@@ -386,6 +387,9 @@ pub struct LoadContext<'a> {
     /// Direct dependencies used by this loader.
     pub(crate) loader_dependencies: HashMap<AssetPath<'static>, AssetHash>,
     pub(crate) labeled_assets: HashMap<CowArc<'static, str>, LabeledAsset>,
+    /// The handle of the asset being loaded, if the load is cancelled once it has no strong
+    /// handles left.
+    pub(crate) cancel_handle: Option<Weak<StrongHandle>>,
 }
 
 impl<'a> LoadContext<'a> {
@@ -404,9 +408,19 @@ impl<'a> LoadContext<'a> {
             dependencies: HashSet::default(),
             loader_dependencies: HashMap::default(),
             labeled_assets: HashMap::default(),
+            cancel_handle: None,
         }
     }
 
+    /// Returns `true` if every strong handle to the asset being loaded was dropped, so it is no
+    /// longer needed. The result of a cancelled load is discarded, and loaders doing expensive work
+    /// can check this to return early.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel_handle
+            .as_ref()
+            .is_some_and(|handle| handle.strong_count() == 0)
+    }
+
     /// Begins a new labeled asset load. Use the returned [`LoadContext`] to load
     /// dependencies for the new asset and call [`LoadContext::finish`] to finalize the asset load.
     /// When finished, make sure you call [`LoadContext::add_labeled_asset`] to add the results back to the parent
@@ -437,12 +451,14 @@ impl<'a> LoadContext<'a> {
     /// }
     /// ```
     pub fn begin_labeled_asset(&self) -> LoadContext {
-        LoadContext::new(
+        let mut context = LoadContext::new(
             self.asset_server,
             self.asset_path.clone(),
             self.should_load_dependencies,
             self.populate_hashes,
-        )
+        );
+        context.cancel_handle = self.cancel_handle.clone();
+        context
     }
 
     /// Creates a new [`LoadContext`] for the given `label`. The `load` function is responsible for loading an [`Asset`] of
@@ -619,6 +635,7 @@ impl<'a> LoadContext<'a> {
                 reader,
                 false,
                 self.populate_hashes,
+                self.cancel_handle.clone(),
             )
             .await
             .map_err(|error| LoadDirectError {
@@ -5,7 +5,8 @@ use crate::{
     io::Reader,
     meta::{meta_transform_settings, AssetMetaDyn, MetaTransform, Settings},
     Asset, AssetLoadError, AssetPath, CompleteErasedLoadedAsset, CompleteLoadedAsset,
-    ErasedAssetLoader, Handle, LoadContext, LoadDirectError, LoadedUntypedAsset, UntypedHandle,
+    ErasedAssetLoader, Handle, LoadContext, LoadDirectError, LoadPriority, LoadedUntypedAsset,
+    UntypedHandle,
 };
 use alloc::{borrow::ToOwned, boxed::Box, sync::Arc};
 use core::any::TypeId;
@@ -118,6 +119,7 @@ impl ReaderRef<'_> {
 pub struct NestedLoader<'ctx, 'builder, T, M> {
     load_context: &'builder mut LoadContext<'ctx>,
     meta_transform: Option<MetaTransform>,
+    priority: LoadPriority,
     typing: T,
     mode: M,
 }
@@ -169,6 +171,7 @@ impl<'ctx, 'builder> NestedLoader<'ctx, 'builder, StaticTyped, Deferred> {
         NestedLoader {
             load_context,
             meta_transform: None,
+            priority: LoadPriority::default(),
             typing: StaticTyped(()),
             mode: Deferred(()),
         }
@@ -203,6 +206,19 @@ impl<'ctx, 'builder, T: sealed::Typing, M: sealed::Mode> NestedLoader<'ctx, 'bui
         self.with_transform(move |meta| meta_transform_settings(meta, &settings))
     }
 
+    /// Configure the [`LoadPriority`] of the asset load.
+    ///
+    /// This only affects [`deferred`] loads, as [`immediate`] loads happen as part of the load of
+    /// the current asset.
+    ///
+    /// [`deferred`]: Self::deferred
+    /// [`immediate`]: Self::immediate
+    #[must_use]
+    pub fn with_priority(mut self, priority: LoadPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
     // convert between `T`s
 
     /// When [`load`]ing, you must pass in the asset type as a type parameter
@@ -219,6 +235,7 @@ impl<'ctx, 'builder, T: sealed::Typing, M: sealed::Mode> NestedLoader<'ctx, 'bui
         NestedLoader {
             load_context: self.load_context,
             meta_transform: self.meta_transform,
+            priority: self.priority,
             typing: StaticTyped(()),
             mode: self.mode,
         }
@@ -236,6 +253,7 @@ impl<'ctx, 'builder, T: sealed::Typing, M: sealed::Mode> NestedLoader<'ctx, 'bui
         NestedLoader {
             load_context: self.load_context,
             meta_transform: self.meta_transform,
+            priority: self.priority,
             typing: DynamicTyped { asset_type_id },
             mode: self.mode,
         }
@@ -250,6 +268,7 @@ impl<'ctx, 'builder, T: sealed::Typing, M: sealed::Mode> NestedLoader<'ctx, 'bui
         NestedLoader {
             load_context: self.load_context,
             meta_transform: self.meta_transform,
+            priority: self.priority,
             typing: UnknownTyped(()),
             mode: self.mode,
         }
@@ -265,6 +284,7 @@ impl<'ctx, 'builder, T: sealed::Typing, M: sealed::Mode> NestedLoader<'ctx, 'bui
         NestedLoader {
             load_context: self.load_context,
             meta_transform: self.meta_transform,
+            priority: self.priority,
             typing: self.typing,
             mode: Deferred(()),
         }
@@ -282,6 +302,7 @@ impl<'ctx, 'builder, T: sealed::Typing, M: sealed::Mode> NestedLoader<'ctx, 'bui
         NestedLoader {
             load_context: self.load_context,
             meta_transform: self.meta_transform,
+            priority: self.priority,
             typing: self.typing,
             mode: Immediate { reader: None },
         }
@@ -305,9 +326,12 @@ impl NestedLoader<'_, '_, StaticTyped, Deferred> {
     pub fn load<'c, A: Asset>(self, path: impl Into<AssetPath<'c>>) -> Handle<A> {
         let path = path.into().to_owned();
         let handle = if self.load_context.should_load_dependencies {
-            self.load_context
-                .asset_server
-                .load_with_meta_transform(path, self.meta_transform, ())
+            self.load_context.asset_server.load_with_meta_transform(
+                path,
+                self.meta_transform,
+                (),
+                self.priority,
+            )
         } else {
             self.load_context
                 .asset_server
@@ -336,6 +360,7 @@ impl NestedLoader<'_, '_, DynamicTyped, Deferred> {
                     self.typing.asset_type_id,
                     self.meta_transform,
                     (),
+                    self.priority,
                 )
         } else {
             self.load_context
@@ -361,7 +386,7 @@ impl NestedLoader<'_, '_, UnknownTyped, Deferred> {
         let handle = if self.load_context.should_load_dependencies {
             self.load_context
                 .asset_server
-                .load_unknown_type_with_meta_transform(path, self.meta_transform)
+                .load_unknown_type_with_meta_transform(path, self.meta_transform, self.priority)
         } else {
             self.load_context
                 .asset_server
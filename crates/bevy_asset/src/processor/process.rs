@@ -311,7 +311,15 @@ impl<'a> ProcessContext<'a> {
         let loader = server.get_asset_loader_with_type_name(loader_name).await?;
         let mut reader = SliceReader::new(self.asset_bytes);
         let complete_asset = server
-            .load_with_meta_loader_and_reader(self.path, &meta, &*loader, &mut reader, false, true)
+            .load_with_meta_loader_and_reader(
+                self.path,
+                &meta,
+                &*loader,
+                &mut reader,
+                false,
+                true,
+                None,
+            )
             .await?;
         for (path, full_hash) in &complete_asset.asset.loader_dependencies {
             self.new_processed_info
@@ -0,0 +1,200 @@
+use crate::{Asset, AssetEvent, AssetEvicted, AssetId, AssetPath, AssetServer, Assets, Handle};
+use alloc::{sync::Arc, vec::Vec};
+use bevy_ecs::{
+    event::{EventReader, EventWriter},
+    resource::Resource,
+    system::{Res, ResMut},
+};
+use bevy_platform_support::collections::HashMap;
+
+/// Keeps the assets of type `A` resident in memory after they stop being used, within a byte
+/// budget.
+///
+/// [`Assets`] drops an asset as soon as its last strong [`Handle`] is dropped. The assets loaded
+/// with [`AssetResidency::load`] (or retained with [`AssetResidency::retain`]) are also held by a
+/// "cache handle", so they stay loaded while the app doesn't use them and can be reused without
+/// loading them again.
+///
+/// Each frame, once the assets of type `A` take more bytes than the budget, the assets that are
+/// only held by their cache handle are evicted, least recently used first, and an
+/// [`AssetEvicted`] event is sent for each of them. An evicted asset is reloaded by the
+/// [`AssetServer`] the next time it is loaded.
+///
+/// Residency is opt-in for each asset type, with
+/// [`AssetApp::init_asset_residency`](crate::AssetApp::init_asset_residency).
+#[derive(Resource)]
+pub struct AssetResidency<A: Asset> {
+    budget: usize,
+    size: fn(&A) -> usize,
+    entries: HashMap<AssetId<A>, ResidentAsset<A>>,
+    frame: u64,
+    /// The size of each asset of type `A`, kept up to date from the [`AssetEvent`]s.
+    sizes: HashMap<AssetId<A>, usize>,
+    resident_bytes: usize,
+}
+
+struct ResidentAsset<A: Asset> {
+    handle: Handle<A>,
+    last_used: u64,
+}
+
+impl<A: Asset> AssetResidency<A> {
+    /// Creates a new [`AssetResidency`] keeping the assets of type `A` within `budget` bytes,
+    /// where the `size` of each asset is given in bytes.
+    pub fn new(budget: usize, size: fn(&A) -> usize) -> Self {
+        Self {
+            budget,
+            size,
+            entries: HashMap::default(),
+            frame: 0,
+            sizes: HashMap::default(),
+            resident_bytes: 0,
+        }
+    }
+
+    /// Returns the budget in bytes of the assets of type `A`.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Sets the budget in bytes of the assets of type `A`.
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+    }
+
+    /// Returns the size in bytes of the given `asset`.
+    pub fn size(&self, asset: &A) -> usize {
+        (self.size)(asset)
+    }
+
+    /// Returns the total size in bytes of the assets of type `A`, including those that aren't
+    /// held by this cache.
+    ///
+    /// This is updated from the [`AssetEvent`]s each time
+    /// [`evict_unused_assets`](Self::evict_unused_assets) runs.
+    pub fn resident_bytes(&self) -> usize {
+        self.resident_bytes
+    }
+
+    /// Updates the size of the asset with the given `id`, which is `None` once it is removed.
+    fn update_size(&mut self, id: AssetId<A>, size: Option<usize>) {
+        let previous = match size {
+            Some(size) => self.sizes.insert(id, size),
+            None => self.sizes.remove(&id),
+        };
+        self.resident_bytes = self.resident_bytes - previous.unwrap_or(0) + size.unwrap_or(0);
+    }
+
+    /// Loads the asset at the given `path` with the `asset_server`, keeps it resident and marks it
+    /// as used. If the asset was evicted, it is loaded again.
+    pub fn load<'a>(
+        &mut self,
+        asset_server: &AssetServer,
+        path: impl Into<AssetPath<'a>>,
+    ) -> Handle<A> {
+        let handle = asset_server.load(path);
+        self.retain(handle.clone());
+        handle
+    }
+
+    /// Keeps the asset of the given strong `handle` resident, and marks it as used. Weak handles
+    /// are ignored.
+    pub fn retain(&mut self, handle: Handle<A>) {
+        if handle.is_weak() {
+            return;
+        }
+        let last_used = self.frame;
+        self.entries
+            .entry(handle.id())
+            .and_modify(|entry| entry.last_used = last_used)
+            .or_insert(ResidentAsset { handle, last_used });
+    }
+
+    /// Marks the asset with the given `id` as used, delaying its eviction. Returns `false` if the
+    /// asset isn't held by this cache.
+    pub fn touch(&mut self, id: impl Into<AssetId<A>>) -> bool {
+        let frame = self.frame;
+        self.entries
+            .get_mut(&id.into())
+            .map(|entry| entry.last_used = frame)
+            .is_some()
+    }
+
+    /// Stops keeping the asset with the given `id` resident, returning its cache handle.
+    pub fn release(&mut self, id: impl Into<AssetId<A>>) -> Option<Handle<A>> {
+        self.entries.remove(&id.into()).map(|entry| entry.handle)
+    }
+
+    /// Returns `true` if the asset with the given `id` is held by this cache.
+    pub fn contains(&self, id: impl Into<AssetId<A>>) -> bool {
+        self.entries.contains_key(&id.into())
+    }
+
+    /// Returns the number of assets held by this cache.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if this cache holds no asset.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// A system that evicts the least recently used assets only held by the [`AssetResidency`],
+    /// until the assets of type `A` fit in the budget.
+    pub fn evict_unused_assets(
+        mut residency: ResMut<Self>,
+        assets: Res<Assets<A>>,
+        mut events: EventReader<AssetEvent<A>>,
+        mut evicted: EventWriter<AssetEvicted<A>>,
+    ) {
+        residency.frame += 1;
+        let residency = residency.as_mut();
+        for event in events.read() {
+            match *event {
+                AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                    let size = assets.get(id).map(residency.size);
+                    residency.update_size(id, size);
+                }
+                AssetEvent::Removed { id } => residency.update_size(id, None),
+                AssetEvent::Unused { .. } | AssetEvent::LoadedWithDependencies { .. } => {}
+            }
+        }
+
+        // Assets still held by other handles are in use, so they are the most recently used.
+        let over_budget = residency.resident_bytes > residency.budget;
+        let mut unused = Vec::new();
+        for (&id, entry) in &mut residency.entries {
+            let in_use = match &entry.handle {
+                Handle::Strong(handle) => Arc::strong_count(handle) > 1,
+                Handle::Weak(_) => false,
+            };
+            if in_use {
+                entry.last_used = residency.frame;
+            } else if over_budget {
+                if let Some(&size) = residency.sizes.get(&id) {
+                    unused.push((entry.last_used, id, size));
+                }
+            }
+        }
+        if !over_budget {
+            return;
+        }
+
+        // Evicted assets are removed from the running total once they are dropped.
+        let mut resident_bytes = residency.resident_bytes;
+
+        unused.sort_unstable();
+        for (_, id, size) in unused {
+            if resident_bytes <= residency.budget {
+                break;
+            }
+            let entry = residency.entries.remove(&id).unwrap();
+            resident_bytes = resident_bytes.saturating_sub(size);
+            evicted.write(AssetEvicted {
+                id,
+                path: entry.handle.path().cloned(),
+            });
+        }
+    }
+}
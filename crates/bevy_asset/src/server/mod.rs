@@ -1,5 +1,6 @@
 mod info;
 mod loaders;
+mod scheduler;
 
 use crate::{
     folder::LoadedFolder,
@@ -15,13 +16,13 @@ use crate::{
     path::AssetPath,
     Asset, AssetEvent, AssetHandleProvider, AssetId, AssetLoadFailedEvent, AssetMetaCheck, Assets,
     CompleteErasedLoadedAsset, DeserializeMetaError, ErasedLoadedAsset, Handle, LoadedUntypedAsset,
-    UntypedAssetId, UntypedAssetLoadFailedEvent, UntypedHandle,
+    StrongHandle, UntypedAssetId, UntypedAssetLoadFailedEvent, UntypedHandle,
 };
 use alloc::{borrow::ToOwned, boxed::Box, vec, vec::Vec};
 use alloc::{
     format,
     string::{String, ToString},
-    sync::Arc,
+    sync::{Arc, Weak},
 };
 use atomicow::CowArc;
 use bevy_ecs::prelude::*;
@@ -34,10 +35,13 @@ use futures_lite::{FutureExt, StreamExt};
 use info::*;
 use loaders::*;
 use parking_lot::{RwLock, RwLockWriteGuard};
+use scheduler::LoadScheduler;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tracing::{error, info};
 
+pub use scheduler::LoadPriority;
+
 /// Loads and tracks the state of [`Asset`] values from a configured [`AssetReader`](crate::io::AssetReader).
 /// This can be used to kick off new asset loads and retrieve their current load states.
 ///
@@ -67,6 +71,7 @@ pub(crate) struct AssetServerData {
     sources: AssetSources,
     mode: AssetServerMode,
     meta_check: AssetMetaCheck,
+    scheduler: LoadScheduler,
 }
 
 /// The "asset mode" the server is currently in.
@@ -127,6 +132,7 @@ impl AssetServer {
                 asset_event_receiver,
                 loaders,
                 infos: RwLock::new(infos),
+                scheduler: LoadScheduler::default(),
             }),
         }
     }
@@ -310,7 +316,36 @@ impl AssetServer {
     /// The asset load will fail and an error will be printed to the logs if the asset stored at `path` is not of type `A`.
     #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
     pub fn load<'a, A: Asset>(&self, path: impl Into<AssetPath<'a>>) -> Handle<A> {
-        self.load_with_meta_transform(path, None, ())
+        self.load_with_meta_transform(path, None, (), LoadPriority::default())
+    }
+
+    /// Begins loading an [`Asset`] of type `A` stored at `path`, with the given [`LoadPriority`].
+    ///
+    /// When the number of concurrent loads is limited with [`AssetServer::set_max_concurrent_loads`],
+    /// loads with a higher priority start first, and requesting a queued asset again with a higher
+    /// priority moves it up the queue. If every strong handle to the asset is dropped before its
+    /// load starts, the load is cancelled. Without a limit, which is the default, the load starts
+    /// right away and this is the same as [`AssetServer::load`].
+    ///
+    /// See [`AssetServer::load`] for more details.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_priority<'a, A: Asset>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        priority: LoadPriority,
+    ) -> Handle<A> {
+        self.load_with_meta_transform(path, None, (), priority)
+    }
+
+    /// Limits the number of assets loaded at the same time to `max_concurrent_loads`, or removes
+    /// the limit with `None`. The loads over the limit wait for their turn, by [`LoadPriority`].
+    /// A limit of `0` pauses all loads that haven't started yet.
+    ///
+    /// See [`AssetPlugin::max_concurrent_loads`](crate::AssetPlugin::max_concurrent_loads).
+    pub fn set_max_concurrent_loads(&self, max_concurrent_loads: Option<usize>) {
+        self.data
+            .scheduler
+            .set_max_concurrent_loads(max_concurrent_loads);
     }
 
     /// Begins loading an [`Asset`] of type `A` stored at `path` while holding a guard item.
@@ -334,7 +369,7 @@ impl AssetServer {
         path: impl Into<AssetPath<'a>>,
         guard: G,
     ) -> Handle<A> {
-        self.load_with_meta_transform(path, None, guard)
+        self.load_with_meta_transform(path, None, guard, LoadPriority::default())
     }
 
     /// Begins loading an [`Asset`] of type `A` stored at `path`. The given `settings` function will override the asset's
@@ -346,7 +381,32 @@ impl AssetServer {
         path: impl Into<AssetPath<'a>>,
         settings: impl Fn(&mut S) + Send + Sync + 'static,
     ) -> Handle<A> {
-        self.load_with_meta_transform(path, Some(loader_settings_meta_transform(settings)), ())
+        self.load_with_meta_transform(
+            path,
+            Some(loader_settings_meta_transform(settings)),
+            (),
+            LoadPriority::default(),
+        )
+    }
+
+    /// Begins loading an [`Asset`] of type `A` stored at `path` with the given [`LoadPriority`].
+    /// The given `settings` function will override the asset's [`AssetLoader`] settings, like with
+    /// [`AssetServer::load_with_settings`].
+    ///
+    /// See [`AssetServer::load_with_priority`] for the effect of the priority.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_settings_and_priority<'a, A: Asset, S: Settings>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        settings: impl Fn(&mut S) + Send + Sync + 'static,
+        priority: LoadPriority,
+    ) -> Handle<A> {
+        self.load_with_meta_transform(
+            path,
+            Some(loader_settings_meta_transform(settings)),
+            (),
+            priority,
+        )
     }
 
     /// Begins loading an [`Asset`] of type `A` stored at `path` while holding a guard item.
@@ -365,7 +425,12 @@ impl AssetServer {
         settings: impl Fn(&mut S) + Send + Sync + 'static,
         guard: G,
     ) -> Handle<A> {
-        self.load_with_meta_transform(path, Some(loader_settings_meta_transform(settings)), guard)
+        self.load_with_meta_transform(
+            path,
+            Some(loader_settings_meta_transform(settings)),
+            guard,
+            LoadPriority::default(),
+        )
     }
 
     pub(crate) fn load_with_meta_transform<'a, A: Asset, G: Send + Sync + 'static>(
@@ -373,6 +438,7 @@ impl AssetServer {
         path: impl Into<AssetPath<'a>>,
         meta_transform: Option<MetaTransform>,
         guard: G,
+        priority: LoadPriority,
     ) -> Handle<A> {
         let path = path.into().into_owned();
         let mut infos = self.data.infos.write();
@@ -383,7 +449,11 @@ impl AssetServer {
         );
 
         if should_load {
-            self.spawn_load_task(handle.clone().untyped(), path, infos, guard);
+            self.spawn_load_task(handle.clone().untyped(), path, infos, guard, priority);
+        } else {
+            self.data
+                .scheduler
+                .raise_priority(handle.id().untyped(), priority);
         }
 
         handle
@@ -395,6 +465,7 @@ impl AssetServer {
         type_id: TypeId,
         meta_transform: Option<MetaTransform>,
         guard: G,
+        priority: LoadPriority,
     ) -> UntypedHandle {
         let path = path.into().into_owned();
         let mut infos = self.data.infos.write();
@@ -407,7 +478,9 @@ impl AssetServer {
         );
 
         if should_load {
-            self.spawn_load_task(handle.clone(), path, infos, guard);
+            self.spawn_load_task(handle.clone(), path, infos, guard, priority);
+        } else {
+            self.data.scheduler.raise_priority(handle.id(), priority);
         }
 
         handle
@@ -419,19 +492,34 @@ impl AssetServer {
         path: AssetPath<'static>,
         infos: RwLockWriteGuard<AssetInfos>,
         guard: G,
+        priority: LoadPriority,
     ) {
         // drop the lock on `AssetInfos` before spawning a task that may block on it in single-threaded
         #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
         drop(infos);
 
-        let owned_handle = handle.clone();
+        // only hold a weak reference while waiting, so the load is cancelled if the handle is dropped
+        let weak_handle = match &handle {
+            UntypedHandle::Strong(handle) => Arc::downgrade(handle),
+            UntypedHandle::Weak(_) => {
+                unreachable!("loads are always requested with a strong handle")
+            }
+        };
+        let scheduled = self.data.scheduler.schedule(handle.id(), priority);
         let server = self.clone();
         let task = IoTaskPool::get().spawn(async move {
-            if let Err(err) = server
-                .load_internal(Some(owned_handle), path, false, None)
-                .await
-            {
-                error!("{}", err);
+            let _permit = scheduled.await;
+            // the load is cancelled if every strong handle was dropped while it was queued
+            if let Some(owned_handle) = weak_handle.upgrade() {
+                let result = server
+                    .load_internal(Some(UntypedHandle::Strong(owned_handle)), path, false, None)
+                    .await;
+                // loaders may fail on purpose once the load is cancelled
+                if let Err(err) = result {
+                    if weak_handle.strong_count() > 0 {
+                        error!("{}", err);
+                    }
+                }
             }
             drop(guard);
         });
@@ -462,6 +550,7 @@ impl AssetServer {
         &self,
         path: impl Into<AssetPath<'a>>,
         meta_transform: Option<MetaTransform>,
+        priority: LoadPriority,
     ) -> Handle<LoadedUntypedAsset> {
         let path = path.into().into_owned();
         let untyped_source = AssetSourceId::Name(match path.source() {
@@ -482,12 +571,24 @@ impl AssetServer {
         drop(infos);
 
         if !should_load {
+            self.data
+                .scheduler
+                .raise_priority(handle.id().untyped(), priority);
             return handle;
         }
         let id = handle.id().untyped();
+        let weak_handle = match &handle {
+            Handle::Strong(handle) => Arc::downgrade(handle),
+            Handle::Weak(_) => unreachable!("loads are always requested with a strong handle"),
+        };
 
+        let scheduled = self.data.scheduler.schedule(id, priority);
         let server = self.clone();
         let task = IoTaskPool::get().spawn(async move {
+            let _permit = scheduled.await;
+            if weak_handle.strong_count() == 0 {
+                return;
+            }
             let path_clone = path.clone();
             match server.load_untyped_async(path).await {
                 Ok(handle) => server.send_asset_event(InternalAssetEvent::Loaded {
@@ -540,7 +641,7 @@ impl AssetServer {
     /// required to figure out the asset type before a handle can be created.
     #[must_use = "not using the returned strong handle may result in the unexpected release of the assets"]
     pub fn load_untyped<'a>(&self, path: impl Into<AssetPath<'a>>) -> Handle<LoadedUntypedAsset> {
-        self.load_unknown_type_with_meta_transform(path, None)
+        self.load_unknown_type_with_meta_transform(path, None, LoadPriority::default())
     }
 
     /// Performs an async asset load.
@@ -578,7 +679,18 @@ impl AssetServer {
         }
         // downgrade the input handle so we don't keep the asset alive just because we're loading it
         // note we can't just pass a weak handle in, as only strong handles contain the asset meta transform
+        let weak_input_handle = match &input_handle {
+            Some(UntypedHandle::Strong(handle)) => Some(Arc::downgrade(handle)),
+            _ => None,
+        };
         input_handle = input_handle.map(|h| h.clone_weak());
+        // if every strong handle was dropped while the meta was read, the asset is no longer needed
+        match (&weak_input_handle, &input_handle) {
+            (Some(weak), Some(handle)) if weak.strong_count() == 0 => return Ok(handle.clone()),
+            _ => {}
+        }
+        // the labeled assets of a load may still be needed when the requested one isn't
+        let cancel_handle = weak_input_handle.filter(|_| path.label().is_none());
 
         // This contains Some(UntypedHandle), if it was retrievable
         // If it is None, that is because it was _not_ retrievable, due to
@@ -656,9 +768,13 @@ impl AssetServer {
                 &mut *reader,
                 true,
                 false,
+                cancel_handle.clone(),
             )
             .await
         {
+            // every strong handle was dropped while the loader ran, so its result is discarded,
+            // including errors of loaders that returned early after checking for cancellation
+            _ if cancel_handle.is_some_and(|handle| handle.strong_count() == 0) => Ok(base_handle),
             Ok(loaded_asset) => {
                 let final_handle = if let Some(label) = path.label_cow() {
                     match loaded_asset.labeled_assets.get(&label) {
@@ -1333,11 +1449,13 @@ impl AssetServer {
         reader: &mut dyn Reader,
         load_dependencies: bool,
         populate_hashes: bool,
+        cancel_handle: Option<Weak<StrongHandle>>,
     ) -> Result<CompleteErasedLoadedAsset, AssetLoadError> {
         // TODO: experiment with this
         let asset_path = asset_path.clone_owned();
-        let load_context =
+        let mut load_context =
             LoadContext::new(self, asset_path.clone(), load_dependencies, populate_hashes);
+        load_context.cancel_handle = cancel_handle;
         AssertUnwindSafe(loader.load(reader, meta, load_context))
             .catch_unwind()
             .await
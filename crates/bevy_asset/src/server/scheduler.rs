@@ -0,0 +1,306 @@
+use crate::UntypedAssetId;
+use alloc::{collections::BinaryHeap, sync::Arc};
+use bevy_platform_support::collections::HashMap;
+use core::{
+    cmp::Ordering,
+    future::Future,
+    pin::Pin,
+    sync::atomic::{self, AtomicBool},
+    task::{Context, Poll, Waker},
+};
+use parking_lot::Mutex;
+
+/// The priority of an asset load. When the number of concurrent loads is limited with
+/// [`AssetPlugin::max_concurrent_loads`](crate::AssetPlugin::max_concurrent_loads), queued loads
+/// with a higher priority start first, and loads with the same priority start in the order they
+/// were requested. Requesting a queued asset again with a higher priority moves its load up the
+/// queue.
+///
+/// Without a limit, which is the default, every load starts right away and the priority has no
+/// effect.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LoadPriority {
+    /// Loads that can wait for all other loads, like assets prefetched ahead of time.
+    Low,
+    /// The priority of loads that don't specify one.
+    #[default]
+    Normal,
+    /// Loads that should start before all other loads, like assets needed to display the next
+    /// frame.
+    High,
+}
+
+/// Limits the number of concurrent asset loads, starting queued loads by priority.
+#[derive(Clone, Default)]
+pub(crate) struct LoadScheduler {
+    state: Arc<Mutex<SchedulerState>>,
+}
+
+#[derive(Default)]
+struct SchedulerState {
+    max_concurrent_loads: Option<usize>,
+    running: usize,
+    /// May hold several entries for the same load after its priority was raised, of which all but
+    /// the first one popped are skipped.
+    queue: BinaryHeap<QueuedLoad>,
+    /// The current priority and slot of each queued load.
+    queued: HashMap<UntypedAssetId, (LoadPriority, Arc<Slot>)>,
+    requested: u64,
+}
+
+/// A load waiting for its turn to start.
+struct QueuedLoad {
+    priority: LoadPriority,
+    order: u64,
+    id: UntypedAssetId,
+    slot: Arc<Slot>,
+}
+
+/// Shared between a [`QueuedLoad`] and the [`ScheduledLoad`] future of its load.
+#[derive(Default)]
+struct Slot {
+    started: AtomicBool,
+    cancelled: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl PartialEq for QueuedLoad {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedLoad {}
+
+impl PartialOrd for QueuedLoad {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedLoad {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The load with the highest priority, then the earliest one, comes first.
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl LoadScheduler {
+    /// Sets the maximum number of loads running at the same time, or `None` for no limit.
+    pub(crate) fn set_max_concurrent_loads(&self, max_concurrent_loads: Option<usize>) {
+        let mut state = self.state.lock();
+        state.max_concurrent_loads = max_concurrent_loads;
+        state.start_queued_loads();
+    }
+
+    /// Schedules the load of the asset `id` with the given `priority`. The returned future
+    /// resolves to a [`LoadPermit`] once the load can start, and the load is running until the
+    /// permit is dropped. Dropping the future before then cancels the load.
+    pub(crate) fn schedule(&self, id: UntypedAssetId, priority: LoadPriority) -> ScheduledLoad {
+        let slot = Arc::new(Slot::default());
+        let mut state = self.state.lock();
+        state.queued.insert(id, (priority, slot.clone()));
+        state.push(id, priority, slot.clone());
+        state.start_queued_loads();
+        ScheduledLoad {
+            scheduler: Some(self.clone()),
+            id,
+            slot,
+        }
+    }
+
+    /// Raises the priority of the queued load of the asset `id` to `priority`, if it is lower.
+    pub(crate) fn raise_priority(&self, id: UntypedAssetId, priority: LoadPriority) {
+        let mut state = self.state.lock();
+        let Some((queued_priority, slot)) = state.queued.get_mut(&id) else {
+            return;
+        };
+        if *queued_priority >= priority {
+            return;
+        }
+        *queued_priority = priority;
+        let slot = slot.clone();
+        state.push(id, priority, slot);
+    }
+}
+
+impl SchedulerState {
+    fn has_capacity(&self) -> bool {
+        self.max_concurrent_loads
+            .is_none_or(|max_concurrent_loads| self.running < max_concurrent_loads)
+    }
+
+    fn push(&mut self, id: UntypedAssetId, priority: LoadPriority, slot: Arc<Slot>) {
+        let order = self.requested;
+        self.requested += 1;
+        self.queue.push(QueuedLoad {
+            priority,
+            order,
+            id,
+            slot,
+        });
+    }
+
+    fn start_queued_loads(&mut self) {
+        while self.has_capacity() {
+            let Some(load) = self.queue.pop() else {
+                return;
+            };
+            if load.slot.cancelled.load(atomic::Ordering::Acquire)
+                || load.slot.started.load(atomic::Ordering::Acquire)
+            {
+                continue;
+            }
+            self.queued.remove(&load.id);
+            self.running += 1;
+            load.slot.started.store(true, atomic::Ordering::Release);
+            if let Some(waker) = load.slot.waker.lock().take() {
+                waker.wake();
+            }
+        }
+    }
+
+    fn finish_load(&mut self) {
+        self.running -= 1;
+        self.start_queued_loads();
+    }
+}
+
+/// Marks a load as running for the [`LoadScheduler`], until it is dropped.
+pub(crate) struct LoadPermit {
+    scheduler: LoadScheduler,
+}
+
+impl Drop for LoadPermit {
+    fn drop(&mut self) {
+        self.scheduler.state.lock().finish_load();
+    }
+}
+
+/// A load scheduled with [`LoadScheduler::schedule`], resolving once the load can start.
+pub(crate) struct ScheduledLoad {
+    /// Taken when the [`LoadPermit`] is handed out.
+    scheduler: Option<LoadScheduler>,
+    id: UntypedAssetId,
+    slot: Arc<Slot>,
+}
+
+impl Future for ScheduledLoad {
+    type Output = LoadPermit;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<LoadPermit> {
+        if !self.slot.started.load(atomic::Ordering::Acquire) {
+            *self.slot.waker.lock() = Some(cx.waker().clone());
+            // The load may have started before the waker was registered.
+            if !self.slot.started.load(atomic::Ordering::Acquire) {
+                return Poll::Pending;
+            }
+        }
+        let scheduler = self
+            .scheduler
+            .take()
+            .expect("`ScheduledLoad` polled after completion");
+        Poll::Ready(LoadPermit { scheduler })
+    }
+}
+
+impl Drop for ScheduledLoad {
+    fn drop(&mut self) {
+        let Some(scheduler) = self.scheduler.take() else {
+            return;
+        };
+        // The load was cancelled before it could run. If it was started in the meantime, its
+        // place is given to the next queued load.
+        let mut state = scheduler.state.lock();
+        if self.slot.started.load(atomic::Ordering::Acquire) {
+            state.finish_load();
+        } else {
+            self.slot.cancelled.store(true, atomic::Ordering::Release);
+            if state
+                .queued
+                .get(&self.id)
+                .is_some_and(|(_, slot)| Arc::ptr_eq(slot, &self.slot))
+            {
+                state.queued.remove(&self.id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AssetIndex;
+    use bevy_tasks::block_on;
+    use futures_lite::future::poll_once;
+
+    fn id(index: u32) -> UntypedAssetId {
+        UntypedAssetId::Index {
+            type_id: core::any::TypeId::of::<()>(),
+            index: AssetIndex::from_bits(u64::from(index)),
+        }
+    }
+
+    #[test]
+    fn starts_queued_loads_by_priority() {
+        let scheduler = LoadScheduler::default();
+        scheduler.set_max_concurrent_loads(Some(1));
+        let running = block_on(scheduler.schedule(id(0), LoadPriority::Low));
+
+        let mut low = scheduler.schedule(id(1), LoadPriority::Low);
+        let mut normal = scheduler.schedule(id(2), LoadPriority::Normal);
+        let mut high = scheduler.schedule(id(3), LoadPriority::High);
+        let mut cancelled = scheduler.schedule(id(4), LoadPriority::High);
+        for load in [&mut low, &mut normal, &mut high, &mut cancelled] {
+            assert!(block_on(poll_once(load)).is_none());
+        }
+        drop(cancelled);
+
+        // Each finished load starts the queued load with the highest priority.
+        drop(running);
+        assert!(block_on(poll_once(&mut normal)).is_none());
+        let high = block_on(poll_once(&mut high)).unwrap();
+        assert!(block_on(poll_once(&mut low)).is_none());
+        drop(high);
+        let normal = block_on(poll_once(&mut normal)).unwrap();
+        assert!(block_on(poll_once(&mut low)).is_none());
+        drop(normal);
+        let low = block_on(poll_once(&mut low)).unwrap();
+
+        // Without a limit, loads start right away.
+        scheduler.set_max_concurrent_loads(None);
+        assert!(block_on(poll_once(scheduler.schedule(id(5), LoadPriority::Low))).is_some());
+        drop(low);
+        let state = scheduler.state.lock();
+        assert_eq!(state.running, 0);
+        assert!(state.queued.is_empty());
+    }
+
+    #[test]
+    fn raising_the_priority_moves_loads_up_the_queue() {
+        let scheduler = LoadScheduler::default();
+        scheduler.set_max_concurrent_loads(Some(1));
+        let running = block_on(scheduler.schedule(id(0), LoadPriority::Normal));
+
+        let mut normal = scheduler.schedule(id(1), LoadPriority::Normal);
+        let mut low = scheduler.schedule(id(2), LoadPriority::Low);
+        // Requesting a load again with a lower priority leaves it in place.
+        scheduler.raise_priority(id(1), LoadPriority::Low);
+        scheduler.raise_priority(id(2), LoadPriority::High);
+
+        drop(running);
+        assert!(block_on(poll_once(&mut normal)).is_none());
+        let low = block_on(poll_once(&mut low)).unwrap();
+        drop(low);
+        let normal = block_on(poll_once(&mut normal)).unwrap();
+        drop(normal);
+
+        // The entry left behind by the raised load is skipped.
+        let state = scheduler.state.lock();
+        assert_eq!(state.running, 0);
+        assert!(state.queue.is_empty());
+        assert!(state.queued.is_empty());
+    }
+}
@@ -83,6 +83,17 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     let on_add_path = attrs.on_add.map(|path| path.to_token_stream());
     let on_remove_path = attrs.on_remove.map(|path| path.to_token_stream());
 
+    let relationship_trait = if attrs.relationship.as_ref().is_some_and(|r| r.many) {
+        quote!(#bevy_ecs_path::relationship::ManyRelationship)
+    } else {
+        quote!(#bevy_ecs_path::relationship::Relationship)
+    };
+    let relationship_target_trait = if attrs.relationship_target.as_ref().is_some_and(|r| r.many) {
+        quote!(#bevy_ecs_path::relationship::ManyRelationshipTarget)
+    } else {
+        quote!(#bevy_ecs_path::relationship::RelationshipTarget)
+    };
+
     let on_insert_path = if relationship.is_some() {
         if attrs.on_insert.is_some() {
             return syn::Error::new(
@@ -93,7 +104,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::Relationship>::on_insert))
+        Some(quote!(<Self as #relationship_trait>::on_insert))
     } else {
         attrs.on_insert.map(|path| path.to_token_stream())
     };
@@ -108,7 +119,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::Relationship>::on_replace))
+        Some(quote!(<Self as #relationship_trait>::on_replace))
     } else if attrs.relationship_target.is_some() {
         if attrs.on_replace.is_some() {
             return syn::Error::new(
@@ -119,13 +130,14 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_replace))
+        Some(quote!(<Self as #relationship_target_trait>::on_replace))
     } else {
         attrs.on_replace.map(|path| path.to_token_stream())
     };
 
     let on_despawn_path = if attrs
         .relationship_target
+        .as_ref()
         .is_some_and(|target| target.linked_spawn)
     {
         if attrs.on_despawn.is_some() {
@@ -137,7 +149,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_despawn))
+        Some(quote!(<Self as #relationship_target_trait>::on_despawn))
     } else {
         attrs.on_despawn.map(|path| path.to_token_stream())
     };
@@ -223,7 +235,9 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         .then_some(quote! { #bevy_ecs_path::component::Immutable })
         .unwrap_or(quote! { #bevy_ecs_path::component::Mutable });
 
-    let clone_behavior = if relationship_target.is_some() {
+    let clone_behavior = if attrs.relationship_target.as_ref().is_some_and(|r| r.many) {
+        quote!(#bevy_ecs_path::component::ComponentCloneBehavior::Custom(#bevy_ecs_path::relationship::clone_many_relationship_target::<Self>))
+    } else if relationship_target.is_some() {
         quote!(#bevy_ecs_path::component::ComponentCloneBehavior::Custom(#bevy_ecs_path::relationship::clone_relationship_target::<Self>))
     } else {
         quote!(
@@ -472,11 +486,13 @@ enum RequireFunc {
 
 struct Relationship {
     relationship_target: Type,
+    many: bool,
 }
 
 struct RelationshipTarget {
     relationship: Type,
     linked_spawn: bool,
+    many: bool,
 }
 
 // values for `storage` attribute
@@ -607,10 +623,21 @@ fn hook_register_function_call(
 impl Parse for Relationship {
     fn parse(input: syn::parse::ParseStream) -> Result<Self> {
         syn::custom_keyword!(relationship_target);
+        syn::custom_keyword!(many);
         input.parse::<relationship_target>()?;
         input.parse::<Token![=]>()?;
+        let relationship_target = input.parse::<Type>()?;
+        let mut many_exists = false;
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            if input.peek(many) {
+                input.parse::<many>()?;
+                many_exists = true;
+            }
+        }
         Ok(Relationship {
-            relationship_target: input.parse::<Type>()?,
+            relationship_target,
+            many: many_exists,
         })
     }
 }
@@ -619,8 +646,10 @@ impl Parse for RelationshipTarget {
     fn parse(input: syn::parse::ParseStream) -> Result<Self> {
         let mut relationship_type: Option<Type> = None;
         let mut linked_spawn_exists = false;
+        let mut many_exists = false;
         syn::custom_keyword!(relationship);
         syn::custom_keyword!(linked_spawn);
+        syn::custom_keyword!(many);
         let mut done = false;
         loop {
             if input.peek(relationship) {
@@ -630,6 +659,9 @@ impl Parse for RelationshipTarget {
             } else if input.peek(linked_spawn) {
                 input.parse::<linked_spawn>()?;
                 linked_spawn_exists = true;
+            } else if input.peek(many) {
+                input.parse::<many>()?;
+                many_exists = true;
             } else {
                 done = true;
             }
@@ -645,6 +677,7 @@ impl Parse for RelationshipTarget {
         Ok(RelationshipTarget {
             relationship,
             linked_spawn: linked_spawn_exists,
+            many: many_exists,
         })
     }
 }
@@ -681,6 +714,29 @@ fn derive_relationship(
 
     let relationship_target = &relationship.relationship_target;
 
+    if relationship.many {
+        let collection = &field.ty;
+        return Ok(Some(quote! {
+            impl #impl_generics #bevy_ecs_path::relationship::ManyRelationship for #struct_name #type_generics #where_clause {
+                type RelationshipTarget = #relationship_target;
+                type Collection = #collection;
+
+                #[inline]
+                fn targets(&self) -> &Self::Collection {
+                    &self.#relationship_member
+                }
+
+                #[inline]
+                fn from_targets(targets: Self::Collection) -> Self {
+                    Self {
+                        #(#members: core::default::Default::default(),),*
+                        #relationship_member: targets
+                    }
+                }
+            }
+        }));
+    }
+
     Ok(Some(quote! {
         impl #impl_generics #bevy_ecs_path::relationship::Relationship for #struct_name #type_generics #where_clause {
             type RelationshipTarget = #relationship_target;
@@ -738,8 +794,13 @@ fn derive_relationship_target(
     let struct_name = &ast.ident;
     let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
     let linked_spawn = relationship_target.linked_spawn;
+    let relationship_target_trait = if relationship_target.many {
+        quote!(#bevy_ecs_path::relationship::ManyRelationshipTarget)
+    } else {
+        quote!(#bevy_ecs_path::relationship::RelationshipTarget)
+    };
     Ok(Some(quote! {
-        impl #impl_generics #bevy_ecs_path::relationship::RelationshipTarget for #struct_name #type_generics #where_clause {
+        impl #impl_generics #relationship_target_trait for #struct_name #type_generics #where_clause {
             const LINKED_SPAWN: bool = #linked_spawn;
             type Relationship = #relationship;
             type Collection = #collection;
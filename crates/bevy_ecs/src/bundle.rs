@@ -1103,7 +1103,91 @@ impl<'w> BundleInserter<'w> {
         caller: MaybeLocation,
         relationship_insert_hook_mode: RelationshipInsertHookMode,
     ) -> (EntityLocation, T::Effect) {
-        let bundle_info = self.bundle_info.as_ref();
+        // SAFETY: The caller ensures that `entity` is in the source archetype.
+        unsafe { self.trigger_before_insert(entity, insert_mode, caller) };
+        // SAFETY: The caller ensures that `location` is the location of `entity`, and the bundle
+        // is written right after.
+        let new_location = unsafe { self.move_entity(entity, location) };
+        // SAFETY: `entity` was just moved to `new_location`, and `T` matches this bundle's type.
+        let after_effect =
+            unsafe { self.write_bundle(entity, new_location, bundle, insert_mode, caller) };
+        // SAFETY: `entity` is in the target archetype, with the bundle written.
+        unsafe {
+            self.trigger_after_insert(entity, insert_mode, caller, relationship_insert_hook_mode);
+        };
+        (new_location, after_effect)
+    }
+
+    /// Inserts a clone of `bundle` into each of the `entities` as a batch.
+    ///
+    /// The `on_replace` hooks and observers run for every entity first. The entities are then
+    /// moved to the target archetype, all at once if they are all of the entities of the source
+    /// archetype, and finally the `on_add` and `on_insert` hooks and observers run for every entity.
+    ///
+    /// # Safety
+    /// The `entities` must be distinct and currently exist in the source archetype for this
+    /// inserter. `T` must match this [`BundleInfo`]'s type
+    pub(crate) unsafe fn insert_batch<T>(
+        &mut self,
+        entities: &[Entity],
+        bundle: T,
+        insert_mode: InsertMode,
+        caller: MaybeLocation,
+    ) where
+        T: DynamicBundle<Effect: NoBundleEffect> + Clone,
+    {
+        for &entity in entities {
+            // SAFETY: The caller ensures that `entity` is in the source archetype.
+            unsafe { self.trigger_before_insert(entity, insert_mode, caller) };
+        }
+
+        // SAFETY: The `entities` are distinct, so they are all of the entities of the archetype if
+        // there are as many. The bundle is written right after.
+        let moved_at_once =
+            entities.len() == self.archetype.as_ref().len() && unsafe { self.move_all_entities() };
+        if !moved_at_once {
+            for &entity in entities {
+                // SAFETY: The caller ensures that `entity` exists in the source archetype.
+                let location = unsafe { self.entities().get(entity).debug_checked_unwrap() };
+                // SAFETY: The bundle is written right after.
+                unsafe { self.move_entity(entity, location) };
+            }
+        }
+
+        for &entity in entities {
+            // SAFETY: `entity` was just moved to the target archetype.
+            let location = unsafe { self.entities().get(entity).debug_checked_unwrap() };
+            // SAFETY: `entity` was just moved to `location`, and `T` matches this bundle's type.
+            unsafe { self.write_bundle(entity, location, bundle.clone(), insert_mode, caller) };
+        }
+
+        for &entity in entities {
+            // SAFETY: `entity` is in the target archetype, with the bundle written.
+            unsafe {
+                self.trigger_after_insert(
+                    entity,
+                    insert_mode,
+                    caller,
+                    RelationshipInsertHookMode::Run,
+                );
+            };
+        }
+    }
+
+    /// Triggers the hooks and observers that run before the bundle is inserted into `entity`.
+    ///
+    /// # Safety
+    /// `entity` must currently exist in the source archetype for this inserter.
+    #[inline]
+    unsafe fn trigger_before_insert(
+        &self,
+        entity: Entity,
+        insert_mode: InsertMode,
+        caller: MaybeLocation,
+    ) {
+        if insert_mode != InsertMode::Replace {
+            return;
+        }
         let archetype_after_insert = self.archetype_after_insert.as_ref();
         let archetype = self.archetype.as_ref();
 
@@ -1113,61 +1197,45 @@ impl<'w> BundleInserter<'w> {
             // SAFETY: Mutable references do not alias and will be dropped after this block
             let mut deferred_world = self.world.into_deferred();
 
-            if insert_mode == InsertMode::Replace {
-                if archetype.has_replace_observer() {
-                    deferred_world.trigger_observers(
-                        ON_REPLACE,
-                        entity,
-                        archetype_after_insert.iter_existing(),
-                        caller,
-                    );
-                }
-                deferred_world.trigger_on_replace(
-                    archetype,
+            if archetype.has_replace_observer() {
+                deferred_world.trigger_observers(
+                    ON_REPLACE,
                     entity,
                     archetype_after_insert.iter_existing(),
                     caller,
                 );
             }
+            deferred_world.trigger_on_replace(
+                archetype,
+                entity,
+                archetype_after_insert.iter_existing(),
+                caller,
+            );
         }
+    }
 
+    /// Moves `entity` to the target archetype of this inserter, and returns its new location. The
+    /// components added by the bundle are left uninitialized.
+    ///
+    /// # Safety
+    /// `entity` must currently exist in the source archetype for this inserter. `location`
+    /// must be `entity`'s location in the archetype. The bundle must be written with
+    /// [`Self::write_bundle`] before the components of `entity` are read.
+    #[inline]
+    unsafe fn move_entity(&mut self, entity: Entity, location: EntityLocation) -> EntityLocation {
         let table = self.table.as_mut();
 
-        // SAFETY: Archetype gets borrowed when running the on_replace observers above,
-        // so this reference can only be promoted from shared to &mut down here, after they have been ran
+        // SAFETY: Archetype gets borrowed when running the on_replace observers,
+        // so this reference can only be promoted from shared to &mut after they have been ran
         let archetype = self.archetype.as_mut();
 
-        let (new_archetype, new_location, after_effect) = match &mut self.archetype_move_type {
-            ArchetypeMoveType::SameArchetype => {
-                // SAFETY: Mutable references do not alias and will be dropped after this block
-                let sparse_sets = {
-                    let world = self.world.world_mut();
-                    &mut world.storages.sparse_sets
-                };
-
-                let after_effect = bundle_info.write_components(
-                    table,
-                    sparse_sets,
-                    archetype_after_insert,
-                    archetype_after_insert.required_components.iter(),
-                    entity,
-                    location.table_row,
-                    self.change_tick,
-                    bundle,
-                    insert_mode,
-                    caller,
-                );
-
-                (archetype, location, after_effect)
-            }
+        match &mut self.archetype_move_type {
+            ArchetypeMoveType::SameArchetype => location,
             ArchetypeMoveType::NewArchetypeSameTable { new_archetype } => {
                 let new_archetype = new_archetype.as_mut();
 
                 // SAFETY: Mutable references do not alias and will be dropped after this block
-                let (sparse_sets, entities) = {
-                    let world = self.world.world_mut();
-                    (&mut world.storages.sparse_sets, &mut world.entities)
-                };
+                let entities = &mut self.world.world_mut().entities;
 
                 let result = archetype.swap_remove(location.archetype_row);
                 if let Some(swapped_entity) = result.swapped_entity {
@@ -1186,20 +1254,7 @@ impl<'w> BundleInserter<'w> {
                 }
                 let new_location = new_archetype.allocate(entity, result.table_row);
                 entities.set(entity.index(), new_location);
-                let after_effect = bundle_info.write_components(
-                    table,
-                    sparse_sets,
-                    archetype_after_insert,
-                    archetype_after_insert.required_components.iter(),
-                    entity,
-                    result.table_row,
-                    self.change_tick,
-                    bundle,
-                    insert_mode,
-                    caller,
-                );
-
-                (new_archetype, new_location, after_effect)
+                new_location
             }
             ArchetypeMoveType::NewArchetypeNewTable {
                 new_archetype,
@@ -1209,14 +1264,10 @@ impl<'w> BundleInserter<'w> {
                 let new_archetype = new_archetype.as_mut();
 
                 // SAFETY: Mutable references do not alias and will be dropped after this block
-                let (archetypes_ptr, sparse_sets, entities) = {
+                let (archetypes_ptr, entities) = {
                     let world = self.world.world_mut();
                     let archetype_ptr: *mut Archetype = world.archetypes.archetypes.as_mut_ptr();
-                    (
-                        archetype_ptr,
-                        &mut world.storages.sparse_sets,
-                        &mut world.entities,
-                    )
+                    (archetype_ptr, &mut world.entities)
                 };
                 let result = archetype.swap_remove(location.archetype_row);
                 if let Some(swapped_entity) = result.swapped_entity {
@@ -1268,24 +1319,100 @@ impl<'w> BundleInserter<'w> {
                     }
                 }
 
-                let after_effect = bundle_info.write_components(
-                    new_table,
-                    sparse_sets,
-                    archetype_after_insert,
-                    archetype_after_insert.required_components.iter(),
-                    entity,
-                    move_result.new_row,
-                    self.change_tick,
-                    bundle,
-                    insert_mode,
-                    caller,
-                );
+                new_location
+            }
+        }
+    }
 
-                (new_archetype, new_location, after_effect)
+    /// Moves every entity of the source archetype to the target archetype at once, with
+    /// [`move_archetype_entities`]. Returns `false` if they must be moved one by one instead.
+    ///
+    /// # Safety
+    /// The bundle must be written with [`Self::write_bundle`] for each moved entity before their
+    /// components are read.
+    unsafe fn move_all_entities(&mut self) -> bool {
+        let archetype = self.archetype.as_mut();
+        // SAFETY: Mutable references do not alias and will be dropped after this block
+        let entities = &mut self.world.world_mut().entities;
+        match &mut self.archetype_move_type {
+            ArchetypeMoveType::SameArchetype => false,
+            ArchetypeMoveType::NewArchetypeSameTable { new_archetype } => {
+                // SAFETY: The caller ensures that the added components are written.
+                unsafe {
+                    move_archetype_entities(archetype, new_archetype.as_mut(), None, entities)
+                }
+            }
+            ArchetypeMoveType::NewArchetypeNewTable {
+                new_archetype,
+                new_table,
+            } => {
+                let tables = Some((self.table.as_mut(), new_table.as_mut()));
+                // SAFETY: The tables are the ones of the archetypes, the target one is a superset of
+                // the source one, and the caller ensures that the added components are written.
+                unsafe {
+                    move_archetype_entities(archetype, new_archetype.as_mut(), tables, entities)
+                }
             }
+        }
+    }
+
+    /// Writes the components of `bundle` to `entity`, after it was moved to the target archetype.
+    ///
+    /// # Safety
+    /// `entity` must have been moved to `location` in the target archetype for this inserter,
+    /// without writing the bundle yet. `T` must match this [`BundleInfo`]'s type
+    #[inline]
+    unsafe fn write_bundle<T: DynamicBundle>(
+        &mut self,
+        entity: Entity,
+        location: EntityLocation,
+        bundle: T,
+        insert_mode: InsertMode,
+        caller: MaybeLocation,
+    ) -> T::Effect {
+        let bundle_info = self.bundle_info.as_ref();
+        let archetype_after_insert = self.archetype_after_insert.as_ref();
+        let table = match &mut self.archetype_move_type {
+            ArchetypeMoveType::NewArchetypeNewTable { new_table, .. } => new_table.as_mut(),
+            _ => self.table.as_mut(),
         };
+        // SAFETY: Mutable references do not alias and will be dropped after this block
+        let sparse_sets = &mut self.world.world_mut().storages.sparse_sets;
+
+        bundle_info.write_components(
+            table,
+            sparse_sets,
+            archetype_after_insert,
+            archetype_after_insert.required_components.iter(),
+            entity,
+            location.table_row,
+            self.change_tick,
+            bundle,
+            insert_mode,
+            caller,
+        )
+    }
 
-        let new_archetype = &*new_archetype;
+    /// Triggers the hooks and observers that run after the bundle is inserted into `entity`.
+    ///
+    /// # Safety
+    /// `entity` must be in the target archetype for this inserter, with the bundle written.
+    #[inline]
+    unsafe fn trigger_after_insert(
+        &self,
+        entity: Entity,
+        insert_mode: InsertMode,
+        caller: MaybeLocation,
+        relationship_insert_hook_mode: RelationshipInsertHookMode,
+    ) {
+        let archetype_after_insert = self.archetype_after_insert.as_ref();
+        let new_archetype = match &self.archetype_move_type {
+            ArchetypeMoveType::SameArchetype => self.archetype.as_ref(),
+            ArchetypeMoveType::NewArchetypeSameTable { new_archetype }
+            | ArchetypeMoveType::NewArchetypeNewTable { new_archetype, .. } => {
+                new_archetype.as_ref()
+            }
+        };
         // SAFETY: We have no outstanding mutable references to world as they were dropped
         let mut deferred_world = unsafe { self.world.into_deferred() };
 
@@ -1346,8 +1473,6 @@ impl<'w> BundleInserter<'w> {
                 }
             }
         }
-
-        (new_location, after_effect)
     }
 
     #[inline]
@@ -1357,6 +1482,52 @@ impl<'w> BundleInserter<'w> {
     }
 }
 
+/// Moves every entity of `archetype` to `new_archetype` at once. If the archetypes have different
+/// `tables`, the rows of the old table are moved to the new one at once too, and the components
+/// that the new table doesn't have are dropped.
+///
+/// Returns `false` without moving anything if the old table also stores the entities of other
+/// archetypes, as its rows can't be moved at once then.
+///
+/// # Safety
+/// - `tables` must be the tables of `archetype` and `new_archetype`, if they are different.
+/// - The components of `new_archetype` missing from `archetype` must be written for each moved
+///   entity before they are read.
+/// - The sparse set components of `archetype` missing from `new_archetype` must already be removed.
+pub(crate) unsafe fn move_archetype_entities(
+    archetype: &mut Archetype,
+    new_archetype: &mut Archetype,
+    tables: Option<(&mut Table, &mut Table)>,
+    entities: &mut Entities,
+) -> bool {
+    let first_row = match tables {
+        Some((table, new_table)) => {
+            if table.entity_count() != archetype.len() {
+                return false;
+            }
+            // SAFETY: The caller ensures that the missing components are written.
+            Some(unsafe { table.move_all_to_and_drop_missing_unchecked(new_table) })
+        }
+        None => None,
+    };
+    new_archetype.reserve(archetype.len());
+    for archetype_entity in archetype.entities() {
+        // Each row keeps its offset from the first one.
+        let table_row = match first_row {
+            Some(first_row) => {
+                TableRow::from_usize(first_row.as_usize() + archetype_entity.table_row().as_usize())
+            }
+            None => archetype_entity.table_row(),
+        };
+        // SAFETY: The components of the entity are stored at `table_row`, or written by the caller.
+        let location = unsafe { new_archetype.allocate(archetype_entity.id(), table_row) };
+        // SAFETY: The entity was just moved to `location`.
+        unsafe { entities.set(archetype_entity.id().index(), location) };
+    }
+    archetype.clear_entities();
+    true
+}
+
 // SAFETY: We have exclusive world access so our pointers can't be invalidated externally
 pub(crate) struct BundleSpawner<'w> {
     world: UnsafeWorldCell<'w>,
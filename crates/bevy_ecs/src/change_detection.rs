@@ -126,6 +126,12 @@ pub trait DetectChangesMut: DetectChanges {
     /// If you want to avoid triggering change detection, use [`bypass_change_detection`](DetectChangesMut::bypass_change_detection) instead.
     fn set_last_changed(&mut self, last_changed: Tick);
 
+    /// Manually sets the added tick recording the time when this data was last added.
+    ///
+    /// # Warning
+    /// This is a complex and error-prone operation, primarily intended for use with rollback networking strategies.
+    fn set_last_added(&mut self, last_added: Tick);
+
     /// Manually bypasses change detection, allowing you to mutate the underlying value without updating the change tick.
     ///
     /// # Warning
@@ -383,6 +389,11 @@ macro_rules! change_detection_mut_impl {
                 self.changed_by.assign(MaybeLocation::caller());
             }
 
+            #[inline]
+            fn set_last_added(&mut self, last_added: Tick) {
+                *self.ticks.added = last_added;
+            }
+
             #[inline]
             fn bypass_change_detection(&mut self) -> &mut Self::Inner {
                 self.value
@@ -1158,6 +1169,11 @@ impl<'w> DetectChangesMut for MutUntyped<'w> {
         self.changed_by.assign(MaybeLocation::caller());
     }
 
+    #[inline]
+    fn set_last_added(&mut self, last_added: Tick) {
+        *self.ticks.added = last_added;
+    }
+
     #[inline]
     #[track_caller]
     fn bypass_change_detection(&mut self) -> &mut Self::Inner {
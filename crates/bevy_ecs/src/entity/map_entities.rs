@@ -4,6 +4,9 @@ use crate::{
     world::World,
 };
 
+use core::ops::Range;
+use log::warn;
+
 use super::{hash_map::EntityHashMap, VisitEntitiesMut};
 
 /// Operation to map all contained [`Entity`] fields in a type to new values.
@@ -171,6 +174,87 @@ impl EntityMapper for EntityHashMap<Entity> {
     }
 }
 
+/// An [`EntityMapper`] translating the entities of another [`World`] to a range of entity indices
+/// reserved with [`Entities::reserve_index_range`](super::Entities::reserve_index_range).
+///
+/// The index of each source entity is offset by the start of the range, and its generation is kept
+/// as is, so that entities replicated from a server never collide with the ones spawned locally
+/// and can be translated back without keeping a map.
+///
+/// ```
+/// # use bevy_ecs::{entity::{Entity, EntityIndexRangeMapper, EntityMapper}, world::World};
+/// let mut world = World::new();
+/// world.reserve_entity_index_range(1000..2000).unwrap();
+/// let mut mapper = EntityIndexRangeMapper::new(1000..2000);
+///
+/// let remote = Entity::from_raw(7);
+/// let local = mapper.get_mapped(remote);
+/// assert_eq!(local.index(), 1007);
+/// world.spawn_at(local, ()).unwrap();
+///
+/// assert_eq!(mapper.unmap(local), Some(remote));
+/// assert_eq!(mapper.try_map(Entity::from_raw(1000)), None);
+/// assert_eq!(mapper.get_mapped(Entity::from_raw(1000)), Entity::PLACEHOLDER);
+/// assert_ne!(world.spawn_empty().id().index(), 1007);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntityIndexRangeMapper {
+    range: Range<u32>,
+}
+
+impl EntityIndexRangeMapper {
+    /// Creates a mapper translating the source entity indices to the `range` of target indices.
+    pub fn new(range: Range<u32>) -> Self {
+        Self { range }
+    }
+
+    /// Returns the range of target entity indices.
+    pub fn range(&self) -> Range<u32> {
+        self.range.clone()
+    }
+
+    /// Returns the target entity that the given `source` maps to, or [`None`] if its offset index
+    /// isn't in the range.
+    ///
+    /// This is the fallible version of [`EntityMapper::get_mapped`], for source entities that
+    /// may not fit in the range.
+    pub fn try_map(&self, source: Entity) -> Option<Entity> {
+        self.range
+            .start
+            .checked_add(source.index())
+            .filter(|index| self.range.contains(index))
+            .map(|index| Entity::from_raw_and_generation(index, source.generation))
+    }
+
+    /// Returns the source entity that maps to the given `target`, or [`None`] if its index isn't
+    /// in the range.
+    pub fn unmap(&self, target: Entity) -> Option<Entity> {
+        self.range.contains(&target.index()).then(|| {
+            Entity::from_raw_and_generation(target.index() - self.range.start, target.generation)
+        })
+    }
+}
+
+impl EntityMapper for EntityIndexRangeMapper {
+    /// Returns the entity at the index of `source` offset by the start of the range.
+    ///
+    /// If the offset index isn't in the range, a warning is logged and [`Entity::PLACEHOLDER`] is
+    /// returned. Use [`EntityIndexRangeMapper::try_map`] to handle entities that may not fit.
+    fn get_mapped(&mut self, source: Entity) -> Entity {
+        self.try_map(source).unwrap_or_else(|| {
+            warn!(
+                "{source} doesn't fit in the entity index range {:?} and was mapped to {}",
+                self.range,
+                Entity::PLACEHOLDER
+            );
+            Entity::PLACEHOLDER
+        })
+    }
+
+    /// Does nothing, as the mapping is fixed.
+    fn set_mapped(&mut self, _source: Entity, _target: Entity) {}
+}
+
 /// A wrapper for [`EntityHashMap<Entity>`], augmenting it with the ability to allocate new [`Entity`] references in a destination
 /// world. These newly allocated references are guaranteed to never point to any living entity in that world.
 ///
@@ -85,9 +85,15 @@ use crate::{
     },
     storage::{SparseSetIndex, TableId, TableRow},
 };
-use alloc::vec::Vec;
-use bevy_platform_support::sync::atomic::Ordering;
-use core::{fmt, hash::Hash, mem, num::NonZero, panic::Location};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bevy_platform_support::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+use core::{fmt, hash::Hash, iter::StepBy, mem, num::NonZero, ops::Range, panic::Location};
 use log::warn;
 
 #[cfg(feature = "serialize")]
@@ -252,6 +258,19 @@ pub(crate) enum AllocAtWithoutReplacement {
     ExistsWithWrongGeneration,
 }
 
+/// The state of the [`Entities`] allocator, saved by [`Entities::snapshot_allocator`].
+#[derive(Clone)]
+pub(crate) struct EntitiesAllocatorSnapshot {
+    /// The number of entity slots.
+    len: u32,
+    pending: Vec<u32>,
+    /// The length of the generation log when the snapshot was taken. The entries after it hold
+    /// the generations of the slots changed since.
+    log_start: usize,
+    /// Keeps the [`Entities`] the snapshot was taken from logging generation changes.
+    log_token: Arc<()>,
+}
+
 impl Entity {
     /// Construct an [`Entity`] from a raw `index` value and a non-zero `generation` value.
     /// Ensure that the generation value is never greater than `0x7FFF_FFFF`.
@@ -498,7 +517,13 @@ pub struct ReserveEntitiesIterator<'a> {
     freelist_indices: core::slice::Iter<'a, u32>,
 
     // New Entity indices to hand out, outside the range of meta.len().
-    new_indices: core::ops::Range<u32>,
+    new_indices: Range<u32>,
+
+    // The freelist the reservation partition was opened with, if there is one.
+    partition_freelist: &'a [u32],
+
+    // Positions in the reservation partition to hand out, see `ReservationPartition`.
+    partition_positions: StepBy<Range<u32>>,
 }
 
 impl<'a> Iterator for ReserveEntitiesIterator<'a> {
@@ -511,10 +536,16 @@ impl<'a> Iterator for ReserveEntitiesIterator<'a> {
                 Entity::from_raw_and_generation(index, self.meta[index as usize].generation)
             })
             .or_else(|| self.new_indices.next().map(Entity::from_raw))
+            .or_else(|| {
+                self.partition_positions.next().map(|position| {
+                    ReservationPartition::entity_at(self.partition_freelist, self.meta, position)
+                })
+            })
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = self.freelist_indices.len() + self.new_indices.len();
+        let len =
+            self.freelist_indices.len() + self.new_indices.len() + self.partition_positions.len();
         (len, Some(len))
     }
 }
@@ -525,6 +556,99 @@ impl<'a> core::iter::FusedIterator for ReserveEntitiesIterator<'a> {}
 // SAFETY: Newly reserved entity values are unique.
 unsafe impl EntitySetIterator for ReserveEntitiesIterator<'_> {}
 
+#[cfg(feature = "std")]
+std::thread_local! {
+    /// The share of the current [`ReservationPartition`] that entities reserved on this thread
+    /// come from.
+    static RESERVATION_SHARE: core::cell::Cell<Option<u32>> = const { core::cell::Cell::new(None) };
+}
+
+/// Runs `f` with entities reserved on this thread coming from `share` of the
+/// [`ReservationPartition`] of the [`Entities`] they are reserved from.
+#[cfg(feature = "std")]
+pub(crate) fn with_reservation_share<R>(share: Option<u32>, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<u32>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            RESERVATION_SHARE.set(self.0);
+        }
+    }
+
+    let _restore = Restore(RESERVATION_SHARE.replace(share));
+    f()
+}
+
+fn current_reservation_share() -> Option<u32> {
+    #[cfg(feature = "std")]
+    return RESERVATION_SHARE.get();
+    #[cfg(not(feature = "std"))]
+    return None;
+}
+
+/// Splits the IDs that can be reserved concurrently into shares, so that the IDs a system
+/// reserves don't depend on what the systems running in parallel with it reserve.
+///
+/// The IDs are laid out as a sequence of positions: first the freelist, starting from the end
+/// like [`Entities::reserve_entity`] does, then new indices starting at `meta.len()`. With `n`
+/// shares, the `k`-th ID reserved from share `s` is at position `s + k * n`. The last share is
+/// used by threads that weren't given one with [`with_reservation_share`].
+#[derive(Debug)]
+struct ReservationPartition {
+    /// The length of the freelist when the partition was opened.
+    freelist_len: u32,
+    /// The number of IDs reserved from each share.
+    reserved: Box<[AtomicU32]>,
+}
+
+impl ReservationPartition {
+    fn new(freelist_len: u32, shares: u32) -> Self {
+        Self {
+            freelist_len,
+            reserved: (0..=shares).map(|_| AtomicU32::new(0)).collect(),
+        }
+    }
+
+    fn stride(&self) -> u32 {
+        self.reserved.len() as u32
+    }
+
+    /// Reserves `count` positions from the share of the current thread.
+    fn reserve(&self, count: u32) -> StepBy<Range<u32>> {
+        let stride = self.stride();
+        let share = current_reservation_share()
+            .filter(|&share| share < stride - 1)
+            .unwrap_or(stride - 1);
+        let first = self.reserved[share as usize].fetch_add(count, Ordering::Relaxed);
+        let position = |k: u32| {
+            k.checked_mul(stride)
+                .and_then(|offset| offset.checked_add(share))
+                .expect("too many entities")
+        };
+        (position(first)..position(first + count)).step_by(stride as usize)
+    }
+
+    /// Returns whether `position` has been handed out.
+    fn is_reserved(&self, position: u32) -> bool {
+        let stride = self.stride();
+        position / stride < self.reserved[(position % stride) as usize].load(Ordering::Relaxed)
+    }
+
+    /// Returns the entity at `position`, given the freelist the partition was opened with.
+    fn entity_at(freelist: &[u32], meta: &[EntityMeta], position: u32) -> Entity {
+        let freelist_len = freelist.len() as u32;
+        if position < freelist_len {
+            let index = freelist[(freelist_len - 1 - position) as usize];
+            Entity::from_raw_and_generation(index, meta[index as usize].generation)
+        } else {
+            Entity::from_raw(
+                u32::try_from(meta.len() as u64 + u64::from(position - freelist_len))
+                    .expect("too many entities"),
+            )
+        }
+    }
+}
+
 /// A [`World`]'s internal metadata store on all of its entities.
 ///
 /// Contains metadata on:
@@ -581,6 +705,16 @@ pub struct Entities {
     free_cursor: AtomicIdCursor,
     /// Stores the number of free entities for [`len`](Entities::len)
     len: u32,
+    /// Index ranges that are never allocated, see [`Entities::reserve_index_range`].
+    reserved_ranges: Vec<Range<u32>>,
+    /// Set while concurrent reservations come from deterministic shares, see
+    /// [`Entities::begin_reservation_partition`].
+    partition: Option<ReservationPartition>,
+    /// The slots whose generation changed along with their previous generation, logged while
+    /// allocator snapshots are alive, see [`Entities::snapshot_allocator`].
+    generation_log: Vec<(u32, NonZero<u32>)>,
+    /// Shared with the allocator snapshots, which are alive as long as it's shared.
+    log_token: Option<Arc<()>>,
 }
 
 impl Entities {
@@ -590,6 +724,10 @@ impl Entities {
             pending: Vec::new(),
             free_cursor: AtomicIdCursor::new(0),
             len: 0,
+            reserved_ranges: Vec::new(),
+            partition: None,
+            generation_log: Vec::new(),
+            log_token: None,
         }
     }
 
@@ -605,6 +743,16 @@ impl Entities {
         reason = "`IdCursor::try_from` may fail on 32-bit platforms."
     )]
     pub fn reserve_entities(&self, count: u32) -> ReserveEntitiesIterator {
+        if let Some(partition) = &self.partition {
+            return ReserveEntitiesIterator {
+                meta: &self.meta[..],
+                freelist_indices: [].iter(),
+                new_indices: 0..0,
+                partition_freelist: &self.pending[..partition.freelist_len as usize],
+                partition_positions: partition.reserve(count),
+            };
+        }
+
         // Use one atomic subtract to grab a range of new IDs. The range might be
         // entirely nonnegative, meaning all IDs come from the freelist, or entirely
         // negative, meaning they are all new IDs to allocate, or a mix of both.
@@ -646,6 +794,8 @@ impl Entities {
             meta: &self.meta[..],
             freelist_indices: self.pending[freelist_range].iter(),
             new_indices: new_id_start..new_id_end,
+            partition_freelist: &[],
+            partition_positions: (0..0).step_by(1),
         }
     }
 
@@ -653,6 +803,15 @@ impl Entities {
     ///
     /// Equivalent to `self.reserve_entities(1).next().unwrap()`, but more efficient.
     pub fn reserve_entity(&self) -> Entity {
+        if let Some(partition) = &self.partition {
+            let position = partition.reserve(1).next().unwrap();
+            return ReservationPartition::entity_at(
+                &self.pending[..partition.freelist_len as usize],
+                &self.meta,
+                position,
+            );
+        }
+
         let n = self.free_cursor.fetch_sub(1, Ordering::Relaxed);
         if n > 0 {
             // Allocate from the freelist.
@@ -715,6 +874,9 @@ impl Entities {
             *self.free_cursor.get_mut() = new_free_cursor;
             self.len += 1;
             None
+        } else if self.is_free_reserved_index(entity.index()) {
+            self.len += 1;
+            None
         } else {
             Some(mem::replace(
                 &mut self.meta[entity.index() as usize].location,
@@ -722,7 +884,7 @@ impl Entities {
             ))
         };
 
-        self.meta[entity.index() as usize].generation = entity.generation;
+        self.set_generation(entity.index(), entity.generation);
 
         loc
     }
@@ -754,6 +916,9 @@ impl Entities {
         } else {
             let current_meta = &self.meta[entity.index() as usize];
             if current_meta.location.archetype_id == ArchetypeId::INVALID {
+                if self.is_index_reserved(entity.index()) {
+                    self.len += 1;
+                }
                 AllocAtWithoutReplacement::DidNotExist
             } else if current_meta.generation == entity.generation {
                 AllocAtWithoutReplacement::Exists(current_meta.location)
@@ -762,33 +927,206 @@ impl Entities {
             }
         };
 
-        self.meta[entity.index() as usize].generation = entity.generation;
+        self.set_generation(entity.index(), entity.generation);
         result
     }
 
+    /// Reserves the entity indices in `range` for entities whose IDs are allocated elsewhere, for
+    /// example by a server that replicates its entities to this [`World`](crate::world::World).
+    ///
+    /// The reserved indices are never used by [`Entities::alloc`] and [`Entities::reserve_entity`],
+    /// even once the entities spawned at them are freed. Spawn entities at reserved indices with
+    /// [`World::spawn_at`](crate::world::World::spawn_at), and translate their IDs with an
+    /// [`EntityIndexRangeMapper`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ReserveIndexRangeError`] if the range overlaps a range that was already
+    /// reserved, or if an entity is already using one of the indices.
+    pub fn reserve_index_range(&mut self, range: Range<u32>) -> Result<(), ReserveIndexRangeError> {
+        self.verify_flushed();
+        if range.is_empty() {
+            return Ok(());
+        }
+        if let Some(reserved) = self
+            .reserved_ranges
+            .iter()
+            .find(|reserved| reserved.start < range.end && range.start < reserved.end)
+        {
+            return Err(ReserveIndexRangeError::Overlapping(reserved.clone()));
+        }
+
+        let end = (range.end).min(self.meta.len() as u32);
+        let mut free = vec![false; end.saturating_sub(range.start) as usize];
+        for &index in &self.pending {
+            if (range.start..end).contains(&index) {
+                free[(index - range.start) as usize] = true;
+            }
+        }
+        for index in range.start..end {
+            if !free[(index - range.start) as usize] {
+                return Err(ReserveIndexRangeError::InUse(EntityIndexInUseError {
+                    entity: Entity::from_raw_and_generation(
+                        index,
+                        self.meta[index as usize].generation,
+                    ),
+                }));
+            }
+        }
+
+        self.pending.retain(|index| !range.contains(index));
+        if range.end as usize > self.meta.len() {
+            self.pending.extend((self.meta.len() as u32)..range.start);
+            self.meta.resize(range.end as usize, EntityMeta::EMPTY);
+        }
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
+        self.reserved_ranges.push(range);
+        Ok(())
+    }
+
+    /// Returns true if the entity `index` was reserved with [`Entities::reserve_index_range`].
+    #[inline]
+    pub fn is_index_reserved(&self, index: u32) -> bool {
+        self.reserved_ranges
+            .iter()
+            .any(|range| range.contains(&index))
+    }
+
+    /// Returns the index ranges reserved with [`Entities::reserve_index_range`].
+    pub fn reserved_index_ranges(&self) -> &[Range<u32>] {
+        &self.reserved_ranges
+    }
+
+    /// Returns true if the entity `index` is reserved and no entity is using it.
+    fn is_free_reserved_index(&self, index: u32) -> bool {
+        self.is_index_reserved(index)
+            && self.meta[index as usize].location.archetype_id == ArchetypeId::INVALID
+    }
+
+    /// Saves the order in which free slots are reused. The generations of the slots aren't
+    /// copied: while the snapshot is alive, the slots whose generation changes are logged instead.
+    pub(crate) fn snapshot_allocator(&mut self) -> EntitiesAllocatorSnapshot {
+        self.verify_flushed();
+        let log_token = self.log_token.get_or_insert_with(|| Arc::new(())).clone();
+        if Arc::strong_count(&log_token) == 2 {
+            // No other snapshot is alive, so the changes logged so far aren't needed anymore.
+            self.generation_log.clear();
+        }
+        EntitiesAllocatorSnapshot {
+            len: self.meta.len() as u32,
+            pending: self.pending.clone(),
+            log_start: self.generation_log.len(),
+            log_token,
+        }
+    }
+
+    /// Sets the generation of the slot at `index`, logging its previous generation while allocator
+    /// snapshots are alive.
+    fn set_generation(&mut self, index: u32, generation: NonZero<u32>) {
+        let meta = &mut self.meta[index as usize];
+        let logging = self
+            .log_token
+            .as_ref()
+            .is_some_and(|token| Arc::strong_count(token) > 1);
+        if logging && meta.generation != generation {
+            self.generation_log.push((index, meta.generation));
+        }
+        meta.generation = generation;
+    }
+
+    /// Rolls the free entity slots back to the state saved by [`Entities::snapshot_allocator`],
+    /// so that entities allocated from now on get the same IDs as the ones allocated after the
+    /// snapshot was taken. Slots that are in use are left untouched.
+    pub(crate) fn restore_allocator(&mut self, snapshot: &EntitiesAllocatorSnapshot) {
+        self.verify_flushed();
+
+        let reserved_ranges = &self.reserved_ranges;
+        let is_free = |index: usize, meta: &EntityMeta| {
+            meta.location.archetype_id == ArchetypeId::INVALID
+                && !reserved_ranges
+                    .iter()
+                    .any(|range| range.contains(&(index as u32)))
+        };
+        let snapshot_len = snapshot.len as usize;
+        let mut was_free = vec![false; snapshot_len];
+        for &index in &snapshot.pending {
+            was_free[index as usize] = true;
+        }
+        // The generations that the slots changed since then had at the time of the snapshot. A
+        // snapshot taken before the entities were cleared keeps the current generations instead.
+        let mut snapshot_generations = <HashMap<_, _>>::default();
+        if self
+            .log_token
+            .as_ref()
+            .is_some_and(|token| Arc::ptr_eq(token, &snapshot.log_token))
+        {
+            for &(index, generation) in self.generation_log[snapshot.log_start..].iter().rev() {
+                snapshot_generations.insert(index, generation);
+            }
+        }
+
+        // Slots that were in use at the time of the snapshot but have been freed since are
+        // reused last, keeping their current generation so that old IDs aren't aliased.
+        let mut pending = (0..snapshot_len)
+            .filter(|&index| !was_free[index] && is_free(index, &self.meta[index]))
+            .map(|index| index as u32)
+            .collect::<Vec<_>>();
+        // Slots that didn't exist yet would have been allocated in increasing order.
+        let mut reset = Vec::new();
+        for index in (snapshot_len..self.meta.len()).rev() {
+            if is_free(index, &self.meta[index]) {
+                reset.push((index as u32, EntityMeta::EMPTY.generation));
+                pending.push(index as u32);
+            }
+        }
+        for &index in &snapshot.pending {
+            let meta = &self.meta[index as usize];
+            if is_free(index as usize, meta) {
+                let generation = snapshot_generations
+                    .get(&index)
+                    .copied()
+                    .unwrap_or(meta.generation);
+                reset.push((index, generation));
+                pending.push(index);
+            }
+        }
+        for (index, generation) in reset {
+            self.set_generation(index, generation);
+        }
+
+        self.pending = pending;
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
+    }
+
     /// Destroy an entity, allowing it to be reused.
     ///
     /// Must not be called while reserved entities are awaiting `flush()`.
     pub fn free(&mut self, entity: Entity) -> Option<EntityLocation> {
         self.verify_flushed();
 
-        let meta = &mut self.meta[entity.index() as usize];
+        let meta = &self.meta[entity.index() as usize];
         if meta.generation != entity.generation {
             return None;
         }
 
-        meta.generation = IdentifierMask::inc_masked_high_by(meta.generation, 1);
+        let generation = IdentifierMask::inc_masked_high_by(meta.generation, 1);
+        self.set_generation(entity.index(), generation);
 
-        if meta.generation == NonZero::<u32>::MIN {
+        if generation == NonZero::<u32>::MIN {
             warn!(
                 "Entity({}) generation wrapped on Entities::free, aliasing may occur",
                 entity.index
             );
         }
 
-        let loc = mem::replace(&mut meta.location, EntityMeta::EMPTY.location);
+        let loc = mem::replace(
+            &mut self.meta[entity.index() as usize].location,
+            EntityMeta::EMPTY.location,
+        );
 
-        self.pending.push(entity.index());
+        if !self.is_index_reserved(entity.index()) {
+            self.pending.push(entity.index());
+        }
 
         let new_free_cursor = self.pending.len() as IdCursor;
         *self.free_cursor.get_mut() = new_free_cursor;
@@ -831,6 +1169,13 @@ impl Entities {
         self.pending.clear();
         *self.free_cursor.get_mut() = 0;
         self.len = 0;
+        self.partition = None;
+        self.generation_log.clear();
+        self.log_token = None;
+        for range in mem::take(&mut self.reserved_ranges) {
+            // Nothing can be using the indices anymore.
+            let _ = self.reserve_index_range(range);
+        }
     }
 
     /// Returns the location of an [`Entity`].
@@ -873,9 +1218,10 @@ impl Entities {
             return false;
         }
 
-        let meta = &mut self.meta[index as usize];
+        let meta = &self.meta[index as usize];
         if meta.location.archetype_id == ArchetypeId::INVALID {
-            meta.generation = IdentifierMask::inc_masked_high_by(meta.generation, generations);
+            let generation = IdentifierMask::inc_masked_high_by(meta.generation, generations);
+            self.set_generation(index, generation);
             true
         } else {
             false
@@ -892,6 +1238,12 @@ impl Entities {
         let idu = index as usize;
         if let Some(&EntityMeta { generation, .. }) = self.meta.get(idu) {
             Some(Entity::from_raw_and_generation(index, generation))
+        } else if let Some(partition) = &self.partition {
+            let position = partition.freelist_len as u64 + (idu - self.meta.len()) as u64;
+            let position = u32::try_from(position).ok()?;
+            partition
+                .is_reserved(position)
+                .then_some(Entity::from_raw(index))
         } else {
             // `id` is outside of the meta list - check whether it is reserved but not yet flushed.
             let free_cursor = self.free_cursor.load(Ordering::Relaxed);
@@ -903,7 +1255,67 @@ impl Entities {
     }
 
     fn needs_flush(&mut self) -> bool {
-        *self.free_cursor.get_mut() != self.pending.len() as IdCursor
+        self.partition.is_some() || *self.free_cursor.get_mut() != self.pending.len() as IdCursor
+    }
+
+    /// Makes [`reserve_entity`](Entities::reserve_entity) and
+    /// [`reserve_entities`](Entities::reserve_entities) hand out IDs from `shares` separate
+    /// shares, so that the IDs reserved from each share don't depend on the order in which
+    /// threads reserve them. A thread picks its share with [`with_reservation_share`].
+    ///
+    /// The shares are merged back by [`end_reservation_partition`](Entities::end_reservation_partition)
+    /// or [`flush`](Entities::flush).
+    #[cfg_attr(
+        not(feature = "std"),
+        expect(dead_code, reason = "currently only used with the std feature")
+    )]
+    pub(crate) fn begin_reservation_partition(&mut self, shares: u32) {
+        self.verify_flushed();
+        self.partition = Some(ReservationPartition::new(self.pending.len() as u32, shares));
+    }
+
+    /// Turns the IDs reserved since [`begin_reservation_partition`](Entities::begin_reservation_partition)
+    /// into regular reserved IDs, waiting for [`flush`](Entities::flush).
+    ///
+    /// New indices that were skipped over by the shares are added to the freelist.
+    pub(crate) fn end_reservation_partition(&mut self) {
+        let Some(partition) = self.partition.take() else {
+            return;
+        };
+        let stride = partition.stride();
+        let freelist_len = partition.freelist_len;
+        let mut end = freelist_len;
+        for (share, reserved) in partition.reserved.iter().enumerate() {
+            let reserved = reserved.load(Ordering::Relaxed);
+            if reserved > 0 {
+                end = end.max(share as u32 + (reserved - 1) * stride + 1);
+            }
+        }
+
+        let meta_len = self.meta.len() as u32;
+        let new_len = end - freelist_len;
+        self.meta
+            .resize((meta_len + new_len) as usize, EntityMeta::EMPTY);
+        // New indices skipped by the shares go on top of the freelist, so they are reused first.
+        let (mut free, mut reserved) = (Vec::new(), Vec::new());
+        for (i, &index) in self.pending.iter().enumerate() {
+            if partition.is_reserved(freelist_len - 1 - i as u32) {
+                reserved.push(index);
+            } else {
+                free.push(index);
+            }
+        }
+        for position in (freelist_len..end).rev() {
+            let index = meta_len + position - freelist_len;
+            if partition.is_reserved(position) {
+                reserved.push(index);
+            } else {
+                free.push(index);
+            }
+        }
+        *self.free_cursor.get_mut() = free.len() as IdCursor;
+        free.append(&mut reserved);
+        self.pending = free;
     }
 
     /// Allocates space for entities previously reserved with [`reserve_entity`](Entities::reserve_entity) or
@@ -917,6 +1329,7 @@ impl Entities {
     /// Note: freshly-allocated entities (ones which don't come from the pending list) are guaranteed
     /// to be initialized with the invalid archetype.
     pub unsafe fn flush(&mut self, mut init: impl FnMut(Entity, &mut EntityLocation)) {
+        self.end_reservation_partition();
         let free_cursor = self.free_cursor.get_mut();
         let current_free_cursor = *free_cursor;
 
@@ -1047,6 +1460,25 @@ impl EntityDoesNotExistError {
     }
 }
 
+/// An error that occurs when an entity index is already used by another [`Entity`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("The entity index is already used by {entity}")]
+pub struct EntityIndexInUseError {
+    /// The entity using the index.
+    pub entity: Entity,
+}
+
+/// An error that occurs when reserving entity indices with [`Entities::reserve_index_range`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ReserveIndexRangeError {
+    /// An entity is already using one of the indices.
+    #[error(transparent)]
+    InUse(#[from] EntityIndexInUseError),
+    /// Some of the indices are part of this range, which was already reserved.
+    #[error("The entity indices are already reserved by the range {0:?}")]
+    Overlapping(Range<u32>),
+}
+
 /// Helper struct that, when printed, will write the appropriate details
 /// regarding an entity that did not exist.
 #[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -1168,6 +1600,75 @@ mod tests {
         assert!(entities.get(e).is_none());
     }
 
+    #[test]
+    fn allocator_snapshots_log_only_changed_generations() {
+        let mut world = crate::world::World::new();
+        let spawned = (0..100)
+            .map(|_| world.spawn_empty().id())
+            .collect::<Vec<_>>();
+        world.despawn(spawned[3]);
+        let snapshot = world.entities.snapshot_allocator();
+        assert!(world.entities.generation_log.is_empty());
+
+        let reused = world.spawn_empty().id();
+        assert_eq!(reused.index(), 3);
+        world.despawn(reused);
+        world.despawn(spawned[5]);
+        assert_eq!(world.entities.generation_log.len(), 2);
+
+        // The slot freed before the snapshot gets back the generation it had then.
+        world.entities.restore_allocator(&snapshot);
+        assert_eq!(world.spawn_empty().id(), reused);
+        assert_eq!(world.spawn_empty().id().index(), 5);
+
+        // The log is dropped once no snapshot needs it anymore.
+        drop(snapshot);
+        let _snapshot = world.entities.snapshot_allocator();
+        assert!(world.entities.generation_log.is_empty());
+    }
+
+    #[test]
+    fn reservation_partition_ignores_reservation_order() {
+        let reserve = |share_first: bool| {
+            let mut entities = Entities::new();
+            let allocated = [entities.alloc(), entities.alloc(), entities.alloc()];
+            entities.free(allocated[0]);
+            entities.free(allocated[2]);
+            entities.begin_reservation_partition(2);
+
+            let from_shares = || {
+                let mut reserved = with_reservation_share(Some(1), || {
+                    entities.reserve_entities(2).collect::<Vec<_>>()
+                });
+                reserved.push(with_reservation_share(Some(0), || {
+                    entities.reserve_entity()
+                }));
+                reserved
+            };
+            let reserved = if share_first {
+                let mut reserved = from_shares();
+                reserved.push(entities.reserve_entity());
+                reserved
+            } else {
+                let shared = entities.reserve_entity();
+                let mut reserved = from_shares();
+                reserved.push(shared);
+                reserved
+            };
+            assert!(reserved.iter().all(|&entity| entities.contains(entity)));
+            assert!(!entities.contains(Entity::from_raw(4)));
+
+            entities.flush_as_invalid();
+            assert_eq!(entities.len(), 5);
+            // The new index skipped by the shares is free again.
+            assert_eq!(entities.alloc().index(), 4);
+            reserved.into_iter().map(Entity::index).collect::<Vec<_>>()
+        };
+
+        assert_eq!(reserve(true), [0, 5, 2, 3]);
+        assert_eq!(reserve(false), [0, 5, 2, 3]);
+    }
+
     #[test]
     fn entity_const() {
         const C1: Entity = Entity::from_raw(42);
@@ -1333,4 +1834,43 @@ mod tests {
         let string = format!("{}", entity);
         assert_eq!(string, "PLACEHOLDER");
     }
+
+    #[test]
+    fn reserved_index_range() {
+        let mut entities = Entities::new();
+        let first = entities.alloc();
+        let second = entities.alloc();
+        entities.free(second);
+
+        assert_eq!(
+            entities.reserve_index_range(0..4),
+            Err(ReserveIndexRangeError::InUse(EntityIndexInUseError {
+                entity: first
+            }))
+        );
+        entities.reserve_index_range(1..4).unwrap();
+        assert!(entities.is_index_reserved(1));
+        for overlapping in [0..2, 3..6, 2..3, 0..8] {
+            assert_eq!(
+                entities.reserve_index_range(overlapping),
+                Err(ReserveIndexRangeError::Overlapping(1..4))
+            );
+        }
+        entities.reserve_index_range(4..6).unwrap();
+        assert_eq!(entities.reserved_index_ranges(), [1..4, 4..6]);
+        assert!((0..8).all(|_| entities.alloc().index() >= 4));
+
+        let reserved = Entity::from_raw(2);
+        entities.alloc_at(reserved);
+        assert!(entities.contains(reserved));
+        assert_eq!(entities.len(), 10);
+        entities.free(reserved);
+        assert!(entities.alloc().index() >= 4);
+
+        entities.clear();
+        let allocated = (0..8).map(|_| entities.alloc().index()).collect::<Vec<_>>();
+        assert!(allocated
+            .iter()
+            .all(|&index| !entities.is_index_reserved(index)));
+    }
 }
@@ -75,6 +75,14 @@ pub trait Event: Send + Sync + 'static {
     fn component_id(world: &World) -> Option<ComponentId> {
         world.component_id::<EventWrapperComponent<Self>>()
     }
+
+    /// Called when an [`Observer`] of this event is added to the [`World`], before it can run.
+    ///
+    /// Events that are triggered by the ECS itself, rather than by hand, can use this to start
+    /// tracking what causes them, like [`OnMatch`](crate::observer::OnMatch) does.
+    ///
+    /// [`Observer`]: crate::observer::Observer
+    fn on_observer_added(_world: &mut World) {}
 }
 
 /// An internal type that implements [`Component`] for a given [`Event`] type.
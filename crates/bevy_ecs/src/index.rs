@@ -0,0 +1,289 @@
+//! Value-indexed lookups of [immutable components](crate::component::Immutable).
+//!
+//! Finding the entities whose component has a given value normally means iterating over a whole
+//! [`Query`]. For immutable components, every change goes through an insertion or a removal,
+//! so the [component hooks](crate::component::ComponentHooks) can keep a map from each value to
+//! the entities holding it up to date instead.
+//!
+//! Indexing is opt-in: call [`World::register_component_index`] before the component is first
+//! used, then look entities up with the [`QueryByIndex`] system parameter.
+//!
+//! ```
+//! use bevy_ecs::{index::QueryByIndex, prelude::*};
+//!
+//! #[derive(Component, Clone, PartialEq, Eq, Hash)]
+//! #[component(immutable)]
+//! struct Team(u8);
+//!
+//! #[derive(Component)]
+//! struct Health(u32);
+//!
+//! let mut world = World::new();
+//! world.register_component_index::<Team>();
+//!
+//! world.spawn((Team(1), Health(100)));
+//! world.spawn((Team(2), Health(80)));
+//! world.spawn((Team(2), Health(60)));
+//!
+//! fn heal_team_two(mut query: QueryByIndex<Team, &mut Health>) {
+//!     for mut health in query.at_mut(&Team(2)) {
+//!         health.0 += 10;
+//!     }
+//! }
+//!
+//! world.run_system_cached(heal_team_two).unwrap();
+//!
+//! let mut healths = world.query::<&Health>().iter(&world).map(|h| h.0).collect::<Vec<_>>();
+//! healths.sort();
+//! assert_eq!(healths, [70, 90, 100]);
+//! ```
+
+use core::hash::Hash;
+
+use bevy_platform_support::collections::HashMap;
+
+use crate::{
+    component::{Component, HookContext, Immutable},
+    entity::{
+        hash_set::{self, EntityHashSet},
+        Entity,
+    },
+    query::{QueryData, QueryFilter, QueryManyUniqueIter},
+    resource::Resource,
+    system::{Query, Res, SystemParam},
+    world::{DeferredWorld, World},
+};
+
+/// A [`Component`] that can be indexed by value with [`World::register_component_index`].
+///
+/// This is implemented for every immutable component that can be used as a hash map key.
+pub trait IndexableComponent: Component<Mutability = Immutable> + Eq + Hash + Clone {}
+
+impl<C: Component<Mutability = Immutable> + Eq + Hash + Clone> IndexableComponent for C {}
+
+/// A [`Resource`] mapping each value of the component `C` to the entities holding it.
+///
+/// It is kept up to date by the hooks registered with [`World::register_component_index`].
+#[derive(Resource)]
+pub struct ComponentIndex<C: IndexableComponent> {
+    map: HashMap<C, EntityHashSet>,
+}
+
+impl<C: IndexableComponent> Default for ComponentIndex<C> {
+    fn default() -> Self {
+        Self {
+            map: HashMap::default(),
+        }
+    }
+}
+
+impl<C: IndexableComponent> ComponentIndex<C> {
+    /// Returns the entities whose `C` component is equal to `value`, if there are any.
+    pub fn get(&self, value: &C) -> Option<&EntityHashSet> {
+        self.map.get(value)
+    }
+
+    /// Returns an iterator over the entities whose `C` component is equal to `value`.
+    pub fn entities(&self, value: &C) -> hash_set::Iter<'_> {
+        self.get(value).map(EntityHashSet::iter).unwrap_or_default()
+    }
+
+    /// Returns true if any entity has a `C` component equal to `value`.
+    pub fn contains(&self, value: &C) -> bool {
+        self.map.contains_key(value)
+    }
+
+    /// Returns an iterator over the distinct values of `C` held by entities.
+    pub fn values(&self) -> impl Iterator<Item = &C> {
+        self.map.keys()
+    }
+
+    /// Returns the number of distinct values of `C` held by entities.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if no entity has a `C` component.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    fn insert_hook(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let value = world.get::<C>(entity).unwrap().clone();
+        let Some(mut index) = world.get_resource_mut::<Self>() else {
+            return;
+        };
+        index.map.entry(value).or_default().insert(entity);
+    }
+
+    fn replace_hook(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let value = world.get::<C>(entity).unwrap().clone();
+        let Some(mut index) = world.get_resource_mut::<Self>() else {
+            return;
+        };
+        if let Some(entities) = index.map.get_mut(&value) {
+            entities.remove(&entity);
+            if entities.is_empty() {
+                index.map.remove(&value);
+            }
+        }
+    }
+}
+
+impl World {
+    /// Starts indexing the entities holding the component `C` by its value, in a
+    /// [`ComponentIndex<C>`] resource, so that they can be looked up with [`QueryByIndex`].
+    ///
+    /// Does nothing if `C` is already indexed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` already exists in any archetype, like [`World::register_component_hooks`],
+    /// or if `C` already has an `on_insert` or `on_replace` hook.
+    pub fn register_component_index<C: IndexableComponent>(&mut self) {
+        if self.contains_resource::<ComponentIndex<C>>() {
+            return;
+        }
+        let hooks = self.register_component_hooks::<C>();
+        assert!(
+            hooks.on_insert.is_none() && hooks.on_replace.is_none(),
+            "{} can't be indexed because it already has an on_insert or on_replace hook",
+            core::any::type_name::<C>()
+        );
+        hooks
+            .on_insert(ComponentIndex::<C>::insert_hook)
+            .on_replace(ComponentIndex::<C>::replace_hook);
+        self.init_resource::<ComponentIndex<C>>();
+    }
+}
+
+/// A [`SystemParam`] that looks up entities by the value of their indexed component `C`, and
+/// fetches their [`QueryData`] `D` matching the [`QueryFilter`] `F`.
+///
+/// Looking up a value takes constant time, instead of iterating over every entity of the query.
+///
+/// # Panics
+///
+/// Panics when used in a system if `C` wasn't indexed with [`World::register_component_index`].
+#[derive(SystemParam)]
+pub struct QueryByIndex<
+    'w,
+    's,
+    C: IndexableComponent,
+    D: QueryData + 'static,
+    F: QueryFilter + 'static = (),
+> {
+    index: Res<'w, ComponentIndex<C>>,
+    query: Query<'w, 's, D, F>,
+}
+
+impl<'w, 's, C: IndexableComponent, D: QueryData, F: QueryFilter> QueryByIndex<'w, 's, C, D, F> {
+    /// Returns an iterator over the read-only query items of the entities whose `C` component is
+    /// equal to `value`.
+    ///
+    /// Entities that don't match the query are skipped.
+    pub fn at(&self, value: &C) -> QueryManyUniqueIter<'_, 's, D::ReadOnly, F, hash_set::Iter<'_>> {
+        self.query.iter_many_unique(self.index.entities(value))
+    }
+
+    /// Returns an iterator over the query items of the entities whose `C` component is equal to
+    /// `value`.
+    ///
+    /// Entities that don't match the query are skipped.
+    pub fn at_mut(&mut self, value: &C) -> QueryManyUniqueIter<'_, 's, D, F, hash_set::Iter<'_>> {
+        self.query.iter_many_unique_mut(self.index.entities(value))
+    }
+
+    /// Returns an iterator over the entities whose `C` component is equal to `value`, whether
+    /// they match the query or not.
+    pub fn entities(&self, value: &C) -> impl Iterator<Item = Entity> + '_ {
+        self.index.entities(value).copied()
+    }
+
+    /// Returns the underlying [`ComponentIndex`].
+    pub fn index(&self) -> &ComponentIndex<C> {
+        &self.index
+    }
+
+    /// Returns the underlying [`Query`].
+    pub fn query(&self) -> &Query<'w, 's, D, F> {
+        &self.query
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Component, Clone, Debug, PartialEq, Eq, Hash)]
+    #[component(immutable)]
+    struct ChunkCoord(i32, i32);
+
+    #[derive(Component)]
+    struct Marker;
+
+    #[test]
+    fn index_follows_inserts_and_removals() {
+        let mut world = World::new();
+        world.register_component_index::<ChunkCoord>();
+
+        let a = world.spawn(ChunkCoord(3, 7)).id();
+        let b = world.spawn(ChunkCoord(3, 7)).id();
+        let c = world.spawn(ChunkCoord(0, 0)).id();
+
+        let index = world.resource::<ComponentIndex<ChunkCoord>>();
+        assert_eq!(index.len(), 2);
+        assert_eq!(
+            index.get(&ChunkCoord(3, 7)),
+            Some(&EntityHashSet::from([a, b]))
+        );
+
+        world.entity_mut(a).insert(ChunkCoord(0, 0));
+        world.entity_mut(b).remove::<ChunkCoord>();
+        world.despawn(c);
+
+        let index = world.resource::<ComponentIndex<ChunkCoord>>();
+        assert!(!index.contains(&ChunkCoord(3, 7)));
+        assert_eq!(index.entities(&ChunkCoord(0, 0)).collect::<Vec<_>>(), [&a]);
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn query_by_index() {
+        let mut world = World::new();
+        world.register_component_index::<ChunkCoord>();
+
+        let a = world.spawn((ChunkCoord(3, 7), Marker)).id();
+        let b = world.spawn(ChunkCoord(3, 7)).id();
+        world.spawn((ChunkCoord(1, 1), Marker));
+
+        let found = world
+            .run_system_cached(|query: QueryByIndex<ChunkCoord, Entity, With<Marker>>| {
+                let mut all = query.entities(&ChunkCoord(3, 7)).collect::<Vec<_>>();
+                all.sort();
+                (query.at(&ChunkCoord(3, 7)).collect::<Vec<_>>(), all)
+            })
+            .unwrap();
+        assert_eq!(found, (vec![a], vec![a, b]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_requires_unused_component() {
+        let mut world = World::new();
+        world.spawn(ChunkCoord(0, 0));
+        world.register_component_index::<ChunkCoord>();
+    }
+
+    #[test]
+    fn removed_index_is_ignored() {
+        let mut world = World::new();
+        world.register_component_index::<ChunkCoord>();
+        world.remove_resource::<ComponentIndex<ChunkCoord>>();
+
+        let entity = world.spawn(ChunkCoord(0, 0)).id();
+        world.despawn(entity);
+    }
+}
@@ -39,6 +39,7 @@ pub mod entity_disabling;
 pub mod event;
 pub mod hierarchy;
 pub mod identifier;
+pub mod index;
 pub mod intern;
 pub mod label;
 pub mod name;
@@ -77,7 +78,7 @@ pub mod prelude {
         event::{Event, EventMutator, EventReader, EventWriter, Events},
         hierarchy::{ChildOf, ChildSpawner, ChildSpawnerCommands, Children},
         name::{Name, NameOrEntity},
-        observer::{Observer, Trigger},
+        observer::{Observer, OnMatch, OnMutate, OnUnmatch, Trigger},
         query::{Added, AnyOf, Changed, Has, Or, QueryBuilder, QueryState, With, Without},
         related,
         relationship::RelationshipTarget,
@@ -108,7 +109,7 @@ pub mod prelude {
     #[doc(hidden)]
     #[cfg(feature = "bevy_reflect")]
     pub use crate::reflect::{
-        AppTypeRegistry, ReflectComponent, ReflectFromWorld, ReflectResource,
+        AppTypeRegistry, ReflectComponent, ReflectEvent, ReflectFromWorld, ReflectResource,
     };
 
     #[doc(hidden)]
@@ -136,7 +137,7 @@ mod tests {
         entity::Entity,
         entity_disabling::DefaultQueryFilters,
         prelude::Or,
-        query::{Added, Changed, FilteredAccess, QueryFilter, With, Without},
+        query::{Added, Changed, FilteredAccess, Has, QueryFilter, With, Without},
         resource::Resource,
         world::{EntityMut, EntityRef, Mut, World},
     };
@@ -1909,6 +1910,145 @@ mod tests {
         );
     }
 
+    #[test]
+    fn insert_and_remove_by_query() {
+        let mut world = World::default();
+        let e0 = world.spawn(A(0)).id();
+        let e1 = world.spawn((A(1), SparseStored(0))).id();
+        let e2 = world.spawn((A(2), B(0))).id();
+        let e3 = world.spawn(B(1)).id();
+
+        world.insert_by_query::<(With<A>, Without<B>), _>((B(5), C));
+        let mut query = world.query::<(Option<&B>, Has<C>)>();
+        assert_eq!(
+            query.get_many(&world, [e0, e1, e2, e3]).unwrap(),
+            [
+                (Some(&B(5)), true),
+                (Some(&B(5)), true),
+                (Some(&B(0)), false),
+                (Some(&B(1)), false)
+            ]
+        );
+
+        world.insert_by_query_if_new::<With<A>, _>(B(9));
+        assert_eq!(world.get::<B>(e2), Some(&B(0)));
+
+        world.remove_by_query::<With<C>, (B, C)>();
+        let mut query = world.query::<(Has<B>, Has<C>)>();
+        assert_eq!(
+            query.get_many(&world, [e0, e1, e2, e3]).unwrap(),
+            [(false, false), (false, false), (true, false), (true, false)]
+        );
+    }
+
+    #[test]
+    fn insert_by_query_runs_hooks() {
+        use crate::{component::HookContext, system::Commands, world::DeferredWorld};
+
+        #[derive(Component, Clone)]
+        #[component(on_add = count_add)]
+        struct Counted;
+
+        #[derive(Resource, Default)]
+        struct Adds(usize);
+
+        fn count_add(mut world: DeferredWorld, _: HookContext) {
+            world.resource_mut::<Adds>().0 += 1;
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Adds>();
+        world.spawn_batch([A(0), A(1), A(2)]);
+        world.spawn((A(3), Counted));
+
+        world
+            .run_system_cached(|mut commands: Commands| {
+                commands.insert_by_query::<With<A>, _>(Counted);
+            })
+            .unwrap();
+
+        assert_eq!(world.resource::<Adds>().0, 4);
+        assert_eq!(world.query::<&Counted>().iter(&world).count(), 4);
+    }
+
+    #[test]
+    fn by_query_moves_whole_archetypes() {
+        use crate::{component::HookContext, world::DeferredWorld};
+
+        #[derive(Component, Clone)]
+        #[component(on_add = count_tagged, on_remove = count_tagged)]
+        struct Tagged;
+
+        /// The entities of the test, and how many of them had `Tagged` each time a hook ran.
+        #[derive(Resource, Default)]
+        struct Tracked(Vec<Entity>, Vec<usize>);
+
+        fn count_tagged(mut world: DeferredWorld, _: HookContext) {
+            let entities = world.resource::<Tracked>().0.clone();
+            let tagged = entities
+                .iter()
+                .filter(|&&entity| world.get::<Tagged>(entity).is_some())
+                .count();
+            world.resource_mut::<Tracked>().1.push(tagged);
+        }
+
+        let mut world = World::default();
+        let entities = world.spawn_batch((0..3).map(A)).collect::<Vec<_>>();
+        world.insert_resource(Tracked(entities.clone(), Vec::new()));
+
+        // Every entity is moved before the `on_add` hooks run, and is still in place when the
+        // `on_remove` hooks run, as the archetype is moved at once.
+        world.insert_by_query::<With<A>, _>(Tagged);
+        assert_eq!(world.resource::<Tracked>().1, [3, 3, 3]);
+        world.remove_by_query::<With<Tagged>, Tagged>();
+        assert_eq!(world.resource::<Tracked>().1, [3, 3, 3, 3, 3, 3]);
+
+        let table_id = world.entity(entities[0]).location().table_id;
+        assert_eq!(
+            world.storages().tables[table_id].entities(),
+            entities.as_slice()
+        );
+        let mut query = world.query::<(&A, Has<Tagged>)>();
+        assert_eq!(
+            query
+                .get_many(&world, [entities[0], entities[1], entities[2]])
+                .unwrap(),
+            [(&A(0), false), (&A(1), false), (&A(2), false)]
+        );
+    }
+
+    #[test]
+    fn by_query_moves_matching_entities_of_archetypes() {
+        let mut world = World::default();
+        let old = world.spawn_batch([A(0), A(1)]).collect::<Vec<_>>();
+        world.clear_trackers();
+        let new = world.spawn_batch([A(2), A(3)]).collect::<Vec<_>>();
+
+        // Only some of the entities of the archetype match.
+        world.insert_by_query::<Added<A>, _>(B(7));
+        let mut query = world.query::<(&A, Option<&B>)>();
+        assert_eq!(
+            query
+                .get_many(&world, [old[0], old[1], new[0], new[1]])
+                .unwrap(),
+            [
+                (&A(0), None),
+                (&A(1), None),
+                (&A(2), Some(&B(7))),
+                (&A(3), Some(&B(7)))
+            ]
+        );
+
+        // Sparse set components are moved within the same table.
+        world.insert_by_query::<With<B>, _>(SparseStored(1));
+        world.remove_by_query::<With<SparseStored>, SparseStored>();
+        let mut query = world.query::<(&A, Has<SparseStored>)>();
+        assert_eq!(
+            query.get_many(&world, [new[0], new[1]]).unwrap(),
+            [(&A(2), false), (&A(3), false)]
+        );
+    }
+
     #[test]
     fn required_components() {
         #[derive(Component)]
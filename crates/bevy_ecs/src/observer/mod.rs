@@ -1,9 +1,11 @@
 //! Types for creating and storing [`Observer`]s
 
 mod entity_observer;
+mod reactive;
 mod runner;
 
 pub use entity_observer::ObservedBy;
+pub use reactive::*;
 pub use runner::*;
 use variadics_please::all_tuples;
 
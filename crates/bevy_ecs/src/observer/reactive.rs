@@ -0,0 +1,486 @@
+//! Observer events triggered by changes that don't go through component hooks: mutations of
+//! components, and entities starting or stopping to match a query filter.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::{
+    change_detection::Mut,
+    component::{ComponentTicks, StorageType},
+    entity::{hash_set::EntityHashSet, Entity},
+    event::Event,
+    observer::{Observer, Trigger},
+    query::{ArchetypeFilter, QueryState},
+    resource::Resource,
+    schedule::{InternedScheduleLabel, ScheduleLabel, Schedules},
+    system::Commands,
+    world::{OnAdd, OnRemove, World},
+};
+
+/// Trigger emitted when a component is mutated through [`Mut`](crate::change_detection::Mut) or
+/// replaced by an insertion, but not when it is first added.
+///
+/// Mutations can't be observed as they happen: they are detected with change ticks, and reported
+/// each time the [`trigger_mutations`] system runs. When the [`MutationTriggerSchedule`] resource
+/// is present, the system is added to its schedule as soon as the first observer of this event is
+/// added. The `App` inserts it with the `Last` schedule.
+///
+/// Observers of this event must watch specific components, like `Trigger<OnMutate, Health>`.
+/// Only the components watched by at least one observer are checked for mutations.
+///
+/// ```
+/// # use bevy_ecs::{observer::{trigger_mutations, OnMutate}, prelude::*};
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Resource, Default)]
+/// struct Mutations(u32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<Mutations>();
+/// world.add_observer(|_: Trigger<OnMutate, Health>, mut mutations: ResMut<Mutations>| {
+///     mutations.0 += 1;
+/// });
+///
+/// let entity = world.spawn(Health(10)).id();
+/// world.run_system_cached(trigger_mutations).unwrap();
+/// assert_eq!(world.resource::<Mutations>().0, 0);
+///
+/// world.get_mut::<Health>(entity).unwrap().0 -= 1;
+/// world.run_system_cached(trigger_mutations).unwrap();
+/// assert_eq!(world.resource::<Mutations>().0, 1);
+/// ```
+#[derive(Debug)]
+pub struct OnMutate;
+
+impl Event for OnMutate {
+    type Traversal = ();
+
+    fn on_observer_added(world: &mut World) {
+        add_trigger_mutations(world);
+    }
+}
+
+/// The schedule [`trigger_mutations`] is added to once an [`OnMutate`] observer exists.
+///
+/// Without this resource, [`trigger_mutations`] has to be run manually.
+#[derive(Resource, Debug)]
+pub struct MutationTriggerSchedule {
+    label: InternedScheduleLabel,
+    added: bool,
+}
+
+impl MutationTriggerSchedule {
+    /// Creates a [`MutationTriggerSchedule`] adding [`trigger_mutations`] to the `label` schedule.
+    pub fn new(label: impl ScheduleLabel) -> Self {
+        Self {
+            label: label.intern(),
+            added: false,
+        }
+    }
+}
+
+/// Adds [`trigger_mutations`] to the [`MutationTriggerSchedule`], if it wasn't already.
+///
+/// A schedule is removed from [`Schedules`] while it runs, and would overwrite any system added to
+/// it in the meantime. Observers added at this point leave the system to the next one.
+fn add_trigger_mutations(world: &mut World) {
+    let Some(label) = world
+        .get_resource::<MutationTriggerSchedule>()
+        .filter(|schedule| !schedule.added)
+        .map(|schedule| schedule.label)
+    else {
+        return;
+    };
+    let Some(mut schedules) = world.get_resource_mut::<Schedules>() else {
+        return;
+    };
+    if !schedules.contains(label) {
+        return;
+    }
+    schedules.add_systems(label, trigger_mutations);
+    world.resource_mut::<MutationTriggerSchedule>().added = true;
+}
+
+/// An exclusive system triggering [`OnMutate`] for every component watched by an observer that
+/// was mutated since the last time the system ran.
+///
+/// Mutations made by the observers themselves are reported the next time the system runs.
+pub fn trigger_mutations(world: &mut World) {
+    let Some(event_id) = OnMutate::component_id(world) else {
+        return;
+    };
+    let Some(observers) = world.observers.try_get_observers(event_id) else {
+        return;
+    };
+    // Components observed on specific entities only need to be checked on these entities.
+    let watched = observers
+        .component_observers
+        .iter()
+        .map(|(&component_id, observers)| {
+            let entities = observers
+                .map
+                .is_empty()
+                .then(|| observers.entity_map.keys().copied().collect::<Vec<_>>());
+            (component_id, entities)
+        })
+        .collect::<Vec<_>>();
+
+    let last_run = world.last_change_tick();
+    let this_run = world.change_tick();
+    let is_mutated = |ticks: ComponentTicks| {
+        ticks.is_changed(last_run, this_run) && !ticks.is_added(last_run, this_run)
+    };
+
+    let mut mutations = Vec::new();
+    for (component_id, entities) in watched {
+        match entities {
+            Some(entities) => mutations.extend(
+                entities
+                    .into_iter()
+                    .filter(|&entity| {
+                        world
+                            .get_entity(entity)
+                            .ok()
+                            .and_then(|entity| entity.get_change_ticks_by_id(component_id))
+                            .is_some_and(is_mutated)
+                    })
+                    .map(|entity| (entity, component_id)),
+            ),
+            None => {
+                let Some(info) = world.components().get_info(component_id) else {
+                    continue;
+                };
+                let storages = world.storages();
+                let sparse_set = storages.sparse_sets.get(component_id);
+                for archetype in world.archetypes().iter() {
+                    if !archetype.contains(component_id) {
+                        continue;
+                    }
+                    let table = &storages.tables[archetype.table_id()];
+                    let entities = archetype.entities().iter().filter(|entity| {
+                        let ticks = match info.storage_type() {
+                            // SAFETY: The rows of the archetype's entities are in its table.
+                            StorageType::Table => unsafe {
+                                table.get_ticks_unchecked(component_id, entity.table_row())
+                            },
+                            StorageType::SparseSet => {
+                                sparse_set.and_then(|set| set.get_ticks(entity.id()))
+                            }
+                        };
+                        ticks.is_some_and(is_mutated)
+                    });
+                    mutations.extend(entities.map(|entity| (entity.id(), component_id)));
+                }
+            }
+        }
+    }
+
+    for (entity, component_id) in mutations {
+        world.trigger_targets(OnMutate, (entity, component_id));
+    }
+}
+
+/// Trigger emitted when an entity starts matching the query filter `F`, targeting that entity.
+///
+/// Entities start or stop matching a filter when one of the components it mentions is added or
+/// removed, so the match is checked after each of these changes. Unless the filter mentions
+/// them, disabled entities don't match it: disabling a matching entity triggers [`OnUnmatch`],
+/// and enabling it again triggers [`OnMatch`].
+///
+/// A filter that doesn't require any component, like `Without<Hidden>`, matches entities as
+/// soon as they are spawned, but this is only noticed once one of its components is added or
+/// removed. Require at least one component, for example with [`With`](crate::query::With), for
+/// these entities to be reported when they are spawned.
+///
+/// Entities that already match the filter when the first observer of this event is added aren't
+/// reported, just like [`OnAdd`] isn't triggered for the components that were already added.
+///
+/// ```
+/// # use bevy_ecs::{observer::OnMatch, prelude::*};
+/// #[derive(Component)]
+/// struct Button;
+///
+/// #[derive(Component)]
+/// struct Disabled;
+///
+/// #[derive(Resource, Default)]
+/// struct Enabled(Vec<Entity>);
+///
+/// let mut world = World::new();
+/// world.init_resource::<Enabled>();
+/// world.add_observer(
+///     |trigger: Trigger<OnMatch<(With<Button>, Without<Disabled>)>>, mut enabled: ResMut<Enabled>| {
+///         enabled.0.push(trigger.target());
+///     },
+/// );
+/// world.flush();
+///
+/// let button = world.spawn((Button, Disabled)).id();
+/// world.entity_mut(button).remove::<Disabled>();
+/// assert_eq!(world.resource::<Enabled>().0, [button]);
+/// ```
+pub struct OnMatch<F: ArchetypeFilter>(PhantomData<fn() -> F>);
+
+/// Trigger emitted when an entity stops matching the query filter `F`, targeting that entity.
+///
+/// This is also triggered when a matching entity is despawned. See [`OnMatch`] for more details.
+pub struct OnUnmatch<F: ArchetypeFilter>(PhantomData<fn() -> F>);
+
+impl<F: ArchetypeFilter + 'static> Event for OnMatch<F> {
+    type Traversal = ();
+
+    fn on_observer_added(world: &mut World) {
+        track_matches::<F>(world);
+    }
+}
+
+impl<F: ArchetypeFilter + 'static> Event for OnUnmatch<F> {
+    type Traversal = ();
+
+    fn on_observer_added(world: &mut World) {
+        track_matches::<F>(world);
+    }
+}
+
+/// The entities matching the query filter `F`, for [`OnMatch`] and [`OnUnmatch`].
+#[derive(Resource)]
+struct MatchTracker<F: ArchetypeFilter + 'static> {
+    state: QueryState<Entity, F>,
+    matching: EntityHashSet,
+}
+
+/// Starts tracking the entities matching `F`, if it isn't already.
+fn track_matches<F: ArchetypeFilter + 'static>(world: &mut World) {
+    if world.contains_resource::<MatchTracker<F>>() {
+        return;
+    }
+
+    let mut state = QueryState::<Entity, F>::new(world);
+    let access = state.component_access();
+    // This includes the disabling components of the default query filters, so disabling a
+    // matching entity makes it stop matching.
+    let mut components = access
+        .with_filters()
+        .chain(access.without_filters())
+        .collect::<Vec<_>>();
+    components.sort_unstable();
+    components.dedup();
+
+    let matching = state.iter(world).collect::<EntityHashSet>();
+    world.insert_resource(MatchTracker { state, matching });
+
+    let mut on_add = Observer::new(|trigger: Trigger<OnAdd>, mut commands: Commands| {
+        let entity = trigger.target();
+        commands.queue(move |world: &mut World| update_match::<F>(world, entity));
+    });
+    let mut on_remove = Observer::new(|trigger: Trigger<OnRemove>, mut commands: Commands| {
+        let entity = trigger.target();
+        commands.queue(move |world: &mut World| update_match::<F>(world, entity));
+    });
+    for component in components {
+        on_add = on_add.with_component(component);
+        on_remove = on_remove.with_component(component);
+    }
+    world.spawn(on_add);
+    world.spawn(on_remove);
+}
+
+/// Checks whether the `entity` started or stopped matching `F`, and triggers the observers.
+fn update_match<F: ArchetypeFilter + 'static>(world: &mut World, entity: Entity) {
+    let changed = world.resource_scope(|world, mut tracker: Mut<MatchTracker<F>>| {
+        let matches = tracker.state.get(world, entity).is_ok();
+        if matches {
+            tracker.matching.insert(entity).then_some(true)
+        } else {
+            tracker.matching.remove(&entity).then_some(false)
+        }
+    });
+    match changed {
+        Some(true) => world.trigger_targets(OnMatch::<F>(PhantomData), entity),
+        Some(false) => world.trigger_targets(OnUnmatch::<F>(PhantomData), entity),
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{entity_disabling::Disabled, prelude::*, schedule::Schedule};
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Component)]
+    struct A(u32);
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<(&'static str, Entity)>);
+
+    #[test]
+    fn mutations() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let a = world.spawn(A(0)).id();
+        let b = world.spawn(A(0)).id();
+        let observed = world.spawn(A(0)).id();
+        world.add_observer(|trigger: Trigger<OnMutate, A>, mut log: ResMut<Log>| {
+            log.0.push(("global", trigger.target()));
+        });
+        world.entity_mut(observed).observe(
+            |trigger: Trigger<OnMutate, A>, mut log: ResMut<Log>| {
+                log.0.push(("entity", trigger.target()));
+            },
+        );
+        world.run_system_cached(trigger_mutations).unwrap();
+        assert!(world.resource::<Log>().0.is_empty());
+
+        world.get_mut::<A>(a).unwrap().0 = 1;
+        world.get_mut::<A>(observed).unwrap().0 = 1;
+        world.entity_mut(b).insert(B);
+        world.spawn(A(0));
+        world.run_system_cached(trigger_mutations).unwrap();
+
+        let mut log = world.resource_mut::<Log>();
+        log.0.sort();
+        assert_eq!(
+            log.0,
+            vec![("entity", observed), ("global", a), ("global", observed)]
+        );
+        log.0.clear();
+
+        world.run_system_cached(trigger_mutations).unwrap();
+        assert!(world.resource::<Log>().0.is_empty());
+    }
+
+    #[test]
+    fn sparse_set_mutations() {
+        #[derive(Component)]
+        #[component(storage = "SparseSet")]
+        struct S(u32);
+
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let a = world.spawn(S(0)).id();
+        world.spawn(S(0));
+        world.add_observer(|trigger: Trigger<OnMutate, S>, mut log: ResMut<Log>| {
+            log.0.push(("global", trigger.target()));
+        });
+        world.run_system_cached(trigger_mutations).unwrap();
+
+        world.get_mut::<S>(a).unwrap().0 = 1;
+        world.run_system_cached(trigger_mutations).unwrap();
+        assert_eq!(world.resource::<Log>().0, vec![("global", a)]);
+    }
+
+    #[test]
+    fn mutations_system_is_added_with_the_first_observer() {
+        #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct Update;
+
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.add_schedule(Schedule::new(Update));
+        world.insert_resource(MutationTriggerSchedule::new(Update));
+        world.add_observer(|_: Trigger<OnAdd, A>| {});
+        world.flush();
+        world.schedule_scope(Update, |_, schedule| assert_eq!(schedule.systems_len(), 0));
+
+        let entity = world.spawn(A(0)).id();
+        world.add_observer(|trigger: Trigger<OnMutate, A>, mut log: ResMut<Log>| {
+            log.0.push(("mutated", trigger.target()));
+        });
+        world.add_observer(|_: Trigger<OnMutate, B>| {});
+        world.flush();
+        world.schedule_scope(Update, |_, schedule| assert_eq!(schedule.systems_len(), 1));
+
+        world.run_schedule(Update);
+        world.get_mut::<A>(entity).unwrap().0 = 1;
+        world.run_schedule(Update);
+        assert_eq!(world.resource::<Log>().0, vec![("mutated", entity)]);
+    }
+
+    #[test]
+    fn matches() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let existing = world.spawn(A(0)).id();
+        world.add_observer(
+            |trigger: Trigger<OnMatch<(With<A>, Without<B>)>>, mut log: ResMut<Log>| {
+                log.0.push(("match", trigger.target()));
+            },
+        );
+        world.add_observer(
+            |trigger: Trigger<OnUnmatch<(With<A>, Without<B>)>>, mut log: ResMut<Log>| {
+                log.0.push(("unmatch", trigger.target()));
+            },
+        );
+        world.flush();
+
+        let entity = world.spawn((A(0), B)).id();
+        world.entity_mut(entity).remove::<B>();
+        world.entity_mut(entity).insert(A(1));
+        world.entity_mut(existing).insert(B);
+        world.despawn(entity);
+
+        assert_eq!(
+            world.resource::<Log>().0,
+            vec![
+                ("match", entity),
+                ("unmatch", existing),
+                ("unmatch", entity)
+            ]
+        );
+    }
+
+    #[test]
+    fn disabling_unmatches() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.add_observer(|trigger: Trigger<OnMatch<With<A>>>, mut log: ResMut<Log>| {
+            log.0.push(("match", trigger.target()));
+        });
+        world.add_observer(
+            |trigger: Trigger<OnUnmatch<With<A>>>, mut log: ResMut<Log>| {
+                log.0.push(("unmatch", trigger.target()));
+            },
+        );
+        world.flush();
+
+        let entity = world.spawn(A(0)).id();
+        world.entity_mut(entity).insert(Disabled);
+        world.entity_mut(entity).remove::<Disabled>();
+
+        assert_eq!(
+            world.resource::<Log>().0,
+            vec![("match", entity), ("unmatch", entity), ("match", entity)]
+        );
+    }
+
+    #[test]
+    fn filters_without_required_components() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.add_observer(
+            |trigger: Trigger<OnMatch<Without<B>>>, mut log: ResMut<Log>| {
+                log.0.push(("match", trigger.target()));
+            },
+        );
+        world.add_observer(
+            |trigger: Trigger<OnUnmatch<Without<B>>>, mut log: ResMut<Log>| {
+                log.0.push(("unmatch", trigger.target()));
+            },
+        );
+        world.flush();
+
+        let entity = world.spawn((A(0), B)).id();
+        world.entity_mut(entity).remove::<B>();
+        world.entity_mut(entity).insert(B);
+
+        assert_eq!(
+            world.resource::<Log>().0,
+            vec![("match", entity), ("unmatch", entity)]
+        );
+    }
+}
@@ -434,6 +434,7 @@ fn hook_on_add<E: Event, B: Bundle, S: ObserverSystem<E, B>>(
 ) {
     world.commands().queue(move |world: &mut World| {
         let event_id = E::register_component_id(world);
+        E::on_observer_added(world);
         let mut components = Vec::new();
         B::component_ids(&mut world.components, &mut |id| {
             components.push(id);
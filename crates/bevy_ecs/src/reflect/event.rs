@@ -0,0 +1,247 @@
+//! Definitions for [`Event`] reflection.
+//! This allows sending, triggering and reading events whose types are only known at runtime.
+//!
+//! This module exports two types: [`ReflectEventFns`] and [`ReflectEvent`].
+//!
+//! See the module doc for [`crate::reflect::component`] for the general architecture.
+
+use crate::{
+    entity::Entity,
+    event::{Event, Events},
+    world::World,
+};
+use alloc::vec::Vec;
+use bevy_reflect::{FromReflect, FromType, PartialReflect, Reflect, TypePath, TypeRegistry};
+
+use super::from_reflect_with_fallback;
+
+/// A struct used to operate on reflected [`Event`]s of a type.
+///
+/// A [`ReflectEvent`] for type `T` can be obtained via
+/// [`bevy_reflect::TypeRegistration::data`].
+#[derive(Clone)]
+pub struct ReflectEvent(ReflectEventFns);
+
+/// The raw function pointers needed to make up a [`ReflectEvent`].
+///
+/// This is used when creating custom implementations of [`ReflectEvent`] with
+/// [`ReflectEvent::new()`].
+///
+/// > **Note:**
+/// > Creating custom implementations of [`ReflectEvent`] is an advanced feature that most users
+/// > will not need.
+/// > Usually a [`ReflectEvent`] is created for a type by deriving [`Reflect`]
+/// > and adding the `#[reflect(Event)]` attribute.
+/// > After adding the event to the [`TypeRegistry`],
+/// > its [`ReflectEvent`] can then be retrieved when needed.
+#[derive(Clone)]
+pub struct ReflectEventFns {
+    /// Function pointer implementing [`ReflectEvent::send()`].
+    pub send: fn(&mut World, &dyn PartialReflect, &TypeRegistry) -> Option<usize>,
+    /// Function pointer implementing [`ReflectEvent::trigger()`].
+    pub trigger: fn(&mut World, &dyn PartialReflect, &[Entity], &TypeRegistry),
+    /// Function pointer implementing [`ReflectEvent::event_count()`].
+    pub event_count: fn(&World) -> Option<usize>,
+    /// Function pointer implementing [`ReflectEvent::read()`].
+    pub read: for<'w> fn(&'w World, &mut usize) -> Option<Vec<&'w dyn Reflect>>,
+    /// Function pointer implementing [`ReflectEvent::current_update_events()`].
+    pub current_update_events: for<'w> fn(&'w World) -> Option<Vec<&'w dyn Reflect>>,
+}
+
+impl ReflectEventFns {
+    /// Get the default set of [`ReflectEventFns`] for a specific event type using its
+    /// [`FromType`] implementation.
+    ///
+    /// This is useful if you want to start with the default implementation before overriding some
+    /// of the functions to create a custom implementation.
+    pub fn new<T: Event + FromReflect + TypePath>() -> Self {
+        <ReflectEvent as FromType<T>>::from_type().0
+    }
+}
+
+impl ReflectEvent {
+    /// Sends a reflected [`Event`] into its [`Events`] resource like [`World::send_event`].
+    ///
+    /// Returns the id of the sent event, or [`None`] if the [`Events`] resource for this
+    /// event type has not been added to the world.
+    pub fn send(
+        &self,
+        world: &mut World,
+        event: &dyn PartialReflect,
+        registry: &TypeRegistry,
+    ) -> Option<usize> {
+        (self.0.send)(world, event, registry)
+    }
+
+    /// Triggers a reflected [`Event`] like [`World::trigger_targets`], running any observers watching for it.
+    ///
+    /// If `targets` is empty, the event is triggered without targets like [`World::trigger`].
+    pub fn trigger(
+        &self,
+        world: &mut World,
+        event: &dyn PartialReflect,
+        targets: &[Entity],
+        registry: &TypeRegistry,
+    ) {
+        (self.0.trigger)(world, event, targets, registry);
+    }
+
+    /// Returns the total number of events of this type sent so far,
+    /// or [`None`] if the [`Events`] resource for this event type has not been added to the world.
+    ///
+    /// Passing this value as the cursor to [`ReflectEvent::read`] only yields events sent afterwards.
+    pub fn event_count(&self, world: &World) -> Option<usize> {
+        (self.0.event_count)(world)
+    }
+
+    /// Reads every event of this type that is still buffered and was sent at or after `cursor`,
+    /// then advances `cursor` past the most recent event.
+    ///
+    /// This is the type-erased equivalent of [`EventCursor::read`](crate::event::EventCursor::read).
+    /// Returns [`None`] if the [`Events`] resource for this event type has not been added to the world.
+    pub fn read<'w>(&self, world: &'w World, cursor: &mut usize) -> Option<Vec<&'w dyn Reflect>> {
+        (self.0.read)(world, cursor)
+    }
+
+    /// Returns every event of this type sent since the last [`Events::update`],
+    /// like [`Events::iter_current_update_events`].
+    ///
+    /// Returns [`None`] if the [`Events`] resource for this event type has not been added to the world.
+    pub fn current_update_events<'w>(&self, world: &'w World) -> Option<Vec<&'w dyn Reflect>> {
+        (self.0.current_update_events)(world)
+    }
+
+    /// Create a custom implementation of [`ReflectEvent`].
+    ///
+    /// This is an advanced feature,
+    /// useful for scripting implementations,
+    /// that should not be used by most users
+    /// unless you know what you are doing.
+    ///
+    /// Usually you should derive [`Reflect`] and add the `#[reflect(Event)]` attribute
+    /// to generate a [`ReflectEvent`] implementation automatically.
+    ///
+    /// See [`ReflectEventFns`] for more information.
+    pub fn new(fns: ReflectEventFns) -> Self {
+        Self(fns)
+    }
+
+    /// The underlying function pointers implementing methods on `ReflectEvent`.
+    ///
+    /// This is useful when you want to keep track locally of an individual
+    /// function pointer.
+    pub fn fn_pointers(&self) -> &ReflectEventFns {
+        &self.0
+    }
+}
+
+impl<E: Event + FromReflect + TypePath> FromType<E> for ReflectEvent {
+    fn from_type() -> Self {
+        ReflectEvent(ReflectEventFns {
+            send: |world, reflected_event, registry| {
+                let event = from_reflect_with_fallback::<E>(reflected_event, world, registry);
+                world.send_event(event).map(|id| id.id)
+            },
+            trigger: |world, reflected_event, targets, registry| {
+                let event = from_reflect_with_fallback::<E>(reflected_event, world, registry);
+                if targets.is_empty() {
+                    world.trigger(event);
+                } else {
+                    world.trigger_targets(event, targets);
+                }
+            },
+            event_count: |world| {
+                world
+                    .get_resource::<Events<E>>()
+                    .map(|events| events.event_count)
+            },
+            read: |world, cursor| {
+                let events = world.get_resource::<Events<E>>()?;
+                let start = (*cursor).max(events.oldest_event_count());
+                let read = (start..events.event_count)
+                    .filter_map(|id| events.get_event(id))
+                    .map(|(event, _)| event as &dyn Reflect)
+                    .collect();
+                *cursor = events.event_count;
+                Some(read)
+            },
+            current_update_events: |world| {
+                let events = world.get_resource::<Events<E>>()?;
+                Some(
+                    events
+                        .iter_current_update_events()
+                        .map(|event| event as &dyn Reflect)
+                        .collect(),
+                )
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        event::{Event, Events},
+        observer::Trigger,
+        reflect::ReflectEvent,
+        resource::Resource,
+        system::ResMut,
+        world::World,
+    };
+    use alloc::vec::Vec;
+    use bevy_reflect::{FromType, Reflect, TypeRegistry};
+
+    #[derive(Event, Reflect, Debug, PartialEq)]
+    struct Ping(u32);
+
+    #[derive(Resource, Default)]
+    struct Triggered(u32);
+
+    #[test]
+    fn send_and_read_reflected_events() {
+        let mut world = World::new();
+        let mut registry = TypeRegistry::default();
+        registry.register::<Ping>();
+        let reflect_event = <ReflectEvent as FromType<Ping>>::from_type();
+
+        assert_eq!(reflect_event.send(&mut world, &Ping(0), &registry), None);
+        assert!(reflect_event.event_count(&world).is_none());
+
+        world.init_resource::<Events<Ping>>();
+        world.send_event(Ping(1));
+        let mut cursor = reflect_event.event_count(&world).unwrap();
+        assert_eq!(reflect_event.send(&mut world, &Ping(2), &registry), Some(1));
+        reflect_event.send(&mut world, &Ping(3), &registry);
+
+        let read = reflect_event.read(&world, &mut cursor).unwrap();
+        let read = read
+            .into_iter()
+            .map(|event| event.downcast_ref::<Ping>().unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(read, [2, 3]);
+        assert!(reflect_event.read(&world, &mut cursor).unwrap().is_empty());
+
+        world.resource_mut::<Events<Ping>>().update();
+        reflect_event.send(&mut world, &Ping(4), &registry);
+        let current = reflect_event.current_update_events(&world).unwrap();
+        assert_eq!(current.len(), 1);
+        assert_eq!(current[0].downcast_ref::<Ping>(), Some(&Ping(4)));
+    }
+
+    #[test]
+    fn trigger_reflected_events() {
+        let mut world = World::new();
+        let mut registry = TypeRegistry::default();
+        registry.register::<Ping>();
+        let reflect_event = <ReflectEvent as FromType<Ping>>::from_type();
+
+        world.init_resource::<Triggered>();
+        world.add_observer(|trigger: Trigger<Ping>, mut triggered: ResMut<Triggered>| {
+            triggered.0 += trigger.event().0;
+        });
+        world.flush();
+
+        reflect_event.trigger(&mut world, &Ping(4), &[], &registry);
+        assert_eq!(world.resource::<Triggered>().0, 4);
+    }
+}
@@ -0,0 +1,495 @@
+use alloc::{format, vec::Vec};
+use log::warn;
+
+use crate::{
+    component::{Component, HookContext, Mutable},
+    entity::{hash_set::EntityHashSet, ComponentCloneCtx, Entity, SourceComponent},
+    system::{
+        command::HandleError,
+        entity_command::{self, CommandWithEntity},
+        error_handler, Commands,
+    },
+    world::{DeferredWorld, EntityWorldMut},
+};
+
+use super::{EntitySourceCollection, RelationshipInsertHookMode, RelationshipSourceCollection};
+
+/// A [`Component`] on a "source" [`Entity`] that references any number of target entities, creating a many-to-many
+/// "relationship" between them. Every [`ManyRelationship`] has a corresponding [`ManyRelationshipTarget`] type
+/// (and vice-versa), which exists on each "target" entity of a relationship and contains the list of all "source"
+/// entities that relate to the given "target".
+///
+/// This is the many-to-many counterpart of [`Relationship`](super::Relationship), which only supports a single target
+/// per source. The [`ManyRelationship`] component is the "source of truth" and the [`ManyRelationshipTarget`] components
+/// reflect that source of truth: they are kept up to date via "component hooks" in the same way.
+///
+/// [`ManyRelationship`] components are immutable, so the set of targets can only be changed by inserting a new
+/// [`ManyRelationship`] component, or with methods like [`EntityWorldMut::add_many_related`].
+///
+/// ## Derive
+///
+/// [`ManyRelationship`] and [`ManyRelationshipTarget`] are derived like [`Relationship`](super::Relationship)
+/// and [`RelationshipTarget`](super::RelationshipTarget), with the `many` attribute. The relationship field of both
+/// components is a [`RelationshipSourceCollection`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::relationship::ManyRelationshipTarget;
+/// #[derive(Component)]
+/// #[relationship(relationship_target = SquadMembers, many)]
+/// pub struct InSquads(pub Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[relationship_target(relationship = InSquads, many)]
+/// pub struct SquadMembers(Vec<Entity>);
+///
+/// let mut world = World::new();
+/// let alpha = world.spawn_empty().id();
+/// let bravo = world.spawn_empty().id();
+/// let unit = world.spawn(InSquads(vec![alpha, bravo])).id();
+///
+/// assert_eq!(world.entity(alpha).get::<SquadMembers>().unwrap().0, [unit]);
+/// assert_eq!(world.entity(bravo).get::<SquadMembers>().unwrap().0, [unit]);
+/// ```
+///
+/// Targets that don't exist, that are the source itself or that are listed more than once are removed from the
+/// [`ManyRelationship`] when it is inserted.
+///
+/// As with [`RelationshipTarget`](super::RelationshipTarget), the `#[relationship_target(many, linked_spawn)]`
+/// attribute despawns the entities stored in a [`ManyRelationshipTarget`] when its entity is despawned, even if
+/// they also relate to other entities.
+pub trait ManyRelationship: Component + Sized {
+    /// The [`Component`] added to the "target" entities of this [`ManyRelationship`], which contains the list of all
+    /// "source" entities that relate to the "target".
+    type RelationshipTarget: ManyRelationshipTarget<Relationship = Self>;
+    /// The collection type that stores the "target" entities of this [`ManyRelationship`].
+    type Collection: EntitySourceCollection;
+
+    /// Returns a reference to the stored [`ManyRelationship::Collection`] of targets.
+    fn targets(&self) -> &Self::Collection;
+
+    /// Creates this [`ManyRelationship`] from the given collection of `targets`.
+    fn from_targets(targets: Self::Collection) -> Self;
+
+    /// Iterates the target entities of this relationship.
+    #[inline]
+    fn iter(&self) -> TargetIter<'_, Self> {
+        self.targets().iter()
+    }
+
+    /// Returns true if the given `entity` is a target of this relationship.
+    #[inline]
+    fn contains(&self, entity: Entity) -> bool {
+        self.iter().any(|target| target == entity)
+    }
+
+    /// The `on_insert` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`] connection.
+    fn on_insert(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            caller,
+            relationship_insert_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        match relationship_insert_hook_mode {
+            RelationshipInsertHookMode::Run => {}
+            RelationshipInsertHookMode::Skip => return,
+            RelationshipInsertHookMode::RunIfNotLinked => {
+                if <Self::RelationshipTarget as ManyRelationshipTarget>::LINKED_SPAWN {
+                    return;
+                }
+            }
+        }
+        let targets: Vec<Entity> = world.entity(entity).get::<Self>().unwrap().iter().collect();
+        let mut seen = EntityHashSet::with_capacity(targets.len());
+        let mut invalid = false;
+        for target_entity in targets {
+            if target_entity == entity || !seen.insert(target_entity) {
+                warn!(
+                    "{}The {} relationship on entity {entity:?} targets {target_entity:?} more than once or points to itself. The invalid target has been removed.",
+                    caller.map(|location|format!("{location}: ")).unwrap_or_default(),
+                    core::any::type_name::<Self>(),
+                );
+                invalid = true;
+                continue;
+            }
+            if let Ok(mut target_entity_mut) = world.get_entity_mut(target_entity) {
+                if let Some(mut relationship_target) =
+                    target_entity_mut.get_mut::<Self::RelationshipTarget>()
+                {
+                    relationship_target.collection_mut_risky().add(entity);
+                } else {
+                    let mut target =
+                        <Self::RelationshipTarget as ManyRelationshipTarget>::with_capacity(1);
+                    target.collection_mut_risky().add(entity);
+                    world.commands().entity(target_entity).insert(target);
+                }
+            } else {
+                warn!(
+                    "{}The {} relationship on entity {entity:?} relates to {target_entity:?}, which does not exist. The invalid target has been removed.",
+                    caller.map(|location|format!("{location}: ")).unwrap_or_default(),
+                    core::any::type_name::<Self>(),
+                );
+                invalid = true;
+            }
+        }
+        if invalid {
+            world
+                .commands()
+                .entity(entity)
+                .queue(remove_invalid_targets::<Self>);
+        }
+    }
+
+    /// The `on_replace` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`] connection.
+    // note: think of this as "on_drop"
+    fn on_replace(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let targets: Vec<Entity> = world.entity(entity).get::<Self>().unwrap().iter().collect();
+        for target_entity in targets {
+            if target_entity == entity {
+                continue;
+            }
+            let Ok(mut target_entity_mut) = world.get_entity_mut(target_entity) else {
+                continue;
+            };
+            let Some(mut relationship_target) =
+                target_entity_mut.get_mut::<Self::RelationshipTarget>()
+            else {
+                continue;
+            };
+            relationship_target.collection_mut_risky().remove(entity);
+            if relationship_target.is_empty() {
+                if let Ok(mut entity) = world.commands().get_entity(target_entity) {
+                    // this "remove" operation must check emptiness because in the event that an identical
+                    // relationship is inserted on top, this despawn would result in the removal of that identical
+                    // relationship ... not what we want!
+                    entity.queue(|mut entity: EntityWorldMut| {
+                        if entity
+                            .get::<Self::RelationshipTarget>()
+                            .is_some_and(ManyRelationshipTarget::is_empty)
+                        {
+                            entity.remove::<Self::RelationshipTarget>();
+                        }
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// The iterator type for the target entities of a [`ManyRelationship`],
+/// as defined in the [`RelationshipSourceCollection`] trait.
+pub type TargetIter<'w, R> =
+    <<R as ManyRelationship>::Collection as RelationshipSourceCollection>::SourceIter<'w>;
+
+/// The iterator type for the source entities in a [`ManyRelationshipTarget`] collection,
+/// as defined in the [`RelationshipSourceCollection`] trait.
+pub type ManySourceIter<'w, S> =
+    <<S as ManyRelationshipTarget>::Collection as RelationshipSourceCollection>::SourceIter<'w>;
+
+/// A [`Component`] containing the collection of entities that relate to this [`Entity`] via the associated
+/// [`ManyRelationship`] type. See the [`ManyRelationship`] documentation for more information.
+pub trait ManyRelationshipTarget: Component<Mutability = Mutable> + Sized {
+    /// If this is true, when despawning the related entities targeting this entity will also be despawned,
+    /// even if they target other entities as well.
+    ///
+    /// This defaults to false when derived.
+    const LINKED_SPAWN: bool;
+    /// The [`ManyRelationship`] that populates this [`ManyRelationshipTarget`] collection.
+    type Relationship: ManyRelationship<RelationshipTarget = Self>;
+    /// The collection type that stores the "source" entities for this [`ManyRelationshipTarget`] component.
+    type Collection: EntitySourceCollection;
+
+    /// Returns a reference to the stored [`ManyRelationshipTarget::Collection`].
+    fn collection(&self) -> &Self::Collection;
+    /// Returns a mutable reference to the stored [`ManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the relationship.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates a new [`ManyRelationshipTarget`] from the given [`ManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as constructing the internal collection could invalidate the relationship.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// The `on_replace` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`] connection.
+    ///
+    /// Unlike [`RelationshipTarget::on_replace`](super::RelationshipTarget::on_replace), this only removes this
+    /// entity from the targets of each source, which keep their other targets.
+    // note: think of this as "on_drop"
+    fn on_replace(mut world: DeferredWorld, HookContext { entity, caller, .. }: HookContext) {
+        let sources: Vec<Entity> = world.entity(entity).get::<Self>().unwrap().iter().collect();
+        let mut commands = world.commands();
+        for source_entity in sources {
+            if commands.get_entity(source_entity).is_ok() {
+                commands.queue(
+                    (move |mut source: EntityWorldMut| {
+                        remove_targets::<Self::Relationship>(&mut source, &[entity]);
+                    })
+                    .with_entity(source_entity)
+                    .handle_error_with(error_handler::silent()),
+                );
+            } else {
+                warn!(
+                    "{}Tried to unlink non-existent entity {}",
+                    caller
+                        .map(|location| format!("{location}: "))
+                        .unwrap_or_default(),
+                    source_entity
+                );
+            }
+        }
+    }
+
+    /// The `on_despawn` component hook that despawns entities stored in an entity's [`ManyRelationshipTarget`] when
+    /// that entity is despawned.
+    // note: think of this as "on_drop"
+    fn on_despawn(mut world: DeferredWorld, HookContext { entity, caller, .. }: HookContext) {
+        let sources: Vec<Entity> = world.entity(entity).get::<Self>().unwrap().iter().collect();
+        let mut commands = world.commands();
+        for source_entity in sources {
+            if commands.get_entity(source_entity).is_ok() {
+                commands.queue(
+                    entity_command::despawn()
+                        .with_entity(source_entity)
+                        .handle_error_with(error_handler::silent()),
+                );
+            } else {
+                warn!(
+                    "{}Tried to despawn non-existent entity {}",
+                    caller
+                        .map(|location| format!("{location}: "))
+                        .unwrap_or_default(),
+                    source_entity
+                );
+            }
+        }
+    }
+
+    /// Creates this [`ManyRelationshipTarget`] with the given pre-allocated entity capacity.
+    fn with_capacity(capacity: usize) -> Self {
+        let collection =
+            <Self::Collection as RelationshipSourceCollection>::with_capacity(capacity);
+        Self::from_collection_risky(collection)
+    }
+
+    /// Iterates the entities stored in this collection.
+    #[inline]
+    fn iter(&self) -> ManySourceIter<'_, Self> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of entities in this collection.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if this entity collection is empty.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+}
+
+/// The "clone behavior" for [`ManyRelationshipTarget`]. This creates an empty [`ManyRelationshipTarget`] instance
+/// with space reserved for the number of sources in the original instance, which is then populated when
+/// [`ManyRelationship`] sources of truth targeting the clone are inserted.
+///
+/// Unlike [`clone_relationship_target`](super::clone_relationship_target), this never clones the sources, as they
+/// may relate to other entities too.
+pub fn clone_many_relationship_target<T: ManyRelationshipTarget>(
+    _commands: &mut Commands,
+    source: &SourceComponent,
+    context: &mut ComponentCloneCtx,
+) {
+    if let Some(component) = source.read::<T>() {
+        context.write_target_component(T::with_capacity(component.len()));
+    }
+}
+
+/// Adds the `targets` that the `R` relationship of the `source` entity doesn't target yet, inserting the
+/// relationship if needed.
+pub(crate) fn add_targets<R: ManyRelationship>(source: &mut EntityWorldMut, targets: &[Entity]) {
+    let existing = source.get::<R>();
+    let mut seen = existing
+        .into_iter()
+        .flat_map(R::iter)
+        .collect::<EntityHashSet>();
+    let new_targets = targets
+        .iter()
+        .copied()
+        .filter(|target| seen.insert(*target))
+        .collect::<Vec<_>>();
+    if new_targets.is_empty() {
+        return;
+    }
+    let mut collection = R::Collection::with_capacity(
+        existing.map_or(0, |existing| existing.targets().len()) + new_targets.len(),
+    );
+    for target in existing.into_iter().flat_map(R::iter) {
+        collection.add(target);
+    }
+    for target in new_targets {
+        collection.add(target);
+    }
+    source.insert(R::from_targets(collection));
+}
+
+/// Removes the `targets` from the `R` relationship of the `source` entity, removing the relationship once it
+/// has no targets left.
+pub(crate) fn remove_targets<R: ManyRelationship>(source: &mut EntityWorldMut, targets: &[Entity]) {
+    retain_targets::<R>(source, |_, target| !targets.contains(&target));
+}
+
+/// Re-inserts the `R` relationship of the `source` entity with only the targets for which `keep` returns true.
+fn retain_targets<R: ManyRelationship>(
+    source: &mut EntityWorldMut,
+    mut keep: impl FnMut(&EntityWorldMut, Entity) -> bool,
+) {
+    let Some(existing) = source.get::<R>() else {
+        return;
+    };
+    let kept: Vec<Entity> = existing
+        .iter()
+        .filter(|target| keep(source, *target))
+        .collect();
+    if kept.len() == existing.targets().len() {
+        return;
+    }
+    if kept.is_empty() {
+        source.remove::<R>();
+        return;
+    }
+    let mut collection = R::Collection::with_capacity(kept.len());
+    for target in kept {
+        collection.add(target);
+    }
+    source.insert(R::from_targets(collection));
+}
+
+/// Removes the targets of the `R` relationship which don't exist, are the entity itself or are duplicates.
+fn remove_invalid_targets<R: ManyRelationship>(mut source: EntityWorldMut) {
+    let id = source.id();
+    let mut seen = EntityHashSet::default();
+    retain_targets::<R>(&mut source, |source, target| {
+        target != id && source.world().get_entity(target).is_ok() && seen.insert(target)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        system::{In, Query},
+        world::World,
+    };
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Component)]
+    #[relationship(relationship_target = Contains, many)]
+    struct InContainers(Vec<Entity>);
+
+    #[derive(Component)]
+    #[relationship_target(relationship = InContainers, many)]
+    struct Contains(Vec<Entity>);
+
+    #[derive(Component)]
+    #[relationship(relationship_target = Members, many)]
+    struct InSquads(Vec<Entity>);
+
+    #[derive(Component)]
+    #[relationship_target(relationship = InSquads, many, linked_spawn)]
+    struct Members(Vec<Entity>);
+
+    #[test]
+    fn many_to_many_relationship() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let item1 = world.spawn(InContainers(vec![a, b])).id();
+        let item2 = world.spawn(InContainers(vec![a])).id();
+
+        assert_eq!(world.entity(a).get::<Contains>().unwrap().0, [item1, item2]);
+        assert_eq!(world.entity(b).get::<Contains>().unwrap().0, [item1]);
+
+        world.entity_mut(item1).insert(InContainers(vec![b]));
+        assert_eq!(world.entity(a).get::<Contains>().unwrap().0, [item2]);
+        assert_eq!(world.entity(b).get::<Contains>().unwrap().0, [item1]);
+
+        world.entity_mut(item1).remove::<InContainers>();
+        assert!(!world.entity(b).contains::<Contains>());
+    }
+
+    #[test]
+    fn despawning_target_unlinks_sources() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let item = world.spawn(InContainers(vec![a, b])).id();
+
+        world.despawn(a);
+        assert_eq!(world.entity(item).get::<InContainers>().unwrap().0, [b]);
+        assert_eq!(world.entity(b).get::<Contains>().unwrap().0, [item]);
+
+        world.despawn(b);
+        assert!(!world.entity(item).contains::<InContainers>());
+    }
+
+    #[test]
+    fn linked_spawn_despawns_sources() {
+        let mut world = World::new();
+        let alpha = world.spawn_empty().id();
+        let bravo = world.spawn_empty().id();
+        let unit = world.spawn(InSquads(vec![alpha, bravo])).id();
+
+        world.despawn(alpha);
+        assert!(world.get_entity(unit).is_err());
+        assert!(!world.entity(bravo).contains::<Members>());
+    }
+
+    #[test]
+    fn invalid_targets_are_removed() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let missing = world.spawn_empty().id();
+        world.despawn(missing);
+        let item = world.spawn_empty().id();
+        world
+            .entity_mut(item)
+            .insert(InContainers(vec![a, item, missing, a]));
+
+        assert_eq!(world.entity(item).get::<InContainers>().unwrap().0, [a]);
+        assert_eq!(world.entity(a).get::<Contains>().unwrap().0, [item]);
+        assert!(!world.entity(item).contains::<Contains>());
+    }
+
+    #[test]
+    fn traversal_visits_each_entity_once() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn(InContainers(vec![a])).id();
+        world.entity_mut(a).insert(InContainers(vec![b]));
+        let item = world.spawn(InContainers(vec![a, b])).id();
+
+        let (ancestors, descendants) = world
+            .run_system_cached_with(
+                |In((item, b)): In<(Entity, Entity)>,
+                 targets: Query<&InContainers>,
+                 sources: Query<&Contains>| {
+                    (
+                        targets.iter_many_ancestors(item).collect::<Vec<_>>(),
+                        sources.iter_many_descendants(b).collect::<Vec<_>>(),
+                    )
+                },
+                (item, b),
+            )
+            .unwrap();
+        assert_eq!(ancestors, [a, b]);
+        assert_eq!(descendants, [a, item]);
+    }
+}
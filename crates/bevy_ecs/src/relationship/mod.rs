@@ -1,11 +1,13 @@
 //! This module provides functionality to link entities to each other using specialized components called "relationships". See the [`Relationship`] trait for more info.
 
+mod many_to_many;
 mod related_methods;
 mod relationship_query;
 mod relationship_source_collection;
 
 use alloc::format;
 
+pub use many_to_many::*;
 pub use related_methods::*;
 pub use relationship_query::*;
 pub use relationship_source_collection::*;
@@ -35,6 +37,9 @@ use log::warn;
 ///
 /// [`Relationship`] and [`RelationshipTarget`] should always be derived via the [`Component`] trait to ensure the hooks are set up properly.
 ///
+/// A [`Relationship`] has a single target. For many-to-many relationships, where a source can target any number of
+/// entities, see [`ManyRelationship`].
+///
 /// ## Derive
 ///
 /// [`Relationship`] and [`RelationshipTarget`] can only be derived for structs with a single unnamed field, single named field
@@ -59,6 +64,38 @@ use log::warn;
 /// pub struct Children(Vec<Entity>);
 /// ```
 ///
+/// ## Edge data
+///
+/// The additional fields of a [`Relationship`] can hold data about the relationship itself, like the slot
+/// an item is equipped in. To also expose that data when iterating over the [`RelationshipTarget`], use
+/// [`RelationshipEdges`] as its collection, which stores a copy of the [`Relationship`] of each source:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::relationship::RelationshipEdges;
+/// #[derive(Component, Clone)]
+/// #[relationship(relationship_target = Equipment)]
+/// pub struct EquippedBy {
+///     #[relationship]
+///     pub holder: Entity,
+///     pub slot: u8,
+/// }
+///
+/// #[derive(Component)]
+/// #[relationship_target(relationship = EquippedBy)]
+/// pub struct Equipment(RelationshipEdges<EquippedBy>);
+///
+/// let mut world = World::new();
+/// let holder = world.spawn_empty().id();
+/// let sword = world.spawn(EquippedBy { holder, slot: 3 }).id();
+///
+/// let equipment = world.get::<Equipment>(holder).unwrap();
+/// assert_eq!(equipment.0.get(sword).unwrap().slot, 3);
+/// for (item, equipped) in equipment.0.edges() {
+///     assert_eq!((item, equipped.slot), (sword, 3));
+/// }
+/// ```
+///
 /// When deriving [`RelationshipTarget`] you can specify the `#[relationship_target(linked_spawn)]` attribute to
 /// automatically despawn entities stored in an entity's [`RelationshipTarget`] when that entity is despawned:
 ///
@@ -114,14 +151,25 @@ pub trait Relationship: Component + Sized {
             world.commands().entity(entity).remove::<Self>();
             return;
         }
-        if let Ok(mut target_entity_mut) = world.get_entity_mut(target_entity) {
-            if let Some(mut relationship_target) =
+        if let Ok([source_entity_mut, mut target_entity_mut]) =
+            world.get_entity_mut([entity, target_entity])
+        {
+            let relationship = source_entity_mut.get::<Self>().unwrap();
+            let new_target = if let Some(mut relationship_target) =
                 target_entity_mut.get_mut::<Self::RelationshipTarget>()
             {
-                relationship_target.collection_mut_risky().add(entity);
+                relationship_target
+                    .collection_mut_risky()
+                    .add_source(entity, relationship);
+                None
             } else {
                 let mut target = <Self::RelationshipTarget as RelationshipTarget>::with_capacity(1);
-                target.collection_mut_risky().add(entity);
+                target
+                    .collection_mut_risky()
+                    .add_source(entity, relationship);
+                Some(target)
+            };
+            if let Some(target) = new_target {
                 world.commands().entity(target_entity).insert(target);
             }
         } else {
@@ -185,10 +233,10 @@ pub trait RelationshipTarget: Component<Mutability = Mutable> + Sized {
     /// The collection type that stores the "source" entities for this [`RelationshipTarget`] component.
     ///
     /// Check the list of types which implement [`RelationshipSourceCollection`] for the data structures that can be used inside of your component.
-    /// If you need a new collection type, you can implement the [`RelationshipSourceCollection`] trait
-    /// for a type you own which wraps the collection you want to use (to avoid the orphan rule),
+    /// If you need a new collection type, you can implement the [`RelationshipSourceCollection`] and
+    /// [`EntitySourceCollection`] traits for a type you own which wraps the collection you want to use (to avoid the orphan rule),
     /// or open an issue on the Bevy repository to request first-party support for your collection type.
-    type Collection: RelationshipSourceCollection;
+    type Collection: RelationshipSourceCollectionOf<Self::Relationship>;
 
     /// Returns a reference to the stored [`RelationshipTarget::Collection`].
     fn collection(&self) -> &Self::Collection;
@@ -309,7 +357,7 @@ pub fn clone_relationship_target<T: RelationshipTarget>(
         if context.linked_cloning() && T::LINKED_SPAWN {
             let collection = cloned.collection_mut_risky();
             for entity in component.iter() {
-                collection.add(entity);
+                collection.add_from(component.collection(), entity);
                 context.queue_entity_clone(entity);
             }
         }
@@ -1,12 +1,15 @@
 use crate::{
     bundle::Bundle,
+    component::Component,
     entity::Entity,
-    relationship::{Relationship, RelationshipTarget},
+    relationship::{
+        add_targets, remove_targets, EntitySourceCollection, ManyRelationship,
+        ManyRelationshipTarget, Relationship, RelationshipSourceCollection, RelationshipTarget,
+    },
     system::{Commands, EntityCommands},
     world::{EntityWorldMut, World},
 };
 use alloc::vec::Vec;
-use core::marker::PhantomData;
 
 impl<'w> EntityWorldMut<'w> {
     /// Spawns entities related to this entity (with the `R` relationship) by taking a function that operates on a [`RelatedSpawner`].
@@ -56,6 +59,61 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Spawns entities related to this entity (with the `R` [`ManyRelationship`]) by taking a function that operates
+    /// on a [`RelatedSpawner`].
+    pub fn with_many_related<R: ManyRelationship>(
+        &mut self,
+        func: impl FnOnce(&mut RelatedSpawner<R>),
+    ) -> &mut Self {
+        let target = self.id();
+        self.world_scope(|world| {
+            func(&mut RelatedSpawner::new_many(world, target));
+        });
+        self
+    }
+
+    /// Relates the given entities to this entity with the [`ManyRelationship`] `R`, in addition to the
+    /// entities they already relate to.
+    pub fn add_many_related<R: ManyRelationship>(&mut self, related: &[Entity]) -> &mut Self {
+        let id = self.id();
+        self.world_scope(|world| {
+            for related in related {
+                add_targets::<R>(&mut world.entity_mut(*related), &[id]);
+            }
+        });
+        self
+    }
+
+    /// Stops relating the given entities to this entity with the [`ManyRelationship`] `R`, while keeping
+    /// the other entities they relate to.
+    pub fn remove_many_related<R: ManyRelationship>(&mut self, related: &[Entity]) -> &mut Self {
+        let id = self.id();
+        self.world_scope(|world| {
+            for related in related {
+                if let Ok(mut related) = world.get_entity_mut(*related) {
+                    remove_targets::<R>(&mut related, &[id]);
+                }
+            }
+        });
+        self
+    }
+
+    /// Despawns entities that relate to this one via the given [`ManyRelationshipTarget`], even if they
+    /// relate to other entities too.
+    /// This entity will not be despawned.
+    pub fn despawn_many_related<S: ManyRelationshipTarget>(&mut self) -> &mut Self {
+        if let Some(sources) = self.take::<S>() {
+            self.world_scope(|world| {
+                for entity in sources.iter() {
+                    if let Ok(entity_mut) = world.get_entity_mut(entity) {
+                        entity_mut.despawn();
+                    }
+                }
+            });
+        }
+        self
+    }
+
     /// Inserts a component or bundle of components into the entity and all related entities,
     /// traversing the relationship tracked in `S` in a breadth-first manner.
     ///
@@ -148,6 +206,50 @@ impl<'a> EntityCommands<'a> {
         self
     }
 
+    /// Spawns entities related to this entity (with the `R` [`ManyRelationship`]) by taking a function that operates
+    /// on a [`RelatedSpawnerCommands`].
+    pub fn with_many_related<R: ManyRelationship>(
+        &mut self,
+        func: impl FnOnce(&mut RelatedSpawnerCommands<R>),
+    ) -> &mut Self {
+        let id = self.id();
+        func(&mut RelatedSpawnerCommands::new_many(self.commands(), id));
+        self
+    }
+
+    /// Relates the given entities to this entity with the [`ManyRelationship`] `R`, in addition to the
+    /// entities they already relate to.
+    pub fn add_many_related<R: ManyRelationship>(&mut self, related: &[Entity]) -> &mut Self {
+        let id = self.id();
+        let related = related.to_vec();
+        self.commands().queue(move |world: &mut World| {
+            world.entity_mut(id).add_many_related::<R>(&related);
+        });
+        self
+    }
+
+    /// Stops relating the given entities to this entity with the [`ManyRelationship`] `R`, while keeping
+    /// the other entities they relate to.
+    pub fn remove_many_related<R: ManyRelationship>(&mut self, related: &[Entity]) -> &mut Self {
+        let id = self.id();
+        let related = related.to_vec();
+        self.commands().queue(move |world: &mut World| {
+            world.entity_mut(id).remove_many_related::<R>(&related);
+        });
+        self
+    }
+
+    /// Despawns entities that relate to this one via the given [`ManyRelationshipTarget`], even if they
+    /// relate to other entities too.
+    /// This entity will not be despawned.
+    pub fn despawn_many_related<S: ManyRelationshipTarget>(&mut self) -> &mut Self {
+        let id = self.id();
+        self.commands.queue(move |world: &mut World| {
+            world.entity_mut(id).despawn_many_related::<S>();
+        });
+        self
+    }
+
     /// Inserts a component or bundle of components into the entity and all related entities,
     /// traversing the relationship tracked in `S` in a breadth-first manner.
     ///
@@ -182,12 +284,12 @@ impl<'a> EntityCommands<'a> {
     }
 }
 
-/// Directly spawns related "source" entities with the given [`Relationship`], targeting
-/// a specific entity.
-pub struct RelatedSpawner<'w, R: Relationship> {
+/// Directly spawns related "source" entities with the given [`Relationship`] or [`ManyRelationship`],
+/// targeting a specific entity.
+pub struct RelatedSpawner<'w, R: Component> {
     target: Entity,
     world: &'w mut World,
-    _marker: PhantomData<R>,
+    relationship: fn(Entity) -> R,
 }
 
 impl<'w, R: Relationship> RelatedSpawner<'w, R> {
@@ -196,34 +298,48 @@ impl<'w, R: Relationship> RelatedSpawner<'w, R> {
         Self {
             world,
             target,
-            _marker: PhantomData,
+            relationship: <R as Relationship>::from,
+        }
+    }
+}
+
+impl<'w, R: ManyRelationship> RelatedSpawner<'w, R> {
+    /// Creates a new instance that will spawn entities with the [`ManyRelationship`] `R`, targeting only the
+    /// `target` entity.
+    pub fn new_many(world: &'w mut World, target: Entity) -> Self {
+        Self {
+            world,
+            target,
+            relationship: single_target::<R>,
         }
     }
+}
 
+impl<'w, R: Component> RelatedSpawner<'w, R> {
     /// Spawns an entity with the given `bundle` and an `R` relationship targeting the `target`
     /// entity this spawner was initialized with.
     pub fn spawn(&mut self, bundle: impl Bundle) -> EntityWorldMut<'_> {
-        self.world.spawn((R::from(self.target), bundle))
+        self.world.spawn(((self.relationship)(self.target), bundle))
     }
 
     /// Spawns an entity with an `R` relationship targeting the `target`
     /// entity this spawner was initialized with.
     pub fn spawn_empty(&mut self) -> EntityWorldMut<'_> {
-        self.world.spawn(R::from(self.target))
+        self.world.spawn((self.relationship)(self.target))
     }
 
-    /// Returns the "target entity" used when spawning entities with an `R` [`Relationship`].
+    /// Returns the "target entity" used when spawning entities with an `R` relationship.
     pub fn target_entity(&self) -> Entity {
         self.target
     }
 }
 
-/// Uses commands to spawn related "source" entities with the given [`Relationship`], targeting
-/// a specific entity.
-pub struct RelatedSpawnerCommands<'w, R: Relationship> {
+/// Uses commands to spawn related "source" entities with the given [`Relationship`] or
+/// [`ManyRelationship`], targeting a specific entity.
+pub struct RelatedSpawnerCommands<'w, R: Component> {
     target: Entity,
     commands: Commands<'w, 'w>,
-    _marker: PhantomData<R>,
+    relationship: fn(Entity) -> R,
 }
 
 impl<'w, R: Relationship> RelatedSpawnerCommands<'w, R> {
@@ -232,23 +348,38 @@ impl<'w, R: Relationship> RelatedSpawnerCommands<'w, R> {
         Self {
             commands,
             target,
-            _marker: PhantomData,
+            relationship: <R as Relationship>::from,
+        }
+    }
+}
+
+impl<'w, R: ManyRelationship> RelatedSpawnerCommands<'w, R> {
+    /// Creates a new instance that will spawn entities with the [`ManyRelationship`] `R`, targeting only the
+    /// `target` entity.
+    pub fn new_many(commands: Commands<'w, 'w>, target: Entity) -> Self {
+        Self {
+            commands,
+            target,
+            relationship: single_target::<R>,
         }
     }
+}
 
+impl<'w, R: Component> RelatedSpawnerCommands<'w, R> {
     /// Spawns an entity with the given `bundle` and an `R` relationship targeting the `target`
     /// entity this spawner was initialized with.
     pub fn spawn(&mut self, bundle: impl Bundle) -> EntityCommands<'_> {
-        self.commands.spawn((R::from(self.target), bundle))
+        self.commands
+            .spawn(((self.relationship)(self.target), bundle))
     }
 
     /// Spawns an entity with an `R` relationship targeting the `target`
     /// entity this spawner was initialized with.
     pub fn spawn_empty(&mut self) -> EntityCommands<'_> {
-        self.commands.spawn(R::from(self.target))
+        self.commands.spawn((self.relationship)(self.target))
     }
 
-    /// Returns the "target entity" used when spawning entities with an `R` [`Relationship`].
+    /// Returns the "target entity" used when spawning entities with an `R` relationship.
     pub fn target_entity(&self) -> Entity {
         self.target
     }
@@ -264,6 +395,13 @@ impl<'w, R: Relationship> RelatedSpawnerCommands<'w, R> {
     }
 }
 
+/// Creates an `R` relationship with the given `target` as its only target.
+fn single_target<R: ManyRelationship>(target: Entity) -> R {
+    let mut targets = <R::Collection as RelationshipSourceCollection>::with_capacity(1);
+    targets.add(target);
+    R::from_targets(targets)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -310,4 +448,45 @@ mod tests {
             assert!(!world.entity(entity).contains::<TestComponent>());
         }
     }
+
+    #[test]
+    fn many_related_methods() {
+        #[derive(Component)]
+        #[relationship(relationship_target = SquadMembers, many)]
+        struct InSquads(Vec<Entity>);
+
+        #[derive(Component)]
+        #[relationship_target(relationship = InSquads, many)]
+        struct SquadMembers(Vec<Entity>);
+
+        let mut world = World::new();
+        let bravo = world.spawn_empty().id();
+        let mut unit = Entity::PLACEHOLDER;
+        let alpha = world
+            .spawn_empty()
+            .with_many_related::<InSquads>(|spawner| {
+                unit = spawner.spawn_empty().id();
+            })
+            .id();
+
+        world
+            .entity_mut(bravo)
+            .add_many_related::<InSquads>(&[unit]);
+        assert_eq!(
+            world.entity(unit).get::<InSquads>().unwrap().0,
+            [alpha, bravo]
+        );
+        assert_eq!(world.entity(bravo).get::<SquadMembers>().unwrap().0, [unit]);
+
+        world
+            .entity_mut(alpha)
+            .remove_many_related::<InSquads>(&[unit]);
+        assert_eq!(world.entity(unit).get::<InSquads>().unwrap().0, [bravo]);
+        assert!(!world.entity(alpha).contains::<SquadMembers>());
+
+        world
+            .entity_mut(bravo)
+            .despawn_many_related::<SquadMembers>();
+        assert!(world.get_entity(unit).is_err());
+    }
 }
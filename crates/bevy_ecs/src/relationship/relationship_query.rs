@@ -1,10 +1,12 @@
 use crate::{
-    entity::Entity,
+    component::Component,
+    entity::{hash_set::EntityHashSet, Entity},
     query::{QueryData, QueryFilter},
-    relationship::{Relationship, RelationshipTarget},
+    relationship::{ManyRelationship, ManyRelationshipTarget, Relationship, RelationshipTarget},
     system::Query,
 };
 use alloc::collections::VecDeque;
+use core::marker::PhantomData;
 use smallvec::SmallVec;
 
 use super::SourceIter;
@@ -141,48 +143,193 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     {
         AncestorIter::new(self, entity)
     }
+
+    /// If the given `entity` contains the `R` [`ManyRelationship`] component, returns the
+    /// target entities of that relationship.
+    pub fn many_related<R: ManyRelationship>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w> = &'w R>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(ManyRelationship::iter)
+    }
+
+    /// If the given `entity` contains the `S` [`ManyRelationshipTarget`] component, returns the
+    /// source entities stored on that component.
+    pub fn many_relationship_sources<S: ManyRelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w> = &'w S>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(ManyRelationshipTarget::iter)
+    }
+
+    /// Iterates all entities that relate to the given `entity`, directly or indirectly, as defined by the
+    /// `S` [`ManyRelationshipTarget`] and their recursive [`ManyRelationshipTarget`], in breadth-first order.
+    ///
+    /// Each entity is returned at most once, so this is safe to use on relationship graphs that contain loops.
+    pub fn iter_many_descendants<S: ManyRelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> DescendantIter<'w, 's, D, F, S, ManyRelatedSources>
+    where
+        D::ReadOnly: QueryData<Item<'w> = &'w S>,
+    {
+        DescendantIter::new(self, entity)
+    }
+
+    /// Iterates all entities that the given `entity` relates to, directly or indirectly, as defined by the
+    /// `R` [`ManyRelationship`], in breadth-first order.
+    ///
+    /// Each entity is returned at most once, so this is safe to use on relationship graphs that contain loops.
+    pub fn iter_many_ancestors<R: ManyRelationship>(
+        &'w self,
+        entity: Entity,
+    ) -> AncestorIter<'w, 's, D, F, R, ManyRelatedTargets>
+    where
+        D::ReadOnly: QueryData<Item<'w> = &'w R>,
+    {
+        AncestorIter::new(self, entity)
+    }
+}
+
+/// A relationship component listing the entities related through it, which [`DescendantIter`] and
+/// [`AncestorIter`] walk through.
+///
+/// This is implemented for both sides of [`Relationship`] and [`ManyRelationship`]. The `Marker` only
+/// keeps these implementations apart, and is inferred.
+pub trait RelatedEntities<Marker>: Component {
+    /// Whether traversals skip the entities they already visited, for relationship graphs that can contain loops.
+    const VISIT_ONCE: bool;
+
+    /// Iterates the entities related through this component.
+    fn related_entities(&self) -> impl Iterator<Item = Entity> + '_;
+}
+
+/// Marks the [`RelatedEntities`] implementation of [`RelationshipTarget`].
+#[doc(hidden)]
+pub struct RelatedSources;
+
+/// Marks the [`RelatedEntities`] implementation of [`Relationship`].
+#[doc(hidden)]
+pub struct RelatedTarget;
+
+/// Marks the [`RelatedEntities`] implementation of [`ManyRelationshipTarget`].
+#[doc(hidden)]
+pub struct ManyRelatedSources;
+
+/// Marks the [`RelatedEntities`] implementation of [`ManyRelationship`].
+#[doc(hidden)]
+pub struct ManyRelatedTargets;
+
+impl<S: RelationshipTarget> RelatedEntities<RelatedSources> for S {
+    const VISIT_ONCE: bool = false;
+
+    fn related_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        RelationshipTarget::iter(self)
+    }
+}
+
+impl<R: Relationship> RelatedEntities<RelatedTarget> for R {
+    const VISIT_ONCE: bool = false;
+
+    fn related_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        core::iter::once(self.get())
+    }
+}
+
+impl<S: ManyRelationshipTarget> RelatedEntities<ManyRelatedSources> for S {
+    const VISIT_ONCE: bool = true;
+
+    fn related_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        ManyRelationshipTarget::iter(self)
+    }
+}
+
+impl<R: ManyRelationship> RelatedEntities<ManyRelatedTargets> for R {
+    const VISIT_ONCE: bool = true;
+
+    fn related_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        ManyRelationship::iter(self)
+    }
+}
+
+/// The queue of entities left to visit by a breadth-first traversal.
+struct BreadthFirstQueue {
+    vecdeque: VecDeque<Entity>,
+    visited: Option<EntityHashSet>,
+}
+
+impl BreadthFirstQueue {
+    fn new(entity: Entity, visit_once: bool) -> Self {
+        Self {
+            vecdeque: VecDeque::new(),
+            visited: visit_once.then(|| EntityHashSet::from([entity])),
+        }
+    }
+
+    fn extend(&mut self, entities: impl Iterator<Item = Entity>) {
+        match &mut self.visited {
+            Some(visited) => self
+                .vecdeque
+                .extend(entities.filter(|entity| visited.insert(*entity))),
+            None => self.vecdeque.extend(entities),
+        }
+    }
 }
 
 /// An [`Iterator`] of [`Entity`]s over the descendants of an [`Entity`].
 ///
-/// Traverses the hierarchy breadth-first.
-pub struct DescendantIter<'w, 's, D: QueryData, F: QueryFilter, S: RelationshipTarget>
+/// Traverses the hierarchy breadth-first. The entities of many-to-many relationships are visited once.
+pub struct DescendantIter<'w, 's, D: QueryData, F: QueryFilter, S, M = RelatedSources>
 where
+    S: RelatedEntities<M>,
     D::ReadOnly: QueryData<Item<'w> = &'w S>,
 {
     children_query: &'w Query<'w, 's, D, F>,
-    vecdeque: VecDeque<Entity>,
+    queue: BreadthFirstQueue,
+    _marker: PhantomData<fn() -> M>,
 }
 
-impl<'w, 's, D: QueryData, F: QueryFilter, S: RelationshipTarget> DescendantIter<'w, 's, D, F, S>
+impl<'w, 's, D: QueryData, F: QueryFilter, S, M> DescendantIter<'w, 's, D, F, S, M>
 where
+    S: RelatedEntities<M>,
     D::ReadOnly: QueryData<Item<'w> = &'w S>,
 {
     /// Returns a new [`DescendantIter`].
     pub fn new(children_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        let mut queue = BreadthFirstQueue::new(entity, S::VISIT_ONCE);
+        if let Ok(children) = children_query.get(entity) {
+            queue.extend(children.related_entities());
+        }
         DescendantIter {
             children_query,
-            vecdeque: children_query
-                .get(entity)
-                .into_iter()
-                .flat_map(RelationshipTarget::iter)
-                .collect(),
+            queue,
+            _marker: PhantomData,
         }
     }
 }
 
-impl<'w, 's, D: QueryData, F: QueryFilter, S: RelationshipTarget> Iterator
-    for DescendantIter<'w, 's, D, F, S>
+impl<'w, 's, D: QueryData, F: QueryFilter, S, M> Iterator for DescendantIter<'w, 's, D, F, S, M>
 where
+    S: RelatedEntities<M>,
     D::ReadOnly: QueryData<Item<'w> = &'w S>,
 {
     type Item = Entity;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let entity = self.vecdeque.pop_front()?;
+        let entity = self.queue.vecdeque.pop_front()?;
 
         if let Ok(children) = self.children_query.get(entity) {
-            self.vecdeque.extend(children.iter());
+            self.queue.extend(children.related_entities());
         }
 
         Some(entity)
@@ -237,36 +384,51 @@ where
 }
 
 /// An [`Iterator`] of [`Entity`]s over the ancestors of an [`Entity`].
-pub struct AncestorIter<'w, 's, D: QueryData, F: QueryFilter, R: Relationship>
+///
+/// The ancestors of many-to-many relationships are traversed breadth-first, visiting each entity once.
+pub struct AncestorIter<'w, 's, D: QueryData, F: QueryFilter, R, M = RelatedTarget>
 where
+    R: RelatedEntities<M>,
     D::ReadOnly: QueryData<Item<'w> = &'w R>,
 {
     parent_query: &'w Query<'w, 's, D, F>,
-    next: Option<Entity>,
+    queue: BreadthFirstQueue,
+    _marker: PhantomData<fn() -> M>,
 }
 
-impl<'w, 's, D: QueryData, F: QueryFilter, R: Relationship> AncestorIter<'w, 's, D, F, R>
+impl<'w, 's, D: QueryData, F: QueryFilter, R, M> AncestorIter<'w, 's, D, F, R, M>
 where
+    R: RelatedEntities<M>,
     D::ReadOnly: QueryData<Item<'w> = &'w R>,
 {
     /// Returns a new [`AncestorIter`].
     pub fn new(parent_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        let mut queue = BreadthFirstQueue::new(entity, R::VISIT_ONCE);
+        if let Ok(parents) = parent_query.get(entity) {
+            queue.extend(parents.related_entities());
+        }
         AncestorIter {
             parent_query,
-            next: Some(entity),
+            queue,
+            _marker: PhantomData,
         }
     }
 }
 
-impl<'w, 's, D: QueryData, F: QueryFilter, R: Relationship> Iterator
-    for AncestorIter<'w, 's, D, F, R>
+impl<'w, 's, D: QueryData, F: QueryFilter, R, M> Iterator for AncestorIter<'w, 's, D, F, R, M>
 where
+    R: RelatedEntities<M>,
     D::ReadOnly: QueryData<Item<'w> = &'w R>,
 {
     type Item = Entity;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next = self.parent_query.get(self.next?).ok().map(R::get);
-        self.next
+        let entity = self.queue.vecdeque.pop_front()?;
+
+        if let Ok(parents) = self.parent_query.get(entity) {
+            self.queue.extend(parents.related_entities());
+        }
+
+        Some(entity)
     }
 }
@@ -1,4 +1,8 @@
-use crate::entity::{hash_set::EntityHashSet, Entity};
+use crate::{
+    component::Component,
+    entity::{hash_set::EntityHashSet, Entity, VisitEntities, VisitEntitiesMut},
+    relationship::Relationship,
+};
 use alloc::vec::Vec;
 use smallvec::SmallVec;
 
@@ -19,9 +23,6 @@ pub trait RelationshipSourceCollection {
     /// Returns an instance with the given pre-allocated entity `capacity`.
     fn with_capacity(capacity: usize) -> Self;
 
-    /// Adds the given `entity` to the collection.
-    fn add(&mut self, entity: Entity);
-
     /// Removes the given `entity` from the collection.
     fn remove(&mut self, entity: Entity);
 
@@ -38,6 +39,36 @@ pub trait RelationshipSourceCollection {
     }
 }
 
+/// A [`RelationshipSourceCollection`] that only stores entities, so that any entity can be added to it.
+pub trait EntitySourceCollection: RelationshipSourceCollection {
+    /// Adds the given `entity` to the collection.
+    fn add(&mut self, entity: Entity);
+}
+
+/// A [`RelationshipSourceCollection`] that the sources of the `R` [`Relationship`] can be added to.
+///
+/// This is implemented for every [`EntitySourceCollection`], and by [`RelationshipEdges<R>`], which also stores
+/// the relationship of each source.
+pub trait RelationshipSourceCollectionOf<R: Relationship>: RelationshipSourceCollection {
+    /// Adds the given `entity`, which is the source of the given `relationship`, to the collection.
+    fn add_source(&mut self, entity: Entity, relationship: &R);
+
+    /// Adds the given `entity` from the `other` collection, along with any data stored for it there.
+    fn add_from(&mut self, other: &Self, entity: Entity);
+}
+
+impl<R: Relationship, C: EntitySourceCollection> RelationshipSourceCollectionOf<R> for C {
+    #[inline]
+    fn add_source(&mut self, entity: Entity, _relationship: &R) {
+        self.add(entity);
+    }
+
+    #[inline]
+    fn add_from(&mut self, _other: &Self, entity: Entity) {
+        self.add(entity);
+    }
+}
+
 impl RelationshipSourceCollection for Vec<Entity> {
     type SourceIter<'a> = core::iter::Copied<core::slice::Iter<'a, Entity>>;
 
@@ -45,10 +76,6 @@ impl RelationshipSourceCollection for Vec<Entity> {
         Vec::with_capacity(capacity)
     }
 
-    fn add(&mut self, entity: Entity) {
-        Vec::push(self, entity);
-    }
-
     fn remove(&mut self, entity: Entity) {
         if let Some(index) = <[Entity]>::iter(self).position(|e| *e == entity) {
             Vec::remove(self, index);
@@ -64,6 +91,12 @@ impl RelationshipSourceCollection for Vec<Entity> {
     }
 }
 
+impl EntitySourceCollection for Vec<Entity> {
+    fn add(&mut self, entity: Entity) {
+        Vec::push(self, entity);
+    }
+}
+
 impl RelationshipSourceCollection for EntityHashSet {
     type SourceIter<'a> = core::iter::Copied<crate::entity::hash_set::Iter<'a>>;
 
@@ -71,10 +104,6 @@ impl RelationshipSourceCollection for EntityHashSet {
         EntityHashSet::with_capacity(capacity)
     }
 
-    fn add(&mut self, entity: Entity) {
-        self.insert(entity);
-    }
-
     fn remove(&mut self, entity: Entity) {
         // We need to call the remove method on the underlying hash set,
         // which takes its argument by reference
@@ -90,6 +119,12 @@ impl RelationshipSourceCollection for EntityHashSet {
     }
 }
 
+impl EntitySourceCollection for EntityHashSet {
+    fn add(&mut self, entity: Entity) {
+        self.insert(entity);
+    }
+}
+
 impl<const N: usize> RelationshipSourceCollection for SmallVec<[Entity; N]> {
     type SourceIter<'a> = core::iter::Copied<core::slice::Iter<'a, Entity>>;
 
@@ -97,10 +132,6 @@ impl<const N: usize> RelationshipSourceCollection for SmallVec<[Entity; N]> {
         SmallVec::with_capacity(capacity)
     }
 
-    fn add(&mut self, entity: Entity) {
-        SmallVec::push(self, entity);
-    }
-
     fn remove(&mut self, entity: Entity) {
         if let Some(index) = <[Entity]>::iter(self).position(|e| *e == entity) {
             SmallVec::remove(self, index);
@@ -116,6 +147,12 @@ impl<const N: usize> RelationshipSourceCollection for SmallVec<[Entity; N]> {
     }
 }
 
+impl<const N: usize> EntitySourceCollection for SmallVec<[Entity; N]> {
+    fn add(&mut self, entity: Entity) {
+        SmallVec::push(self, entity);
+    }
+}
+
 impl RelationshipSourceCollection for Entity {
     type SourceIter<'a> = core::iter::Once<Entity>;
 
@@ -123,10 +160,6 @@ impl RelationshipSourceCollection for Entity {
         Entity::PLACEHOLDER
     }
 
-    fn add(&mut self, entity: Entity) {
-        *self = entity;
-    }
-
     fn remove(&mut self, entity: Entity) {
         if *self == entity {
             *self = Entity::PLACEHOLDER;
@@ -145,6 +178,103 @@ impl RelationshipSourceCollection for Entity {
     }
 }
 
+impl EntitySourceCollection for Entity {
+    fn add(&mut self, entity: Entity) {
+        *self = entity;
+    }
+}
+
+/// A [`RelationshipSourceCollection`] storing a copy of the `R` [`Relationship`] of each source entity, so that
+/// the data carried by a relationship can be read from its [`RelationshipTarget`](crate::relationship::RelationshipTarget).
+///
+/// The copies are kept up to date by the hooks of `R`, which can only be changed by inserting it again since
+/// [`Relationship`] components are immutable.
+///
+/// It is only filled from the hooks of `R`, so it isn't an [`EntitySourceCollection`] and can't be used as the
+/// collection of a [`ManyRelationshipTarget`](crate::relationship::ManyRelationshipTarget).
+#[derive(Debug, Clone)]
+pub struct RelationshipEdges<R> {
+    edges: Vec<(Entity, R)>,
+}
+
+impl<R> Default for RelationshipEdges<R> {
+    fn default() -> Self {
+        Self { edges: Vec::new() }
+    }
+}
+
+impl<R> RelationshipEdges<R> {
+    /// Iterates the source entities in the collection, along with their relationship.
+    pub fn edges(&self) -> impl DoubleEndedIterator<Item = (Entity, &R)> + ExactSizeIterator {
+        self.edges
+            .iter()
+            .map(|(entity, relationship)| (*entity, relationship))
+    }
+
+    /// Returns the relationship of the given source `entity`, if it is in the collection.
+    pub fn get(&self, entity: Entity) -> Option<&R> {
+        self.edges
+            .iter()
+            .find(|(source, _)| *source == entity)
+            .map(|(_, relationship)| relationship)
+    }
+}
+
+impl<R: 'static> RelationshipSourceCollection for RelationshipEdges<R> {
+    type SourceIter<'a> =
+        core::iter::Map<core::slice::Iter<'a, (Entity, R)>, fn(&(Entity, R)) -> Entity>;
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            edges: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(index) = self.edges.iter().position(|(source, _)| *source == entity) {
+            self.edges.remove(index);
+        }
+    }
+
+    fn iter(&self) -> Self::SourceIter<'_> {
+        self.edges.iter().map(|(entity, _)| *entity)
+    }
+
+    fn len(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+impl<R: Relationship + Clone> RelationshipSourceCollectionOf<R> for RelationshipEdges<R> {
+    fn add_source(&mut self, entity: Entity, relationship: &R) {
+        self.edges.push((entity, relationship.clone()));
+    }
+
+    fn add_from(&mut self, other: &Self, entity: Entity) {
+        if let Some(relationship) = other.get(entity) {
+            self.edges.push((entity, relationship.clone()));
+        }
+    }
+}
+
+impl<R: Component> VisitEntities for RelationshipEdges<R> {
+    fn visit_entities<F: FnMut(Entity)>(&self, mut f: F) {
+        for (entity, relationship) in &self.edges {
+            f(*entity);
+            R::visit_entities(relationship, &mut f);
+        }
+    }
+}
+
+impl<R: Component> VisitEntitiesMut for RelationshipEdges<R> {
+    fn visit_entities_mut<F: FnMut(&mut Entity)>(&mut self, mut f: F) {
+        for (entity, relationship) in &mut self.edges {
+            f(entity);
+            R::visit_entities_mut(relationship, &mut f);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,4 +376,64 @@ mod tests {
         assert!(world.get::<Below>(b).is_none());
         assert_eq!(a, world.get::<Below>(c).unwrap().0);
     }
+
+    #[test]
+    fn relationship_edges() {
+        #[derive(Component, Clone)]
+        #[relationship(relationship_target = Linked)]
+        struct LinkedTo {
+            #[relationship]
+            target: Entity,
+            weight: u32,
+        }
+
+        #[derive(Component)]
+        #[relationship_target(relationship = LinkedTo, linked_spawn)]
+        struct Linked(RelationshipEdges<LinkedTo>);
+
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world
+            .spawn(LinkedTo {
+                target: a,
+                weight: 2,
+            })
+            .id();
+        let c = world
+            .spawn(LinkedTo {
+                target: a,
+                weight: 5,
+            })
+            .id();
+
+        let weights = |world: &World| {
+            world
+                .get::<Linked>(a)
+                .unwrap()
+                .0
+                .edges()
+                .map(|(source, link)| (source, link.weight))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(weights(&world), [(b, 2), (c, 5)]);
+
+        world.entity_mut(b).insert(LinkedTo {
+            target: a,
+            weight: 3,
+        });
+        assert_eq!(weights(&world), [(c, 5), (b, 3)]);
+        assert_eq!(world.get::<Linked>(a).unwrap().0.get(b).unwrap().weight, 3);
+
+        world.entity_mut(c).remove::<LinkedTo>();
+        assert_eq!(weights(&world), [(b, 3)]);
+
+        let clone = world.entity_mut(a).clone_and_spawn_with(|builder| {
+            builder.linked_cloning(true);
+        });
+        let cloned = world.get::<Linked>(clone).unwrap();
+        let (source, link) = cloned.0.edges().next().unwrap();
+        assert_ne!(source, b);
+        assert_eq!((link.target, link.weight), (clone, 3));
+        assert_eq!(world.get::<LinkedTo>(source).unwrap().target, clone);
+    }
 }
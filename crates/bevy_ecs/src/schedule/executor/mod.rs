@@ -19,7 +19,7 @@ use crate::{
     prelude::{IntoSystemSet, SystemSet},
     query::Access,
     result::{Error, Result, SystemErrorContext},
-    schedule::{BoxedCondition, InternedSystemSet, NodeId, SystemTypeSet},
+    schedule::{timing::TimingRecorder, BoxedCondition, InternedSystemSet, NodeId, SystemTypeSet},
     system::{ScheduleSystem, System, SystemIn},
     world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
 };
@@ -34,6 +34,7 @@ pub(super) trait SystemExecutor: Send + Sync {
         world: &mut World,
         skip_systems: Option<&FixedBitSet>,
         error_handler: fn(Error, SystemErrorContext),
+        recorder: Option<&TimingRecorder>,
     );
     fn set_apply_final_deferred(&mut self, value: bool);
 }
@@ -58,6 +59,17 @@ pub enum ExecutorKind {
     #[cfg(feature = "std")]
     #[cfg_attr(all(not(target_arch = "wasm32"), feature = "multi_threaded"), default)]
     MultiThreaded,
+    /// Like [`MultiThreaded`](ExecutorKind::MultiThreaded) but systems with conflicting access
+    /// always run in the order of the schedule, even when their order is ambiguous.
+    ///
+    /// Non-conflicting systems still run in parallel, but events are written and commands are
+    /// applied in the same order as with [`SingleThreaded`](ExecutorKind::SingleThreaded).
+    /// Systems with deferred parameters such as [`Commands`](crate::system::Commands) each get
+    /// their own share of entity ids, so spawned entities get the same ids on every run, though
+    /// not necessarily the ones they would get on a single thread. This is useful for lockstep
+    /// multiplayer and replays.
+    #[cfg(feature = "std")]
+    MultiThreadedDeterministic,
 }
 
 /// Holds systems and conditions of a [`Schedule`](super::Schedule) sorted in topological order
@@ -324,10 +336,11 @@ mod tests {
     #[derive(Resource)]
     struct R2;
 
-    const EXECUTORS: [ExecutorKind; 3] = [
+    const EXECUTORS: [ExecutorKind; 4] = [
         ExecutorKind::Simple,
         ExecutorKind::SingleThreaded,
         ExecutorKind::MultiThreaded,
+        ExecutorKind::MultiThreadedDeterministic,
     ];
 
     #[test]
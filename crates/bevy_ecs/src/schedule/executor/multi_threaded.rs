@@ -1,9 +1,9 @@
-use alloc::{boxed::Box, vec::Vec};
-use bevy_platform_support::sync::Arc;
+use alloc::{boxed::Box, vec, vec::Vec};
+use bevy_platform_support::{sync::Arc, time::Instant};
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
 use bevy_utils::{default, syncunsafecell::SyncUnsafeCell};
 use concurrent_queue::ConcurrentQueue;
-use core::{any::Any, panic::AssertUnwindSafe};
+use core::{any::Any, panic::AssertUnwindSafe, time::Duration};
 use fixedbitset::FixedBitSet;
 use std::{
     eprintln,
@@ -15,10 +15,16 @@ use tracing::{info_span, Span};
 
 use crate::{
     archetype::ArchetypeComponentId,
+    component::ComponentId,
+    entity::with_reservation_share,
     prelude::Resource,
     query::Access,
     result::{Error, Result, SystemErrorContext},
-    schedule::{is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
+    schedule::{
+        is_apply_deferred,
+        timing::{time_system, TimingRecorder},
+        BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
+    },
     system::ScheduleSystem,
     world::{unsafe_world_cell::UnsafeWorldCell, World},
 };
@@ -31,6 +37,7 @@ struct Environment<'env, 'sys> {
     systems: &'sys [SyncUnsafeCell<ScheduleSystem>],
     conditions: SyncUnsafeCell<Conditions<'sys>>,
     world_cell: UnsafeWorldCell<'env>,
+    recorder: Option<&'env TimingRecorder>,
 }
 
 struct Conditions<'a> {
@@ -45,6 +52,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
         executor: &'env MultiThreadedExecutor,
         schedule: &'sys mut SystemSchedule,
         world: &'env mut World,
+        recorder: Option<&'env TimingRecorder>,
     ) -> Self {
         Environment {
             executor,
@@ -56,6 +64,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
                 systems_in_sets_with_conditions: &schedule.systems_in_sets_with_conditions,
             }),
             world_cell: world.as_unsafe_world_cell(),
+            recorder,
         }
     }
 }
@@ -71,6 +80,12 @@ struct SystemTaskMetadata {
     is_send: bool,
     /// Is `true` if the system is exclusive.
     is_exclusive: bool,
+    /// Indices of the systems that come before the system in the schedule and have conflicting
+    /// access, which must complete before it can run. Only used when running deterministically.
+    conflicting_predecessors: FixedBitSet,
+    /// The share of entity reservations the system gets, see
+    /// [`Entities::begin_reservation_partition`]. Only used when running deterministically.
+    reservation_share: Option<u32>,
 }
 
 /// The result of running a system that is sent across a channel.
@@ -89,6 +104,10 @@ pub struct MultiThreadedExecutor {
     /// When set, tells the executor that a thread has panicked.
     panic_payload: Mutex<Option<Box<dyn Any + Send>>>,
     starting_systems: FixedBitSet,
+    /// Setting when true runs systems with conflicting access in the order of the schedule.
+    deterministic: bool,
+    /// The number of systems with a share of entity reservations.
+    reservation_shares: u32,
     /// Cached tracing span
     #[cfg(feature = "trace")]
     executor_span: Span,
@@ -122,6 +141,10 @@ pub struct ExecutorState {
     completed_systems: FixedBitSet,
     /// Systems that have run but have not had their buffers applied.
     unapplied_systems: FixedBitSet,
+    /// Is `true` if the system runs are being timed.
+    recording: bool,
+    /// When each system became ready to run, if the system runs are being timed.
+    ready_since: Vec<Option<Instant>>,
 }
 
 /// References to data required by the executor.
@@ -143,7 +166,11 @@ impl Default for MultiThreadedExecutor {
 
 impl SystemExecutor for MultiThreadedExecutor {
     fn kind(&self) -> ExecutorKind {
-        ExecutorKind::MultiThreaded
+        if self.deterministic {
+            ExecutorKind::MultiThreadedDeterministic
+        } else {
+            ExecutorKind::MultiThreaded
+        }
     }
 
     fn init(&mut self, schedule: &SystemSchedule) {
@@ -161,6 +188,7 @@ impl SystemExecutor for MultiThreadedExecutor {
         state.completed_systems = FixedBitSet::with_capacity(sys_count);
         state.skipped_systems = FixedBitSet::with_capacity(sys_count);
         state.unapplied_systems = FixedBitSet::with_capacity(sys_count);
+        state.ready_since = vec![None; sys_count];
 
         state.system_task_metadata = Vec::with_capacity(sys_count);
         for index in 0..sys_count {
@@ -169,12 +197,48 @@ impl SystemExecutor for MultiThreadedExecutor {
                 dependents: schedule.system_dependents[index].clone(),
                 is_send: schedule.systems[index].is_send(),
                 is_exclusive: schedule.systems[index].is_exclusive(),
+                conflicting_predecessors: FixedBitSet::new(),
+                reservation_share: None,
             });
             if schedule.system_dependencies[index] == 0 {
                 self.starting_systems.insert(index);
             }
         }
 
+        if self.deterministic {
+            // Systems are sorted topologically, so making each system wait for the conflicting
+            // systems before it can't introduce cycles.
+            let accesses = (0..sys_count)
+                .map(|index| system_and_conditions_access(schedule, index))
+                .collect::<Vec<_>>();
+            for (index, access) in accesses.iter().enumerate() {
+                let is_exclusive = state.system_task_metadata[index].is_exclusive;
+                let mut conflicting_predecessors = FixedBitSet::with_capacity(sys_count);
+                for (other, other_access) in accesses[..index].iter().enumerate() {
+                    if is_exclusive
+                        || state.system_task_metadata[other].is_exclusive
+                        || !access.is_compatible(other_access)
+                    {
+                        conflicting_predecessors.insert(other);
+                    }
+                }
+                state.system_task_metadata[index].conflicting_predecessors =
+                    conflicting_predecessors;
+            }
+
+            // Systems with deferred parameters like `Commands` can reserve entities while running
+            // in parallel, so each one gets its own share of the reserved entities for them to
+            // get the same ids on every run.
+            self.reservation_shares = 0;
+            for (index, system) in schedule.systems.iter().enumerate() {
+                if !system.is_exclusive() && system.has_deferred() {
+                    state.system_task_metadata[index].reservation_share =
+                        Some(self.reservation_shares);
+                    self.reservation_shares += 1;
+                }
+            }
+        }
+
         state.num_dependencies_remaining = Vec::with_capacity(sys_count);
     }
 
@@ -184,6 +248,7 @@ impl SystemExecutor for MultiThreadedExecutor {
         world: &mut World,
         _skip_systems: Option<&FixedBitSet>,
         error_handler: fn(Error, SystemErrorContext),
+        recorder: Option<&TimingRecorder>,
     ) {
         let state = self.state.get_mut().unwrap();
         // reset counts
@@ -195,6 +260,13 @@ impl SystemExecutor for MultiThreadedExecutor {
             .num_dependencies_remaining
             .clone_from(&schedule.system_dependencies);
         state.ready_systems.clone_from(&self.starting_systems);
+        state.recording = recorder.is_some();
+        if state.recording {
+            let now = Instant::now();
+            for system_index in state.ready_systems.ones() {
+                state.ready_since[system_index] = Some(now);
+            }
+        }
 
         // If stepping is enabled, make sure we skip those systems that should
         // not be run.
@@ -217,7 +289,8 @@ impl SystemExecutor for MultiThreadedExecutor {
             .map(|e| e.0.clone());
         let thread_executor = thread_executor.as_deref();
 
-        let environment = &Environment::new(self, schedule, world);
+        self.begin_reservation_partition(world);
+        let environment = &Environment::new(self, schedule, world, recorder);
 
         ComputeTaskPool::get_or_init(TaskPool::default).scope_with_executor(
             false,
@@ -238,6 +311,7 @@ impl SystemExecutor for MultiThreadedExecutor {
         // End the borrows of self and world in environment by copying out the reference to systems.
         let systems = environment.systems;
 
+        world.entities.end_reservation_partition();
         let state = self.state.get_mut().unwrap();
         if self.apply_final_deferred {
             // Do one final apply buffers after all systems have completed
@@ -332,10 +406,34 @@ impl MultiThreadedExecutor {
             starting_systems: FixedBitSet::new(),
             apply_final_deferred: true,
             panic_payload: Mutex::new(None),
+            deterministic: false,
+            reservation_shares: 0,
             #[cfg(feature = "trace")]
             executor_span: info_span!("multithreaded executor"),
         }
     }
+
+    /// Creates a new `multi_threaded` executor that runs systems with conflicting access in the
+    /// order of the [`Schedule`], for use with [`ExecutorKind::MultiThreadedDeterministic`].
+    ///
+    /// [`Schedule`]: crate::schedule::Schedule
+    pub fn new_deterministic() -> Self {
+        Self {
+            deterministic: true,
+            ..Self::new()
+        }
+    }
+
+    /// When running deterministically, gives each system with a reservation share its own
+    /// share of the entities reserved until the partition is ended.
+    fn begin_reservation_partition(&self, world: &mut World) {
+        if self.deterministic {
+            world.flush_entities();
+            world
+                .entities
+                .begin_reservation_partition(self.reservation_shares);
+        }
+    }
 }
 
 impl ExecutorState {
@@ -354,6 +452,8 @@ impl ExecutorState {
             skipped_systems: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
+            recording: false,
+            ready_since: Vec::new(),
         }
     }
 
@@ -473,6 +573,13 @@ impl ExecutorState {
             return false;
         }
 
+        if !system_meta
+            .conflicting_predecessors
+            .is_subset(&self.completed_systems)
+        {
+            return false;
+        }
+
         if !system_meta.is_send && self.local_thread_running {
             return false;
         }
@@ -599,28 +706,34 @@ impl ExecutorState {
         // Move the full context object into the new future.
         let context = *context;
 
+        let waiting = self.take_waiting_time(system_index);
         let system_meta = &self.system_task_metadata[system_index];
+        let reservation_share = system_meta.reservation_share;
 
         let task = async move {
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
-                // SAFETY:
-                // - The caller ensures that we have permission to
-                // access the world data used by the system.
-                // - `update_archetype_component_access` has been called.
-                unsafe {
-                    if let Err(err) = __rust_begin_short_backtrace::run_unsafe(
-                        system,
-                        context.environment.world_cell,
-                    ) {
-                        (context.error_handler)(
-                            err,
-                            SystemErrorContext {
-                                name: system.name(),
-                                last_run: system.get_last_run(),
-                            },
-                        );
-                    }
-                };
+                time_system(context.environment.recorder, system_index, waiting, || {
+                    with_reservation_share(reservation_share, || {
+                        // SAFETY:
+                        // - The caller ensures that we have permission to
+                        // access the world data used by the system.
+                        // - `update_archetype_component_access` has been called.
+                        unsafe {
+                            if let Err(err) = __rust_begin_short_backtrace::run_unsafe(
+                                system,
+                                context.environment.world_cell,
+                            ) {
+                                (context.error_handler)(
+                                    err,
+                                    SystemErrorContext {
+                                        name: system.name(),
+                                        last_run: system.get_last_run(),
+                                    },
+                                );
+                            }
+                        };
+                    });
+                });
             }));
             context.system_completed(system_index, res, system);
         };
@@ -652,27 +765,40 @@ impl ExecutorState {
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
+                world.entities.end_reservation_partition();
                 let res = apply_deferred(&unapplied_systems, context.environment.systems, world);
+                context
+                    .environment
+                    .executor
+                    .begin_reservation_partition(world);
                 context.system_completed(system_index, res, system);
             };
 
             context.scope.spawn_on_scope(task);
         } else {
+            let waiting = self.take_waiting_time(system_index);
             let task = async move {
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
+                world.entities.end_reservation_partition();
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
-                    if let Err(err) = __rust_begin_short_backtrace::run(system, world) {
-                        (context.error_handler)(
-                            err,
-                            SystemErrorContext {
-                                name: system.name(),
-                                last_run: system.get_last_run(),
-                            },
-                        );
-                    }
+                    time_system(context.environment.recorder, system_index, waiting, || {
+                        if let Err(err) = __rust_begin_short_backtrace::run(system, world) {
+                            (context.error_handler)(
+                                err,
+                                SystemErrorContext {
+                                    name: system.name(),
+                                    last_run: system.get_last_run(),
+                                },
+                            );
+                        }
+                    });
                 }));
+                context
+                    .environment
+                    .executor
+                    .begin_reservation_partition(world);
                 context.system_completed(system_index, res, system);
             };
 
@@ -683,6 +809,14 @@ impl ExecutorState {
         self.local_thread_running = true;
     }
 
+    /// Returns how long the system has been ready to run, if the system runs are being timed.
+    fn take_waiting_time(&mut self, system_index: usize) -> Duration {
+        self.ready_since[system_index]
+            .take()
+            .map(|ready_since| ready_since.elapsed())
+            .unwrap_or_default()
+    }
+
     fn finish_system_and_handle_dependents(&mut self, result: SystemResult) {
         let SystemResult { system_index, .. } = result;
 
@@ -715,6 +849,9 @@ impl ExecutorState {
             *remaining -= 1;
             if *remaining == 0 && !self.completed_systems.contains(dep_idx) {
                 self.ready_systems.insert(dep_idx);
+                if self.recording {
+                    self.ready_since[dep_idx] = Some(Instant::now());
+                }
             }
         }
     }
@@ -729,6 +866,20 @@ impl ExecutorState {
     }
 }
 
+/// Returns the [`ComponentId`] access of the system and of the conditions it is run under.
+fn system_and_conditions_access(schedule: &SystemSchedule, index: usize) -> Access<ComponentId> {
+    let mut access = schedule.systems[index].component_access().clone();
+    for condition in &schedule.system_conditions[index] {
+        access.extend(condition.component_access());
+    }
+    for set_index in schedule.sets_with_conditions_of_systems[index].ones() {
+        for condition in &schedule.set_conditions[set_index] {
+            access.extend(condition.component_access());
+        }
+    }
+    access
+}
+
 fn apply_deferred(
     unapplied_systems: &FixedBitSet,
     systems: &[SyncUnsafeCell<ScheduleSystem>],
@@ -805,9 +956,10 @@ mod tests {
     use crate::{
         prelude::Resource,
         schedule::{ExecutorKind, IntoSystemConfigs, Schedule},
-        system::Commands,
+        system::{Commands, ResMut},
         world::World,
     };
+    use alloc::vec::Vec;
 
     #[derive(Resource)]
     struct R;
@@ -831,6 +983,63 @@ mod tests {
         assert!(world.get_resource::<R>().is_some());
     }
 
+    #[derive(Resource, Default)]
+    struct Log(Vec<u32>);
+
+    fn push<const N: u32>(mut log: ResMut<Log>, mut commands: Commands) {
+        log.0.push(N);
+        commands.queue(|world: &mut World| world.resource_mut::<Log>().0.push(N + 10));
+    }
+
+    fn spawn<const N: u64>(mut commands: Commands) {
+        // Later systems reserve their entity first when they run in parallel.
+        std::thread::sleep(core::time::Duration::from_millis(4 - N));
+        let entity = commands.spawn_empty().id();
+        commands.queue(move |world: &mut World| {
+            world.resource_mut::<Log>().0.push(1000 + entity.index());
+        });
+    }
+
+    #[test]
+    fn deterministic_runs_match() {
+        // Make sure systems can run in parallel, even on a single core.
+        bevy_tasks::ComputeTaskPool::get_or_init(|| {
+            bevy_tasks::TaskPoolBuilder::new().num_threads(4).build()
+        });
+        let run = |executor| {
+            let mut world = World::new();
+            world.init_resource::<Log>();
+            let mut schedule = Schedule::default();
+            schedule.set_executor_kind(executor);
+            schedule.add_systems((
+                (|| std::thread::sleep(core::time::Duration::from_millis(2))).before(push::<1>),
+                push::<1>,
+                push::<2>,
+                push::<3>.after(push::<2>),
+                push::<4>,
+                // These only use `Commands`, so they run in parallel, but their spawned entities
+                // must not depend on which one reserves its entity first.
+                (spawn::<0>, spawn::<1>, spawn::<2>, spawn::<3>),
+            ));
+            for _ in 0..4 {
+                schedule.run(&mut world);
+            }
+            world.remove_resource::<Log>().unwrap().0
+        };
+
+        let expected = run(ExecutorKind::MultiThreadedDeterministic);
+        for _ in 0..4 {
+            assert_eq!(run(ExecutorKind::MultiThreadedDeterministic), expected);
+        }
+        // Apart from the spawned entity ids, the results match running on a single thread.
+        let without_entities =
+            |log: Vec<u32>| log.into_iter().filter(|&n| n < 1000).collect::<Vec<_>>();
+        assert_eq!(
+            without_entities(expected),
+            without_entities(run(ExecutorKind::SingleThreaded))
+        );
+    }
+
     /// Regression test for a weird bug flagged by MIRI in
     /// `spawn_exclusive_system_task`, related to a `&mut World` being captured
     /// inside an `async` block and somehow remaining alive even after its last use.
@@ -1,4 +1,4 @@
-use core::panic::AssertUnwindSafe;
+use core::{panic::AssertUnwindSafe, time::Duration};
 use fixedbitset::FixedBitSet;
 
 #[cfg(feature = "trace")]
@@ -10,7 +10,9 @@ use std::eprintln;
 use crate::{
     result::{Error, SystemErrorContext},
     schedule::{
-        executor::is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
+        executor::is_apply_deferred,
+        timing::{time_system, TimingRecorder},
+        BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
     },
     world::World,
 };
@@ -45,6 +47,7 @@ impl SystemExecutor for SimpleExecutor {
         world: &mut World,
         _skip_systems: Option<&FixedBitSet>,
         error_handler: fn(Error, SystemErrorContext),
+        recorder: Option<&TimingRecorder>,
     ) {
         // If stepping is enabled, make sure we skip those systems that should
         // not be run.
@@ -106,15 +109,17 @@ impl SystemExecutor for SimpleExecutor {
             }
 
             let f = AssertUnwindSafe(|| {
-                if let Err(err) = __rust_begin_short_backtrace::run(system, world) {
-                    error_handler(
-                        err,
-                        SystemErrorContext {
-                            name: system.name(),
-                            last_run: system.get_last_run(),
-                        },
-                    );
-                }
+                time_system(recorder, system_index, Duration::ZERO, || {
+                    if let Err(err) = __rust_begin_short_backtrace::run(system, world) {
+                        error_handler(
+                            err,
+                            SystemErrorContext {
+                                name: system.name(),
+                                last_run: system.get_last_run(),
+                            },
+                        );
+                    }
+                });
             });
 
             #[cfg(feature = "std")]
@@ -1,4 +1,4 @@
-use core::panic::AssertUnwindSafe;
+use core::{panic::AssertUnwindSafe, time::Duration};
 use fixedbitset::FixedBitSet;
 
 #[cfg(feature = "trace")]
@@ -9,7 +9,11 @@ use std::eprintln;
 
 use crate::{
     result::{Error, SystemErrorContext},
-    schedule::{is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
+    schedule::{
+        is_apply_deferred,
+        timing::{time_system, TimingRecorder},
+        BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
+    },
     world::World,
 };
 
@@ -51,6 +55,7 @@ impl SystemExecutor for SingleThreadedExecutor {
         world: &mut World,
         _skip_systems: Option<&FixedBitSet>,
         error_handler: fn(Error, SystemErrorContext),
+        recorder: Option<&TimingRecorder>,
     ) {
         // If stepping is enabled, make sure we skip those systems that should
         // not be run.
@@ -113,24 +118,9 @@ impl SystemExecutor for SingleThreadedExecutor {
             }
 
             let f = AssertUnwindSafe(|| {
-                if system.is_exclusive() {
-                    if let Err(err) = __rust_begin_short_backtrace::run(system, world) {
-                        error_handler(
-                            err,
-                            SystemErrorContext {
-                                name: system.name(),
-                                last_run: system.get_last_run(),
-                            },
-                        );
-                    }
-                } else {
-                    // Use run_unsafe to avoid immediately applying deferred buffers
-                    let world = world.as_unsafe_world_cell();
-                    system.update_archetype_component_access(world);
-                    // SAFETY: We have exclusive, single-threaded access to the world and
-                    // update_archetype_component_access is being called immediately before this.
-                    unsafe {
-                        if let Err(err) = __rust_begin_short_backtrace::run_unsafe(system, world) {
+                time_system(recorder, system_index, Duration::ZERO, || {
+                    if system.is_exclusive() {
+                        if let Err(err) = __rust_begin_short_backtrace::run(system, world) {
                             error_handler(
                                 err,
                                 SystemErrorContext {
@@ -139,8 +129,27 @@ impl SystemExecutor for SingleThreadedExecutor {
                                 },
                             );
                         }
-                    };
-                }
+                    } else {
+                        // Use run_unsafe to avoid immediately applying deferred buffers
+                        let world = world.as_unsafe_world_cell();
+                        system.update_archetype_component_access(world);
+                        // SAFETY: We have exclusive, single-threaded access to the world and
+                        // update_archetype_component_access is being called immediately before this.
+                        unsafe {
+                            if let Err(err) =
+                                __rust_begin_short_backtrace::run_unsafe(system, world)
+                            {
+                                error_handler(
+                                    err,
+                                    SystemErrorContext {
+                                        name: system.name(),
+                                        last_run: system.get_last_run(),
+                                    },
+                                );
+                            }
+                        };
+                    }
+                });
             });
 
             #[cfg(feature = "std")]
@@ -0,0 +1,524 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_platform_support::collections::{HashMap, HashSet};
+use core::fmt::Write;
+use disqualified::ShortName;
+
+use crate::{
+    component::Components,
+    schedule::{graph::Direction, timing::push_json_string, BoxedCondition, NodeId, Schedule},
+};
+
+/// A snapshot of the [`ScheduleGraph`](crate::schedule::ScheduleGraph) of a [`Schedule`] that
+/// can be rendered to [DOT](https://graphviz.org/doc/info/lang.html),
+/// [Mermaid](https://mermaid.js.org/syntax/flowchart.html) or JSON, to visualize or review how
+/// systems are scheduled.
+///
+/// Nodes and edges are sorted by [`NodeId`], so exporting the same schedule twice gives the same
+/// output. The sets automatically created for each system type, used by `.before(my_system)`,
+/// aren't included: edges to or from them are replaced by edges to or from their systems.
+///
+/// Ambiguities are only known once the schedule has been built, for example by
+/// [`Schedule::initialize`](crate::schedule::Schedule::initialize).
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, schedule::graph::ScheduleGraphExport};
+/// fn spawn() {}
+/// fn despawn() {}
+///
+/// let mut world = World::new();
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems((spawn, despawn.after(spawn)));
+/// schedule.initialize(&mut world).unwrap();
+///
+/// let export = ScheduleGraphExport::new(&schedule, world.components());
+/// let dot = export.to_dot();
+/// assert!(dot.contains("\"spawn\" -> \"despawn\""));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScheduleGraphExport {
+    /// The systems of the schedule.
+    pub systems: Vec<ExportedNode>,
+    /// The system sets of the schedule.
+    pub sets: Vec<ExportedNode>,
+    /// The edges of the hierarchy, from a set to one of its members.
+    pub hierarchy: Vec<(NodeId, NodeId)>,
+    /// The edges of the dependency graph, from a node to a node that must run after it.
+    pub dependencies: Vec<(NodeId, NodeId)>,
+    /// The pairs of systems with conflicting access and no ordering between them.
+    pub ambiguities: Vec<ExportedAmbiguity>,
+}
+
+/// A system or system set in a [`ScheduleGraphExport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportedNode {
+    /// The id of the system or system set in the [`ScheduleGraph`].
+    pub id: NodeId,
+    /// The name of the system, or the [`Debug`] representation of the system set.
+    pub name: String,
+    /// The names of the run conditions of the system or system set.
+    pub conditions: Vec<String>,
+}
+
+/// An ambiguity between two systems in a [`ScheduleGraphExport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportedAmbiguity {
+    /// The ids of the two ambiguous systems.
+    pub systems: (NodeId, NodeId),
+    /// The names of the components both systems access in a conflicting way.
+    ///
+    /// If this is empty, the systems conflict on their access to the whole world.
+    pub components: Vec<String>,
+}
+
+impl ScheduleGraphExport {
+    /// Collects the systems, sets, edges and ambiguities of the `schedule`, using `components` to
+    /// name the components the ambiguous systems conflict on.
+    pub fn new(schedule: &Schedule, components: &Components) -> Self {
+        let graph = schedule.graph();
+        let condition_names = |conditions: &[BoxedCondition]| {
+            conditions
+                .iter()
+                .map(|condition| condition.name().into_owned())
+                .collect()
+        };
+
+        let mut systems = schedule
+            .systems_with_conditions()
+            .map(|(id, system, conditions)| ExportedNode {
+                id,
+                name: system.name().into_owned(),
+                conditions: condition_names(conditions),
+            })
+            .collect::<Vec<_>>();
+        systems.sort_by_key(|system| system.id);
+
+        let mut sets = Vec::new();
+        let mut system_type_sets = Vec::new();
+        for (id, set, conditions) in schedule.system_sets_with_conditions() {
+            if set.system_type().is_some() {
+                system_type_sets.push(id);
+            } else {
+                sets.push(ExportedNode {
+                    id,
+                    name: format!("{set:?}"),
+                    conditions: condition_names(conditions),
+                });
+            }
+        }
+        sets.sort_by_key(|set| set.id);
+
+        let hierarchy_graph = graph.hierarchy().graph();
+        // The systems a node stands for in the exported dependencies.
+        let resolve = |id: NodeId| -> Vec<NodeId> {
+            if system_type_sets.contains(&id) {
+                hierarchy_graph
+                    .neighbors_directed(id, Direction::Outgoing)
+                    .collect()
+            } else {
+                Vec::from([id])
+            }
+        };
+
+        let mut hierarchy = hierarchy_graph
+            .all_edges()
+            .filter(|(set, _)| !system_type_sets.contains(set))
+            .collect::<Vec<_>>();
+        hierarchy.sort_unstable();
+
+        let mut dependencies = Vec::new();
+        for (a, b) in graph.dependency().graph().all_edges() {
+            for a in resolve(a) {
+                dependencies.extend(resolve(b).into_iter().map(|b| (a, b)));
+            }
+        }
+        dependencies.sort_unstable();
+        dependencies.dedup();
+
+        let mut ambiguities = graph
+            .conflicting_systems()
+            .iter()
+            .map(|(a, b, conflicts)| ExportedAmbiguity {
+                systems: if a <= b { (*a, *b) } else { (*b, *a) },
+                components: conflicts
+                    .iter()
+                    .filter_map(|&id| components.get_name(id))
+                    .map(ToString::to_string)
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        ambiguities.sort_by_key(|ambiguity| ambiguity.systems);
+
+        Self {
+            systems,
+            sets,
+            hierarchy,
+            dependencies,
+            ambiguities,
+        }
+    }
+
+    /// Renders the graph in the DOT language of [Graphviz](https://graphviz.org).
+    ///
+    /// Systems are boxes and sets are ellipses, labeled with their run conditions. Dependencies
+    /// are solid arrows, set memberships are dashed gray arrows from the set, and ambiguities are
+    /// red lines labeled with the conflicting components.
+    pub fn to_dot(&self) -> String {
+        let keys = self.node_keys();
+        let key = |id: NodeId| &keys[&id];
+        let mut dot = String::from("digraph schedule {\n    rankdir=LR;\n");
+        for (node, shape) in self
+            .systems
+            .iter()
+            .map(|system| (system, "box"))
+            .chain(self.sets.iter().map(|set| (set, "ellipse")))
+        {
+            let _ = write!(dot, "    \"{}\" [label=", key(node.id));
+            push_dot_string(&mut dot, &node_label(node));
+            let _ = writeln!(dot, ", shape={shape}];");
+        }
+        for (set, member) in &self.hierarchy {
+            let _ = writeln!(
+                dot,
+                "    \"{}\" -> \"{}\" [style=dashed, color=gray];",
+                key(*set),
+                key(*member)
+            );
+        }
+        for (a, b) in &self.dependencies {
+            let _ = writeln!(dot, "    \"{}\" -> \"{}\";", key(*a), key(*b));
+        }
+        for ambiguity in &self.ambiguities {
+            let (a, b) = ambiguity.systems;
+            let _ = write!(
+                dot,
+                "    \"{}\" -> \"{}\" [dir=none, color=red, label=",
+                key(a),
+                key(b)
+            );
+            push_dot_string(&mut dot, &ambiguity_label(ambiguity));
+            dot.push_str("];\n");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the graph as a [Mermaid](https://mermaid.js.org) flowchart.
+    ///
+    /// Systems are rectangles and sets are stadiums, labeled with their run conditions.
+    /// Dependencies are arrows, set memberships are dotted arrows from the set, and ambiguities
+    /// are dotted lines labeled with the conflicting components.
+    pub fn to_mermaid(&self) -> String {
+        let keys = self.node_keys();
+        let key = |id: NodeId| &keys[&id];
+        let mut mermaid = String::from("flowchart LR\n");
+        for system in &self.systems {
+            let _ = writeln!(
+                mermaid,
+                "    {}[\"{}\"]",
+                key(system.id),
+                mermaid_escape(&node_label(system))
+            );
+        }
+        for set in &self.sets {
+            let _ = writeln!(
+                mermaid,
+                "    {}([\"{}\"])",
+                key(set.id),
+                mermaid_escape(&node_label(set))
+            );
+        }
+        for (set, member) in &self.hierarchy {
+            let _ = writeln!(mermaid, "    {} -.-> {}", key(*set), key(*member));
+        }
+        for (a, b) in &self.dependencies {
+            let _ = writeln!(mermaid, "    {} --> {}", key(*a), key(*b));
+        }
+        for ambiguity in &self.ambiguities {
+            let (a, b) = ambiguity.systems;
+            let _ = writeln!(
+                mermaid,
+                "    {} -. \"{}\" .- {}",
+                key(a),
+                mermaid_escape(&ambiguity_label(ambiguity)),
+                key(b)
+            );
+        }
+        mermaid
+    }
+
+    /// Renders the graph as a JSON object.
+    ///
+    /// The object has the same fields as [`ScheduleGraphExport`]. Nodes are referenced by objects
+    /// like `{"system": 3}` or `{"set": 1}`, holding the index of the [`NodeId`].
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"systems\":");
+        push_json_nodes(&mut json, &self.systems);
+        json.push_str(",\"sets\":");
+        push_json_nodes(&mut json, &self.sets);
+        json.push_str(",\"hierarchy\":");
+        push_json_edges(&mut json, &self.hierarchy);
+        json.push_str(",\"dependencies\":");
+        push_json_edges(&mut json, &self.dependencies);
+        json.push_str(",\"ambiguities\":[");
+        for (index, ambiguity) in self.ambiguities.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"systems\":");
+            push_json_edge(&mut json, ambiguity.systems);
+            json.push_str(",\"components\":");
+            push_json_strings(&mut json, &ambiguity.components);
+            json.push('}');
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+impl ScheduleGraphExport {
+    /// Returns the identifiers of the nodes in the DOT and Mermaid outputs.
+    ///
+    /// They are derived from the names of the nodes, so that they are the same when systems are
+    /// added or removed elsewhere in the schedule. Nodes with the same name get a numbered suffix,
+    /// in the order of their [`NodeId`].
+    fn node_keys(&self) -> HashMap<NodeId, String> {
+        let mut keys = HashMap::default();
+        let mut used = <HashSet<String>>::default();
+        for node in self.systems.iter().chain(&self.sets) {
+            let base = node_key(&node.name);
+            let mut key = base.clone();
+            let mut count = 1;
+            while !used.insert(key.clone()) {
+                count += 1;
+                key = format!("{base}_{count}");
+            }
+            keys.insert(node.id, key);
+        }
+        keys
+    }
+}
+
+/// Turns the name of a node into an identifier that is valid in both DOT and Mermaid.
+fn node_key(name: &str) -> String {
+    let mut key = ShortName(name)
+        .to_string()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    // Mermaid doesn't allow nodes named `end`, and identifiers can't start with a digit.
+    if key.is_empty() || key.starts_with(|c: char| c.is_ascii_digit()) {
+        key.insert(0, '_');
+    } else if key.eq_ignore_ascii_case("end") {
+        key.push('_');
+    }
+    key
+}
+
+fn node_label(node: &ExportedNode) -> String {
+    if node.conditions.is_empty() {
+        node.name.clone()
+    } else {
+        format!("{}\nif {}", node.name, node.conditions.join(" && "))
+    }
+}
+
+fn ambiguity_label(ambiguity: &ExportedAmbiguity) -> String {
+    if ambiguity.components.is_empty() {
+        String::from("World")
+    } else {
+        ambiguity.components.join(", ")
+    }
+}
+
+fn push_dot_string(dot: &mut String, value: &str) {
+    dot.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => dot.push_str("\\\""),
+            '\\' => dot.push_str("\\\\"),
+            '\n' => dot.push_str("\\n"),
+            c => dot.push(c),
+        }
+    }
+    dot.push('"');
+}
+
+/// Escapes the characters that can't appear in a quoted Mermaid label.
+fn mermaid_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("#quot;"),
+            '<' => escaped.push_str("#lt;"),
+            '>' => escaped.push_str("#gt;"),
+            '\n' => escaped.push_str("<br>"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn push_json_node_id(json: &mut String, id: NodeId) {
+    let _ = match id {
+        NodeId::System(index) => write!(json, "{{\"system\":{index}}}"),
+        NodeId::Set(index) => write!(json, "{{\"set\":{index}}}"),
+    };
+}
+
+fn push_json_strings(json: &mut String, values: &[String]) {
+    json.push('[');
+    for (index, value) in values.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        push_json_string(json, value);
+    }
+    json.push(']');
+}
+
+fn push_json_nodes(json: &mut String, nodes: &[ExportedNode]) {
+    json.push('[');
+    for (index, node) in nodes.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        json.push_str("{\"id\":");
+        push_json_node_id(json, node.id);
+        json.push_str(",\"name\":");
+        push_json_string(json, &node.name);
+        json.push_str(",\"conditions\":");
+        push_json_strings(json, &node.conditions);
+        json.push('}');
+    }
+    json.push(']');
+}
+
+fn push_json_edge(json: &mut String, (a, b): (NodeId, NodeId)) {
+    json.push('[');
+    push_json_node_id(json, a);
+    json.push(',');
+    push_json_node_id(json, b);
+    json.push(']');
+}
+
+fn push_json_edges(json: &mut String, edges: &[(NodeId, NodeId)]) {
+    json.push('[');
+    for (index, &edge) in edges.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        push_json_edge(json, edge);
+    }
+    json.push(']');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, schedule::ScheduleBuildSettings};
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Physics;
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    fn setup() {}
+
+    fn step(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    fn report(mut counter: ResMut<Counter>) {
+        counter.0 = 0;
+    }
+
+    fn export() -> ScheduleGraphExport {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::default();
+        schedule.set_build_settings(ScheduleBuildSettings {
+            ambiguity_detection: crate::schedule::LogLevel::Ignore,
+            ..Default::default()
+        });
+        schedule
+            .configure_sets(Physics.run_if(|| true))
+            .add_systems((setup, step.in_set(Physics).after(setup), report));
+        schedule.initialize(&mut world).unwrap();
+        ScheduleGraphExport::new(&schedule, world.components())
+    }
+
+    #[test]
+    fn collects_nodes_and_edges() {
+        let export = export();
+        let names = export
+            .systems
+            .iter()
+            .map(|system| system.name.rsplit("::").next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["setup", "step", "report"]);
+        let [setup, step, report] = [0, 1, 2].map(NodeId::System);
+
+        assert_eq!(export.sets.len(), 1);
+        let physics = &export.sets[0];
+        assert_eq!(physics.name, "Physics");
+        assert_eq!(physics.conditions.len(), 1);
+
+        assert_eq!(export.hierarchy, [(physics.id, step)]);
+        assert_eq!(export.dependencies, [(setup, step)]);
+        assert_eq!(export.ambiguities.len(), 1);
+        assert_eq!(export.ambiguities[0].systems, (step, report));
+        assert!(export.ambiguities[0].components[0].ends_with("Counter"));
+    }
+
+    #[test]
+    fn renders() {
+        let export = export();
+
+        let dot = export.to_dot();
+        assert!(dot.starts_with("digraph schedule {"));
+        assert!(dot.contains("\"Physics\" -> \"step\" [style=dashed, color=gray];"));
+        assert!(dot.contains("\"setup\" -> \"step\";"));
+        assert!(dot.contains("\"step\" -> \"report\" [dir=none, color=red, label="));
+
+        let mermaid = export.to_mermaid();
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("Physics([\"Physics<br>if "));
+        assert!(mermaid.contains("setup --> step"));
+        assert!(mermaid.contains("step -. \""));
+
+        let json = export.to_json();
+        assert!(json.contains("\"dependencies\":[[{\"system\":0},{\"system\":1}]]"));
+        assert!(json.contains("\"ambiguities\":[{\"systems\":[{\"system\":1},{\"system\":2}],"));
+        assert!(json.ends_with("]}]}"));
+    }
+
+    #[test]
+    fn keys_nodes_by_name() {
+        mod other {
+            pub fn step() {}
+        }
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct End;
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((step, other::step.in_set(End), |_: Res<Counter>| {}));
+        schedule.initialize(&mut world).unwrap();
+        let export = ScheduleGraphExport::new(&schedule, world.components());
+
+        let keys = export.node_keys();
+        let [step, other_step, closure] = [0, 1, 2].map(|index| &keys[&NodeId::System(index)]);
+        assert_eq!(step, "step");
+        assert_eq!(other_step, "step_2");
+        assert!(closure
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_'));
+        assert_eq!(keys[&export.sets[0].id], "End_");
+    }
+}
@@ -12,10 +12,12 @@ use fixedbitset::FixedBitSet;
 
 use crate::schedule::set::*;
 
+mod export;
 mod graph_map;
 mod node;
 mod tarjan_scc;
 
+pub use export::{ExportedAmbiguity, ExportedNode, ScheduleGraphExport};
 pub use graph_map::{DiGraph, Direction, UnGraph};
 pub use node::NodeId;
 
@@ -8,9 +8,10 @@ mod pass;
 mod schedule;
 mod set;
 mod stepping;
+mod timing;
 
 use self::graph::*;
-pub use self::{condition::*, config::*, executor::*, schedule::*, set::*};
+pub use self::{condition::*, config::*, executor::*, schedule::*, set::*, timing::*};
 pub use pass::ScheduleBuildPass;
 
 pub use self::graph::NodeId;
@@ -276,6 +277,42 @@ mod tests {
             assert_eq!(world.resource::<SystemOrder>().0, vec![0]);
         }
 
+        #[test]
+        fn systems_with_conditions_before_and_after_initialization() {
+            #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+            struct Set;
+
+            let mut world = World::default();
+            let mut schedule = Schedule::default();
+
+            world.init_resource::<RunConditionBool>();
+            world.init_resource::<SystemOrder>();
+
+            schedule.configure_sets(Set.run_if(|| true));
+            schedule.add_systems(
+                make_function_system(0)
+                    .in_set(Set)
+                    .run_if(|condition: Res<RunConditionBool>| condition.0),
+            );
+
+            let count_conditions = |schedule: &Schedule| {
+                let systems = schedule
+                    .systems_with_conditions()
+                    .map(|(_, _, conditions)| conditions.len())
+                    .collect::<Vec<_>>();
+                let sets = schedule
+                    .system_sets_with_conditions()
+                    .filter(|(_, set, _)| set.system_type().is_none())
+                    .map(|(_, _, conditions)| conditions.len())
+                    .collect::<Vec<_>>();
+                (systems, sets)
+            };
+
+            assert_eq!(count_conditions(&schedule), (vec![1], vec![1]));
+            schedule.run(&mut world);
+            assert_eq!(count_conditions(&schedule), (vec![1], vec![1]));
+        }
+
         #[test]
         fn systems_with_distributive_condition() {
             let mut world = World::default();
@@ -1232,5 +1269,11 @@ mod tests {
         fn multi_threaded_executor() {
             assert_executor_supports_stepping!(ExecutorKind::MultiThreaded);
         }
+
+        /// verify the deterministic [`MultiThreadedExecutor`] supports stepping
+        #[test]
+        fn multi_threaded_deterministic_executor() {
+            assert_executor_supports_stepping!(ExecutorKind::MultiThreadedDeterministic);
+        }
     }
 }
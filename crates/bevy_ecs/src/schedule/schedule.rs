@@ -29,7 +29,7 @@ use crate::{
     prelude::Component,
     resource::Resource,
     result::{DefaultSystemErrorHandler, Error, SystemErrorContext},
-    schedule::*,
+    schedule::{timing::TimingRecorder, *},
     system::ScheduleSystem,
     world::World,
 };
@@ -221,6 +221,10 @@ fn make_executor(kind: ExecutorKind) -> Box<dyn SystemExecutor> {
         ExecutorKind::SingleThreaded => Box::new(SingleThreadedExecutor::new()),
         #[cfg(feature = "std")]
         ExecutorKind::MultiThreaded => Box::new(MultiThreadedExecutor::new()),
+        #[cfg(feature = "std")]
+        ExecutorKind::MultiThreadedDeterministic => {
+            Box::new(MultiThreadedExecutor::new_deterministic())
+        }
     }
 }
 
@@ -445,9 +449,18 @@ impl Schedule {
 
         let error_handler = self.error_handler.expect("schedule initialized");
 
+        let recorder = world
+            .contains_resource::<SystemTimings>()
+            .then(TimingRecorder::new);
+
         #[cfg(not(feature = "bevy_debug_stepping"))]
-        self.executor
-            .run(&mut self.executable, world, None, error_handler);
+        self.executor.run(
+            &mut self.executable,
+            world,
+            None,
+            error_handler,
+            recorder.as_ref(),
+        );
 
         #[cfg(feature = "bevy_debug_stepping")]
         {
@@ -461,8 +474,15 @@ impl Schedule {
                 world,
                 skip_systems.as_ref(),
                 error_handler,
+                recorder.as_ref(),
             );
         }
+
+        if let Some(recorder) = recorder {
+            if let Some(mut timings) = world.get_resource_mut::<SystemTimings>() {
+                timings.record(self.label, &self.executable, recorder);
+            }
+        }
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
@@ -572,6 +592,46 @@ impl Schedule {
         Ok(iter)
     }
 
+    /// Returns an iterator over all systems in this schedule, along with the conditions for each
+    /// system.
+    ///
+    /// Unlike [`Schedule::systems`], this works whether or not the schedule has been initialized.
+    pub fn systems_with_conditions(
+        &self,
+    ) -> impl Iterator<Item = (NodeId, &ScheduleSystem, &[BoxedCondition])> {
+        // Initializing the schedule moves its systems and their conditions out of the graph and
+        // into the executable schedule, so look in both.
+        let executable = self
+            .executable
+            .system_ids
+            .iter()
+            .zip(&self.executable.systems)
+            .zip(&self.executable.system_conditions)
+            .map(|((node_id, system), conditions)| (*node_id, system, conditions.as_slice()));
+        self.graph.systems().chain(executable)
+    }
+
+    /// Returns an iterator over all system sets in this schedule, along with the conditions for
+    /// each system set.
+    ///
+    /// Unlike [`ScheduleGraph::system_sets`], this reports the conditions of the system sets
+    /// even after the schedule has been initialized.
+    pub fn system_sets_with_conditions(
+        &self,
+    ) -> impl Iterator<Item = (NodeId, &dyn SystemSet, &[BoxedCondition])> {
+        self.graph.system_sets().map(|(node_id, set, conditions)| {
+            let conditions = self
+                .executable
+                .set_ids
+                .iter()
+                .position(|id| *id == node_id)
+                .map_or(conditions, |index| {
+                    self.executable.set_conditions[index].as_slice()
+                });
+            (node_id, set, conditions)
+        })
+    }
+
     /// Returns the number of systems in this schedule.
     pub fn systems_len(&self) -> usize {
         if !self.executor_initialized {
@@ -0,0 +1,482 @@
+//! Optional instrumentation of the systems run by [`Schedule`](super::Schedule)s.
+//!
+//! Inserting the [`SystemTimings`] resource makes every schedule run in its [`World`] record how
+//! many times each system ran and for how long, and how much time was spent waiting to run. The
+//! runs can also be kept to be exported in the
+//! [Chrome trace event format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU),
+//! viewable in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+//!
+//! ```
+//! # use bevy_ecs::{prelude::*, schedule::SystemTimings};
+//! fn physics() {}
+//!
+//! let mut world = World::new();
+//! world.insert_resource(SystemTimings::with_trace());
+//!
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems(physics);
+//! schedule.run(&mut world);
+//! schedule.run(&mut world);
+//!
+//! let timings = world.resource::<SystemTimings>();
+//! let physics = timings
+//!     .schedule(schedule.label())
+//!     .unwrap()
+//!     .system("physics")
+//!     .unwrap();
+//! assert_eq!(physics.runs, 2);
+//! assert!(timings.chrome_trace().contains("physics"));
+//! ```
+//!
+//! [`World`]: crate::world::World
+
+use alloc::{borrow::Cow, format, string::String, vec::Vec};
+use bevy_platform_support::{collections::HashMap, sync::Mutex, time::Instant};
+use core::{fmt::Write, time::Duration};
+
+use crate::{
+    resource::Resource,
+    schedule::{InternedScheduleLabel, NodeId, ScheduleLabel, SystemSchedule},
+};
+
+/// A [`Resource`] collecting the run counts and durations of the systems of every schedule.
+///
+/// Recording only happens while this resource exists in the world running the schedules.
+#[derive(Resource)]
+pub struct SystemTimings {
+    /// The origin of the trace timestamps.
+    epoch: Instant,
+    schedules: HashMap<InternedScheduleLabel, ScheduleTimings>,
+    /// Every system and schedule run, if they are kept.
+    trace: Option<Vec<TraceEvent>>,
+}
+
+impl Default for SystemTimings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemTimings {
+    /// Creates a [`SystemTimings`] that only collects statistics.
+    pub fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+            schedules: HashMap::default(),
+            trace: None,
+        }
+    }
+
+    /// Creates a [`SystemTimings`] that also keeps every run, to be exported with
+    /// [`chrome_trace`](Self::chrome_trace).
+    ///
+    /// Memory usage grows with each run, until [`clear`](Self::clear) is called.
+    pub fn with_trace() -> Self {
+        Self {
+            trace: Some(Vec::new()),
+            ..Self::new()
+        }
+    }
+
+    /// Returns the timings of the schedule with the given `label`, if it ran.
+    pub fn schedule(&self, label: impl ScheduleLabel) -> Option<&ScheduleTimings> {
+        self.schedules.get(&label.intern())
+    }
+
+    /// Returns an iterator over the timings of every schedule that ran.
+    pub fn schedules(&self) -> impl Iterator<Item = (InternedScheduleLabel, &ScheduleTimings)> {
+        self.schedules
+            .iter()
+            .map(|(label, timings)| (*label, timings))
+    }
+
+    /// Forgets every recorded run.
+    pub fn clear(&mut self) {
+        self.schedules.clear();
+        if let Some(trace) = &mut self.trace {
+            trace.clear();
+        }
+    }
+
+    /// Returns the recorded runs as a JSON document in the Chrome trace event format.
+    ///
+    /// Each system run is a complete event named after the system, in the category of its
+    /// schedule, on the thread it ran on. The runs of the schedules themselves are included too.
+    ///
+    /// The trace is empty unless this was created with [`with_trace`](Self::with_trace).
+    pub fn chrome_trace(&self) -> String {
+        let mut json = String::from("{\"traceEvents\":[");
+        for (index, event) in self.trace.iter().flatten().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"name\":");
+            push_json_string(&mut json, &event.name);
+            json.push_str(",\"cat\":");
+            push_json_string(&mut json, &format!("{:?}", event.schedule));
+            // Timestamps are in microseconds.
+            let _ = write!(
+                json,
+                ",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":0,\"tid\":{}}}",
+                event.start.as_secs_f64() * 1e6,
+                event.duration.as_secs_f64() * 1e6,
+                event.thread,
+            );
+        }
+        json.push_str("],\"displayTimeUnit\":\"ms\"}");
+        json
+    }
+
+    /// Writes the recorded runs to `writer` in the Chrome trace event format.
+    ///
+    /// See [`chrome_trace`](Self::chrome_trace).
+    #[cfg(feature = "std")]
+    pub fn write_chrome_trace(&self, mut writer: impl std::io::Write) -> std::io::Result<()> {
+        writer.write_all(self.chrome_trace().as_bytes())
+    }
+
+    /// Adds the runs recorded while running the `schedule` with the given `label`.
+    pub(super) fn record(
+        &mut self,
+        label: InternedScheduleLabel,
+        schedule: &SystemSchedule,
+        recorder: TimingRecorder,
+    ) {
+        let duration = recorder.start.elapsed();
+        let spans = recorder.spans.into_inner().unwrap();
+        let timings = self.schedules.entry(label).or_default();
+        timings.runs += 1;
+        timings.total += duration;
+
+        for span in &spans {
+            let system = &schedule.systems[span.system_index];
+            timings
+                .systems
+                .entry(schedule.system_ids[span.system_index])
+                .or_insert_with(|| SystemTiming::new(system.name()))
+                .add_run(span.duration, span.waiting);
+        }
+
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEvent {
+                name: format!("{label:?}").into(),
+                schedule: label,
+                thread: recorder.thread,
+                start: recorder.start.saturating_duration_since(self.epoch),
+                duration,
+            });
+            trace.extend(spans.into_iter().map(|span| TraceEvent {
+                name: schedule.systems[span.system_index].name(),
+                schedule: label,
+                thread: span.thread,
+                start: span.start.saturating_duration_since(self.epoch),
+                duration: span.duration,
+            }));
+        }
+    }
+}
+
+/// The timings of the systems of a schedule, in [`SystemTimings`].
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleTimings {
+    /// The number of times the schedule ran.
+    pub runs: u64,
+    /// The total time spent running the schedule.
+    pub total: Duration,
+    /// The timings of each system of the schedule that ran at least once.
+    pub systems: HashMap<NodeId, SystemTiming>,
+}
+
+impl ScheduleTimings {
+    /// Returns the timings of the first system with the given `name`, if it ran.
+    ///
+    /// The name is matched against both the full and the short name of the system, so
+    /// `"my_system"` finds `my_crate::my_system`.
+    pub fn system(&self, name: &str) -> Option<&SystemTiming> {
+        self.systems.values().find(|system| {
+            system.name == name
+                || system
+                    .name
+                    .strip_suffix(name)
+                    .is_some_and(|path| path.ends_with("::"))
+        })
+    }
+
+    /// Returns the total time spent running systems, summed across threads.
+    pub fn busy(&self) -> Duration {
+        self.systems.values().map(|system| system.total).sum()
+    }
+
+    /// Returns the average number of systems running at the same time while the schedule ran.
+    ///
+    /// This is `1.0` at most for single-threaded executors, and is lower when the schedule spends
+    /// time outside of systems, like evaluating run conditions or applying commands.
+    pub fn parallelism(&self) -> f64 {
+        if self.total.is_zero() {
+            return 0.0;
+        }
+        self.busy().as_secs_f64() / self.total.as_secs_f64()
+    }
+}
+
+/// The timings of a system, in [`ScheduleTimings`].
+#[derive(Debug, Clone)]
+pub struct SystemTiming {
+    /// The name of the system.
+    pub name: Cow<'static, str>,
+    /// The number of times the system ran.
+    pub runs: u64,
+    /// The total time spent running the system.
+    pub total: Duration,
+    /// The duration of the shortest run of the system.
+    pub min: Duration,
+    /// The duration of the longest run of the system.
+    pub max: Duration,
+    /// The total time the system was ready to run, with all of its dependencies completed, but
+    /// couldn't start because of systems with conflicting access or busy threads.
+    ///
+    /// This is always zero for single-threaded executors.
+    pub waiting: Duration,
+}
+
+impl SystemTiming {
+    fn new(name: Cow<'static, str>) -> Self {
+        Self {
+            name,
+            runs: 0,
+            total: Duration::ZERO,
+            min: Duration::MAX,
+            max: Duration::ZERO,
+            waiting: Duration::ZERO,
+        }
+    }
+
+    fn add_run(&mut self, duration: Duration, waiting: Duration) {
+        self.runs += 1;
+        self.total += duration;
+        self.min = self.min.min(duration);
+        self.max = self.max.max(duration);
+        self.waiting += waiting;
+    }
+
+    /// Returns the average duration of a run of the system.
+    pub fn mean(&self) -> Duration {
+        match u32::try_from(self.runs) {
+            Ok(0) => Duration::ZERO,
+            Ok(runs) => self.total / runs,
+            Err(_) => Duration::from_secs_f64(self.total.as_secs_f64() / self.runs as f64),
+        }
+    }
+}
+
+/// A system or schedule run kept for [`SystemTimings::chrome_trace`].
+struct TraceEvent {
+    name: Cow<'static, str>,
+    schedule: InternedScheduleLabel,
+    thread: u64,
+    /// The start of the run, since [`SystemTimings::epoch`].
+    start: Duration,
+    duration: Duration,
+}
+
+/// Collects the system runs of a single schedule run, shared by the tasks of the executor.
+pub(super) struct TimingRecorder {
+    start: Instant,
+    thread: u64,
+    spans: Mutex<Vec<SystemSpan>>,
+}
+
+struct SystemSpan {
+    system_index: usize,
+    thread: u64,
+    start: Instant,
+    duration: Duration,
+    waiting: Duration,
+}
+
+impl TimingRecorder {
+    pub(super) fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            thread: thread_index(),
+            spans: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+/// Runs `f`, recording it as a run of the system at `system_index` if there is a `recorder`.
+pub(super) fn time_system<T>(
+    recorder: Option<&TimingRecorder>,
+    system_index: usize,
+    waiting: Duration,
+    f: impl FnOnce() -> T,
+) -> T {
+    let Some(recorder) = recorder else {
+        return f();
+    };
+    let start = Instant::now();
+    let out = f();
+    let duration = start.elapsed();
+    recorder.spans.lock().unwrap().push(SystemSpan {
+        system_index,
+        thread: thread_index(),
+        start,
+        duration,
+        waiting,
+    });
+    out
+}
+
+/// Returns a small number identifying the current thread in traces.
+#[cfg(feature = "std")]
+fn thread_index() -> u64 {
+    use core::sync::atomic::{AtomicU64, Ordering};
+
+    static NEXT_INDEX: AtomicU64 = AtomicU64::new(0);
+    std::thread_local! {
+        static INDEX: u64 = NEXT_INDEX.fetch_add(1, Ordering::Relaxed);
+    }
+    INDEX.with(|index| *index)
+}
+
+#[cfg(not(feature = "std"))]
+fn thread_index() -> u64 {
+    0
+}
+
+/// Appends `value` to `json` as a JSON string literal.
+pub(super) fn push_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::*,
+        schedule::{ExecutorKind, ScheduleLabel},
+    };
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Update;
+
+    fn first() {}
+
+    fn second(_: &mut World) {}
+
+    fn skipped() {}
+
+    #[test]
+    fn records_each_system() {
+        for kind in [
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::Simple,
+            #[cfg(feature = "std")]
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = World::new();
+            world.insert_resource(SystemTimings::new());
+            let mut schedule = Schedule::new(Update);
+            schedule.set_executor_kind(kind);
+            schedule.add_systems((first, second.after(first), skipped.run_if(|| false)));
+            for _ in 0..3 {
+                schedule.run(&mut world);
+            }
+
+            let timings = world.resource::<SystemTimings>();
+            let update = timings.schedule(Update).unwrap();
+            assert_eq!(update.runs, 3);
+            assert_eq!(update.systems.len(), 2, "{kind:?}");
+            let first = update.system("first").unwrap();
+            assert_eq!(first.runs, 3);
+            assert!(first.min <= first.mean() && first.mean() <= first.max);
+            assert_eq!(update.system("second").unwrap().runs, 3);
+            assert!(update.busy() <= update.total || kind == ExecutorKind::MultiThreaded);
+            assert_eq!(
+                timings.chrome_trace(),
+                "{\"traceEvents\":[],\"displayTimeUnit\":\"ms\"}"
+            );
+        }
+    }
+
+    #[test]
+    fn chrome_trace() {
+        let mut world = World::new();
+        world.insert_resource(SystemTimings::with_trace());
+        let mut schedule = Schedule::new(Update);
+        schedule.add_systems((first, second));
+        schedule.run(&mut world);
+
+        let trace = world.resource::<SystemTimings>().chrome_trace();
+        assert!(trace.starts_with("{\"traceEvents\":[{\"name\":\"Update\",\"cat\":\"Update\""));
+        assert!(trace.contains("\"name\":\"bevy_ecs::schedule::timing::tests::first\""));
+        assert!(trace.contains("\"name\":\"bevy_ecs::schedule::timing::tests::second\""));
+        assert_eq!(trace.matches("\"ph\":\"X\"").count(), 3);
+
+        world.resource_mut::<SystemTimings>().clear();
+        assert!(world
+            .resource::<SystemTimings>()
+            .schedules()
+            .next()
+            .is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn conflicting_systems_wait() {
+        #[derive(Resource, Default)]
+        struct Shared;
+
+        fn slow(_: ResMut<Shared>) {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Shared>();
+        world.insert_resource(SystemTimings::new());
+        let mut schedule = Schedule::new(Update);
+        schedule.set_executor_kind(ExecutorKind::MultiThreaded);
+        schedule.add_systems((slow, slow));
+        schedule.run(&mut world);
+
+        let update = world.resource::<SystemTimings>().schedule(Update).unwrap();
+        let waiting = update
+            .systems
+            .values()
+            .map(|system| system.waiting)
+            .max()
+            .unwrap();
+        assert!(waiting >= Duration::from_millis(5));
+        assert!(update.parallelism() <= 1.0);
+    }
+
+    #[test]
+    fn json_strings() {
+        let mut json = String::new();
+        push_json_string(&mut json, "a\"b\\c\n<T>");
+        assert_eq!(json, "\"a\\\"b\\\\c\\u000a<T>\"");
+    }
+
+    #[test]
+    fn no_recording_without_resource() {
+        let mut world = World::new();
+        let mut schedule = Schedule::new(Update);
+        schedule.add_systems(first);
+        schedule.run(&mut world);
+        world.insert_resource(SystemTimings::new());
+        schedule.run(&mut world);
+        let timings = world.resource::<SystemTimings>();
+        assert_eq!(timings.schedule(Update).unwrap().runs, 1);
+    }
+}
@@ -204,6 +204,39 @@ impl BlobArray {
         }
     }
 
+    /// Moves the first `count` elements of `other` to this array, starting at `index`. The moved
+    /// elements of `other` are left uninitialized, and are not dropped.
+    ///
+    /// # Safety
+    /// - `other` must store elements with the same layout as this array.
+    /// - The first `count` elements of `other` must be initialized.
+    /// - The elements at `index..index + count` must be within the capacity of this array, and not
+    ///   initialized.
+    pub unsafe fn move_from_unchecked(
+        &mut self,
+        other: &mut BlobArray,
+        index: usize,
+        count: usize,
+    ) {
+        #[cfg(debug_assertions)]
+        {
+            debug_assert_eq!(self.item_layout, other.item_layout);
+            debug_assert!(index + count <= self.capacity);
+            debug_assert!(count <= other.capacity);
+        }
+        let size = self.item_layout.size();
+        // SAFETY:
+        // - The caller ensures that both ranges are in bounds of their allocations.
+        // - The arrays are distinct allocations, so the ranges don't overlap.
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                other.data.as_ptr(),
+                self.data.as_ptr().add(index * size),
+                count * size,
+            );
+        }
+    }
+
     /// Drops the last element in this [`BlobArray`].
     ///
     /// # Safety
@@ -229,6 +229,34 @@ impl ThinColumn {
         );
     }
 
+    /// Moves the first `len` elements of `other` to this column, starting at `dst_row`. The
+    /// elements of `other` are left uninitialized.
+    ///
+    /// # Safety
+    ///  - `other` must have the same data layout as `self`
+    ///  - the first `len` elements of `other` must be initialized to valid values.
+    ///  - `dst_row.as_usize()..dst_row.as_usize() + len` must be in bounds for `self`, and not
+    ///    initialized yet.
+    #[inline]
+    pub(crate) unsafe fn initialize_all_from_unchecked(
+        &mut self,
+        other: &mut ThinColumn,
+        len: usize,
+        dst_row: TableRow,
+    ) {
+        let index = dst_row.as_usize();
+        self.data.move_from_unchecked(&mut other.data, index, len);
+        self.added_ticks
+            .move_from_unchecked(&mut other.added_ticks, index, len);
+        self.changed_ticks
+            .move_from_unchecked(&mut other.changed_ticks, index, len);
+        self.changed_by.as_mut().zip(other.changed_by.as_mut()).map(
+            |(self_changed_by, other_changed_by)| {
+                self_changed_by.move_from_unchecked(other_changed_by, index, len);
+            },
+        );
+    }
+
     /// Call [`Tick::check_tick`] on all of the ticks stored in this column.
     ///
     /// # Safety
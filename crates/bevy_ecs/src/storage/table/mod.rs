@@ -355,6 +355,40 @@ impl Table {
         }
     }
 
+    /// Moves every row of this table to the end of `new_table` at once, for the columns shared
+    /// between both tables, and drops the values of the other columns of this table. Returns the
+    /// row of `new_table` that the first row of this table was moved to: each row keeps its
+    /// offset from it.
+    ///
+    /// # Safety
+    /// - The columns of `new_table` missing from this table must be initialized for the moved rows
+    ///   before they are read.
+    pub(crate) unsafe fn move_all_to_and_drop_missing_unchecked(
+        &mut self,
+        new_table: &mut Table,
+    ) -> TableRow {
+        let len = self.entity_count();
+        let first_row = TableRow::from_usize(new_table.entity_count());
+        new_table.reserve(len);
+        let mut missing = Vec::new();
+        for (component_id, column) in self.columns.iter_mut() {
+            if let Some(new_column) = new_table.get_column_mut(*component_id) {
+                new_column.initialize_all_from_unchecked(column, len, first_row);
+            } else {
+                missing.push(*component_id);
+            }
+        }
+        new_table.entities.append(&mut self.entities);
+        // The entities are moved before dropping the missing columns, so that a panicking drop
+        // can't result in a double free.
+        for component_id in missing {
+            self.get_column_mut(component_id)
+                .debug_checked_unwrap()
+                .clear(len);
+        }
+        first_row
+    }
+
     /// Get the data of the column matching `component_id` as a slice.
     ///
     /// # Safety
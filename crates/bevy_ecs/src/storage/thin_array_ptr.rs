@@ -252,6 +252,28 @@ impl<T> ThinArrayPtr<T> {
         ptr::drop_in_place(ptr::from_mut(val));
     }
 
+    /// Moves the first `count` elements of `other` to this array, starting at `index`. The moved
+    /// elements of `other` are left uninitialized, and are not dropped.
+    ///
+    /// # Safety
+    /// - The first `count` elements of `other` must be initialized.
+    /// - The elements at `index..index + count` must be within the capacity of this array, and not
+    ///   initialized.
+    #[inline]
+    pub unsafe fn move_from_unchecked(&mut self, other: &mut Self, index: usize, count: usize) {
+        #[cfg(debug_assertions)]
+        {
+            debug_assert!(index + count <= self.capacity);
+            debug_assert!(count <= other.capacity);
+        }
+        // SAFETY:
+        // - The caller ensures that both ranges are in bounds of their allocations.
+        // - The arrays are distinct allocations, so the ranges don't overlap.
+        unsafe {
+            ptr::copy_nonoverlapping(other.data.as_ptr(), self.data.as_ptr().add(index), count);
+        }
+    }
+
     /// Get a raw pointer to the last element of the array, return `None` if the length is 0
     ///
     /// # Safety
@@ -10,6 +10,7 @@ use crate::{
     entity::Entity,
     event::{Event, Events},
     observer::TriggerTargets,
+    query::QueryFilter,
     resource::Resource,
     result::{Error, Result},
     schedule::ScheduleLabel,
@@ -143,6 +144,33 @@ where
     }
 }
 
+/// A [`Command`] that adds a clone of a [`Bundle`] to every entity matching the query filter `F`.
+#[track_caller]
+pub fn insert_by_query<F, B>(bundle: B, insert_mode: InsertMode) -> impl Command
+where
+    F: QueryFilter + 'static,
+    B: Bundle<Effect: NoBundleEffect> + Clone,
+{
+    let caller = MaybeLocation::caller();
+    move |world: &mut World| {
+        world.insert_by_query_with_caller::<F, B>(bundle, insert_mode, caller);
+    }
+}
+
+/// A [`Command`] that removes the components of a [`Bundle`] from every entity matching the query
+/// filter `F`.
+#[track_caller]
+pub fn remove_by_query<F, B>() -> impl Command
+where
+    F: QueryFilter + 'static,
+    B: Bundle,
+{
+    let caller = MaybeLocation::caller();
+    move |world: &mut World| {
+        world.remove_by_query_with_caller::<F, B>(caller);
+    }
+}
+
 /// A [`Command`] that inserts a [`Resource`] into the world using a value
 /// created with the [`FromWorld`] trait.
 #[track_caller]
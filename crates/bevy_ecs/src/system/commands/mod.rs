@@ -23,6 +23,7 @@ use crate::{
     entity::{Entities, Entity, EntityClonerBuilder, EntityDoesNotExistError},
     event::Event,
     observer::{Observer, TriggerTargets},
+    query::QueryFilter,
     resource::Resource,
     result::Error,
     schedule::ScheduleLabel,
@@ -810,6 +811,62 @@ impl<'w, 's> Commands<'w, 's> {
         );
     }
 
+    /// Pushes a [`Command`] to the queue for adding a clone of a [`Bundle`] to every entity
+    /// matching the query filter `F`.
+    ///
+    /// When the command is applied, the `Bundle` overwrites any existing components it shares
+    /// with the entities. See [`World::insert_by_query`] for more details.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Enemy;
+    /// #[derive(Component, Clone)]
+    /// struct Frozen;
+    ///
+    /// fn freeze_enemies(mut commands: Commands) {
+    ///     commands.insert_by_query::<With<Enemy>, _>(Frozen);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(freeze_enemies);
+    /// ```
+    #[track_caller]
+    pub fn insert_by_query<F, B>(&mut self, bundle: B)
+    where
+        F: QueryFilter + 'static,
+        B: Bundle<Effect: NoBundleEffect> + Clone,
+    {
+        self.queue(command::insert_by_query::<F, B>(
+            bundle,
+            InsertMode::Replace,
+        ));
+    }
+
+    /// Pushes a [`Command`] to the queue for adding a clone of a [`Bundle`] to every entity
+    /// matching the query filter `F`, except for any components already present on the entities.
+    ///
+    /// See [`World::insert_by_query_if_new`] for more details.
+    #[track_caller]
+    pub fn insert_by_query_if_new<F, B>(&mut self, bundle: B)
+    where
+        F: QueryFilter + 'static,
+        B: Bundle<Effect: NoBundleEffect> + Clone,
+    {
+        self.queue(command::insert_by_query::<F, B>(bundle, InsertMode::Keep));
+    }
+
+    /// Pushes a [`Command`] to the queue for removing the components of a [`Bundle`] from every
+    /// entity matching the query filter `F`.
+    ///
+    /// See [`World::remove_by_query`] for more details.
+    #[track_caller]
+    pub fn remove_by_query<F, B>(&mut self)
+    where
+        F: QueryFilter + 'static,
+        B: Bundle,
+    {
+        self.queue(command::remove_by_query::<F, B>());
+    }
+
     /// Pushes a [`Command`] to the queue for inserting a [`Resource`] in the [`World`] with an inferred value.
     ///
     /// The inferred value is determined by the [`FromWorld`] trait of the resource.
@@ -1388,6 +1388,26 @@ impl<'w> EntityWorldMut<'w> {
         self.as_readonly().get_change_ticks_by_id(component_id)
     }
 
+    /// Overwrites the change ticks of the component with the given [`ComponentId`], even if the
+    /// component is [immutable](crate::component::Immutable).
+    ///
+    /// Returns `false` if the entity doesn't have the component.
+    ///
+    /// # Panics
+    ///
+    /// If the entity has been despawned while this `EntityWorldMut` is still alive.
+    pub(crate) fn set_change_ticks_by_id(
+        &mut self,
+        component_id: ComponentId,
+        ticks: ComponentTicks,
+    ) -> bool {
+        // SAFETY: `&mut self` gives exclusive access to the components of the entity.
+        unsafe {
+            self.as_unsafe_entity_cell()
+                .set_change_ticks_by_id(component_id, ticks)
+        }
+    }
+
     /// Returns [untyped read-only reference(s)](Ptr) to component(s) for the
     /// current entity, based on the given [`ComponentId`]s.
     ///
@@ -1873,7 +1893,7 @@ impl<'w> EntityWorldMut<'w> {
     /// when DROP is true removed components will be dropped otherwise they will be forgotten
     // We use a const generic here so that we are less reliant on
     // inlining for rustc to optimize out the `match DROP`
-    unsafe fn move_entity_from_remove<const DROP: bool>(
+    pub(crate) unsafe fn move_entity_from_remove<const DROP: bool>(
         entity: Entity,
         self_location: &mut EntityLocation,
         old_archetype_id: ArchetypeId,
@@ -2722,7 +2742,7 @@ impl<'w> EntityWorldMut<'w> {
 
 /// # Safety
 /// All components in the archetype must exist in world
-unsafe fn trigger_on_replace_and_on_remove_hooks_and_observers(
+pub(crate) unsafe fn trigger_on_replace_and_on_remove_hooks_and_observers(
     deferred_world: &mut DeferredWorld,
     archetype: &Archetype,
     entity: Entity,
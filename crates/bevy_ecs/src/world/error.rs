@@ -55,3 +55,14 @@ pub enum EntityMutableFetchError {
     #[error("The entity with ID {0} was requested mutably more than once")]
     AliasedMutability(Entity),
 }
+
+/// The error type returned by [`World::restore_snapshot`] if some entities of the snapshot could
+/// not be respawned, because their ID is used by another entity with a different generation.
+///
+/// [`World::restore_snapshot`]: crate::world::World::restore_snapshot
+#[derive(thiserror::Error, Debug, Clone)]
+#[error("Could not restore the entities with the following IDs because other entities use their index: {entities:?}")]
+pub struct RestoreSnapshotError {
+    /// The IDs of the entities that could not be restored.
+    pub entities: Vec<Entity>,
+}
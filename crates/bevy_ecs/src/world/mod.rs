@@ -8,6 +8,7 @@ mod entity_ref;
 pub mod error;
 mod filtered_resource;
 mod identifier;
+pub mod snapshot;
 mod spawn_batch;
 pub mod unsafe_world_cell;
 
@@ -33,16 +34,17 @@ pub use spawn_batch::*;
 use crate::{
     archetype::{ArchetypeId, ArchetypeRow, Archetypes},
     bundle::{
-        Bundle, BundleEffect, BundleInfo, BundleInserter, BundleSpawner, Bundles, InsertMode,
-        NoBundleEffect,
+        move_archetype_entities, Bundle, BundleEffect, BundleId, BundleInfo, BundleInserter,
+        BundleSpawner, Bundles, InsertMode, NoBundleEffect,
     },
     change_detection::{MaybeLocation, MutUntyped, TicksMut},
     component::{
         Component, ComponentDescriptor, ComponentHooks, ComponentId, ComponentInfo, ComponentTicks,
-        Components, Mutable, RequiredComponents, RequiredComponentsError, Tick,
+        Components, Mutable, RequiredComponents, RequiredComponentsError, StorageType, Tick,
     },
     entity::{
-        AllocAtWithoutReplacement, Entities, Entity, EntityDoesNotExistError, EntityLocation,
+        AllocAtWithoutReplacement, Entities, Entity, EntityDoesNotExistError,
+        EntityIndexInUseError, EntityLocation, ReserveIndexRangeError,
     },
     entity_disabling::DefaultQueryFilters,
     event::{Event, EventId, Events, SendBatchIds},
@@ -64,7 +66,7 @@ use crate::{
 use alloc::{boxed::Box, vec::Vec};
 use bevy_platform_support::sync::atomic::{AtomicU32, Ordering};
 use bevy_ptr::{OwningPtr, Ptr, UnsafeCellDeref};
-use core::{any::TypeId, fmt};
+use core::{any::TypeId, fmt, ops::Range};
 use log::warn;
 use unsafe_world_cell::{UnsafeEntityCell, UnsafeWorldCell};
 
@@ -1096,8 +1098,84 @@ impl World {
         caller: MaybeLocation,
     ) -> EntityWorldMut {
         self.flush();
-        let change_tick = self.change_tick();
         let entity = self.entities.alloc();
+        // SAFETY: entity was just allocated
+        unsafe { self.spawn_allocated_with_caller(entity, bundle, caller) }
+    }
+
+    /// Spawns a new [`Entity`] with the given ID and [`Bundle`] of [components](`Component`), and
+    /// returns a corresponding [`EntityWorldMut`].
+    ///
+    /// This is meant for entities whose IDs are allocated elsewhere, like the entities replicated
+    /// from a server. Reserve their indices with [`World::reserve_entity_index_range`] so that they
+    /// are never used by [`World::spawn`], and translate them with an
+    /// [`EntityIndexRangeMapper`](crate::entity::EntityIndexRangeMapper).
+    /// Most apps should use [`World::spawn`] instead.
+    ///
+    /// ```
+    /// use bevy_ecs::{entity::Entity, world::World};
+    ///
+    /// let mut world = World::new();
+    /// world.reserve_entity_index_range(100..200).unwrap();
+    ///
+    /// let entity = Entity::from_raw(150);
+    /// world.spawn_at(entity, ()).unwrap();
+    /// assert!(world.get_entity(entity).is_ok());
+    /// assert!(world.spawn_at(entity, ()).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EntityIndexInUseError`] if an entity is already using the index of `entity`,
+    /// whatever its generation.
+    #[track_caller]
+    pub fn spawn_at<B: Bundle>(
+        &mut self,
+        entity: Entity,
+        bundle: B,
+    ) -> Result<EntityWorldMut<'_>, EntityIndexInUseError> {
+        self.flush();
+        match self.entities.alloc_at_without_replacement(entity) {
+            AllocAtWithoutReplacement::DidNotExist => {
+                // SAFETY: entity was just allocated
+                Ok(unsafe {
+                    self.spawn_allocated_with_caller(entity, bundle, MaybeLocation::caller())
+                })
+            }
+            AllocAtWithoutReplacement::Exists(_) => Err(EntityIndexInUseError { entity }),
+            AllocAtWithoutReplacement::ExistsWithWrongGeneration => Err(EntityIndexInUseError {
+                entity: self.entities.resolve_from_id(entity.index()).unwrap(),
+            }),
+        }
+    }
+
+    /// Reserves the entity indices in `range` for entities whose IDs are allocated elsewhere, so
+    /// that they are never used by [`World::spawn`]. Spawn entities at these indices with
+    /// [`World::spawn_at`].
+    ///
+    /// See [`Entities::reserve_index_range`] for more details.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ReserveIndexRangeError`] if the range overlaps a range that was already
+    /// reserved, or if an entity is already using one of the indices.
+    pub fn reserve_entity_index_range(
+        &mut self,
+        range: Range<u32>,
+    ) -> Result<(), ReserveIndexRangeError> {
+        self.flush();
+        self.entities.reserve_index_range(range)
+    }
+
+    /// # Safety
+    /// must be called on an entity that was just allocated
+    unsafe fn spawn_allocated_with_caller<B: Bundle>(
+        &mut self,
+        entity: Entity,
+        bundle: B,
+        caller: MaybeLocation,
+    ) -> EntityWorldMut<'_> {
+        let change_tick = self.change_tick();
         let mut bundle_spawner = BundleSpawner::new::<B>(self, change_tick);
         // SAFETY: bundle's type matches `bundle_info`, entity is allocated but non-existent
         let (mut entity_location, after_effect) =
@@ -2580,6 +2658,253 @@ impl World {
         }
     }
 
+    /// Adds a clone of `bundle` to every entity matching the query filter `F`.
+    ///
+    /// The matching entities are inserted into archetype by archetype: the insertion is only set
+    /// up once per archetype, and when every entity of an archetype matches, their table rows are
+    /// moved to the new archetype all at once instead of one at a time.
+    ///
+    /// Hooks and observers run for each entity like with [`EntityWorldMut::insert`], but for the
+    /// whole archetype at a time: the `on_replace` ones run for all of its entities before they
+    /// are moved, and the `on_add` and `on_insert` ones after all of them have been moved.
+    ///
+    /// This will overwrite any previous values of components shared by the `Bundle`.
+    /// See [`World::insert_by_query_if_new`] to keep the old values instead.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Frozen;
+    ///
+    /// let mut world = World::new();
+    /// world.spawn_batch([Enemy, Enemy]);
+    /// world.insert_by_query::<With<Enemy>, _>(Frozen);
+    ///
+    /// assert_eq!(world.query::<&Frozen>().iter(&world).count(), 2);
+    /// ```
+    #[track_caller]
+    pub fn insert_by_query<F, B>(&mut self, bundle: B)
+    where
+        F: QueryFilter,
+        B: Bundle<Effect: NoBundleEffect> + Clone,
+    {
+        self.insert_by_query_with_caller::<F, B>(
+            bundle,
+            InsertMode::Replace,
+            MaybeLocation::caller(),
+        );
+    }
+
+    /// Adds a clone of `bundle` to every entity matching the query filter `F`, without
+    /// overwriting.
+    ///
+    /// This is the same as [`World::insert_by_query`], but in case of duplicate
+    /// components it will leave the old values instead of replacing them with new ones.
+    #[track_caller]
+    pub fn insert_by_query_if_new<F, B>(&mut self, bundle: B)
+    where
+        F: QueryFilter,
+        B: Bundle<Effect: NoBundleEffect> + Clone,
+    {
+        self.insert_by_query_with_caller::<F, B>(bundle, InsertMode::Keep, MaybeLocation::caller());
+    }
+
+    /// Split into a new function so we can differentiate the calling location.
+    ///
+    /// This can be called by:
+    /// - [`World::insert_by_query`]
+    /// - [`World::insert_by_query_if_new`]
+    pub(crate) fn insert_by_query_with_caller<F, B>(
+        &mut self,
+        bundle: B,
+        insert_mode: InsertMode,
+        caller: MaybeLocation,
+    ) where
+        F: QueryFilter,
+        B: Bundle<Effect: NoBundleEffect> + Clone,
+    {
+        let batches = self.query_entities_by_archetype::<F>();
+        let change_tick = self.change_tick();
+        let bundle_id = self
+            .bundles
+            .register_info::<B>(&mut self.components, &mut self.storages);
+
+        for (archetype_id, entities) in batches {
+            // SAFETY: we initialized this bundle_id in `register_info`
+            let mut inserter =
+                unsafe { BundleInserter::new_with_id(self, archetype_id, bundle_id, change_tick) };
+            // SAFETY: The entities are distinct, and still in their archetype as hooks and
+            // observers can't make structural changes until the world is flushed.
+            unsafe { inserter.insert_batch(&entities, bundle.clone(), insert_mode, caller) };
+        }
+        self.flush();
+    }
+
+    /// Removes the components of the [`Bundle`] `B` from every entity matching the query filter
+    /// `F`.
+    ///
+    /// Like [`World::insert_by_query`], the matching entities are moved archetype by archetype,
+    /// all at once when every entity of an archetype matches. The `on_replace` and `on_remove`
+    /// hooks and observers run for all the entities of an archetype before they are moved.
+    #[track_caller]
+    pub fn remove_by_query<F: QueryFilter, B: Bundle>(&mut self) {
+        self.remove_by_query_with_caller::<F, B>(MaybeLocation::caller());
+    }
+
+    pub(crate) fn remove_by_query_with_caller<F: QueryFilter, B: Bundle>(
+        &mut self,
+        caller: MaybeLocation,
+    ) {
+        let batches = self.query_entities_by_archetype::<F>();
+        let bundle_id = self
+            .bundles
+            .register_info::<B>(&mut self.components, &mut self.storages);
+
+        for (archetype_id, entities) in batches {
+            // SAFETY: We initialized this bundle_id in `register_info`. The entities are distinct,
+            // and still in their archetype as hooks and observers can't make structural changes
+            // until the world is flushed.
+            unsafe { self.remove_batch(archetype_id, &entities, bundle_id, caller) };
+        }
+        self.flush();
+    }
+
+    /// Removes the components of a bundle from each of the `entities` of an archetype, running
+    /// the `on_replace` and `on_remove` hooks and observers for all of them before moving them.
+    ///
+    /// # Safety
+    /// - A `BundleInfo` with the corresponding `BundleId` must have been initialized.
+    /// - The `entities` must be distinct and currently exist in the archetype `archetype_id`.
+    unsafe fn remove_batch(
+        &mut self,
+        archetype_id: ArchetypeId,
+        entities: &[Entity],
+        bundle_id: BundleId,
+        caller: MaybeLocation,
+    ) {
+        // SAFETY: the caller guarantees that the BundleInfo for this id has been initialized.
+        let bundle_info = unsafe { self.bundles.get_unchecked(bundle_id) };
+
+        // SAFETY: `archetype_id` exists because the entities are in it, and components in
+        // `bundle_info` must exist due to this function's safety invariants.
+        let new_archetype_id = unsafe {
+            bundle_info.remove_bundle_from_archetype(
+                &mut self.archetypes,
+                &mut self.storages,
+                &self.components,
+                &self.observers,
+                archetype_id,
+                // components from the bundle that are not present on the entities are ignored
+                true,
+            )
+        }
+        .expect("intersections should always return a result");
+
+        if new_archetype_id == archetype_id {
+            return;
+        }
+
+        // SAFETY: Archetypes and Bundles cannot be mutably aliased through DeferredWorld
+        let (archetype, bundle_info, mut deferred_world) = unsafe {
+            let bundle_info: *const BundleInfo = bundle_info;
+            let world = self.as_unsafe_world_cell();
+            (
+                &world.archetypes()[archetype_id],
+                &*bundle_info,
+                world.into_deferred(),
+            )
+        };
+        for &entity in entities {
+            // SAFETY: all bundle components exist in World
+            unsafe {
+                entity_ref::trigger_on_replace_and_on_remove_hooks_and_observers(
+                    &mut deferred_world,
+                    archetype,
+                    entity,
+                    bundle_info,
+                    caller,
+                );
+            }
+        }
+
+        let archetype = &self.archetypes[archetype_id];
+        for component_id in bundle_info.iter_explicit_components() {
+            let Some(storage_type) = archetype.get_storage_type(component_id) else {
+                continue;
+            };
+            for &entity in entities {
+                self.removed_components.send(component_id, entity);
+            }
+            // Make sure to drop components stored in sparse sets.
+            // Dense components are dropped later, when moving the table rows.
+            if storage_type == StorageType::SparseSet {
+                // Set exists because the component existed on the entities
+                let sparse_set = self.storages.sparse_sets.get_mut(component_id).unwrap();
+                for &entity in entities {
+                    sparse_set.remove(entity);
+                }
+            }
+        }
+
+        let moved_at_once = entities.len() == self.archetypes[archetype_id].len() && {
+            let (archetype, new_archetype) =
+                self.archetypes.get_2_mut(archetype_id, new_archetype_id);
+            let tables = (archetype.table_id() != new_archetype.table_id()).then(|| {
+                self.storages
+                    .tables
+                    .get_2_mut(archetype.table_id(), new_archetype.table_id())
+            });
+            // SAFETY: The tables are the ones of the archetypes, the new archetype has a subset of
+            // the components of the old one, and the sparse set components were removed above.
+            unsafe { move_archetype_entities(archetype, new_archetype, tables, &mut self.entities) }
+        };
+        if !moved_at_once {
+            for &entity in entities {
+                // SAFETY: The caller ensures that `entity` exists.
+                let location = unsafe { self.entities.get(entity).debug_checked_unwrap() };
+                let mut new_location = location;
+                // SAFETY: `new_archetype_id` is a subset of the components in `archetype_id`
+                // because it is created by removing a bundle from these components.
+                unsafe {
+                    EntityWorldMut::move_entity_from_remove::<true>(
+                        entity,
+                        &mut new_location,
+                        archetype_id,
+                        location,
+                        &mut self.entities,
+                        &mut self.archetypes,
+                        &mut self.storages,
+                        new_archetype_id,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns the entities matching the query filter `F`, grouped by archetype.
+    fn query_entities_by_archetype<F: QueryFilter>(&mut self) -> Vec<(ArchetypeId, Vec<Entity>)> {
+        self.flush();
+        let mut query = self.query_filtered::<(Entity, EntityLocation), F>();
+        let mut entities = query
+            .iter(self)
+            .map(|(entity, location)| (location.archetype_id, entity))
+            .collect::<Vec<_>>();
+        // Entities of archetypes sharing a table are interleaved in dense iteration.
+        entities.sort_by_key(|(archetype_id, _)| *archetype_id);
+        entities
+            .chunk_by(|(a, _), (b, _)| a == b)
+            .map(|batch| {
+                (
+                    batch[0].0,
+                    batch.iter().map(|(_, entity)| *entity).collect(),
+                )
+            })
+            .collect()
+    }
+
     /// Temporarily removes the requested resource from this [`World`], runs custom user code,
     /// then re-adds the resource before returning.
     ///
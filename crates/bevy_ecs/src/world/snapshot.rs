@@ -0,0 +1,572 @@
+//! Snapshots of a subset of the [`World`], that can be restored to roll it back in time.
+//!
+//! This is the building block of rollback networking and other forms of deterministic
+//! resimulation: take a [`WorldSnapshot`] every fixed tick, and when late inputs arrive, restore
+//! the snapshot of the tick they apply to and run the simulation again from there.
+//!
+//! Only the components and resources allowed by a [`SnapshotFilter`] are captured. Any entity
+//! holding at least one of the allowed components is tracked by the snapshot, so add a marker
+//! component to the filter to track entities regardless of their other components. Restoring a
+//! snapshot brings the tracked entities back exactly as they were:
+//!
+//! - tracked entities spawned since the snapshot are despawned, and the despawned ones are
+//!   respawned with the same [`Entity`] ID,
+//! - the values and [change ticks](crate::change_detection) of the allowed components and
+//!   resources are restored, inserting or removing them as needed,
+//! - entities spawned after the restoration get the same IDs as the ones spawned after the
+//!   snapshot was taken.
+//!
+//! Untracked entities and the current change tick of the world are left untouched.
+//!
+//! ```
+//! use bevy_ecs::{prelude::*, world::snapshot::SnapshotFilter};
+//!
+//! #[derive(Component, Clone)]
+//! struct Rollback;
+//!
+//! #[derive(Component, Clone, PartialEq, Debug)]
+//! struct Position(i32);
+//!
+//! #[derive(Resource, Clone)]
+//! struct FrameCount(u32);
+//!
+//! let filter = SnapshotFilter::new()
+//!     .allow_component::<Rollback>()
+//!     .allow_component::<Position>()
+//!     .allow_resource::<FrameCount>();
+//!
+//! let mut world = World::new();
+//! world.insert_resource(FrameCount(0));
+//! let player = world.spawn((Rollback, Position(0))).id();
+//!
+//! let snapshot = world.snapshot(&filter);
+//!
+//! world.get_mut::<Position>(player).unwrap().0 += 10;
+//! world.resource_mut::<FrameCount>().0 += 1;
+//! let projectile = world.spawn((Rollback, Position(5))).id();
+//!
+//! world.restore_snapshot(&snapshot).unwrap();
+//! assert_eq!(world.get::<Position>(player), Some(&Position(0)));
+//! assert_eq!(world.resource::<FrameCount>().0, 0);
+//! assert!(world.get_entity(projectile).is_err());
+//!
+//! // Resimulating spawns the same entity again.
+//! assert_eq!(world.spawn((Rollback, Position(5))).id(), projectile);
+//! ```
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::any::TypeId;
+
+use crate::{
+    archetype::ArchetypeEntity,
+    change_detection::{DetectChangesMut, MaybeLocation},
+    component::{Component, ComponentId, ComponentMutability, ComponentTicks},
+    entity::{hash_set::EntityHashSet, EntitiesAllocatorSnapshot, Entity},
+    resource::Resource,
+    world::{error::RestoreSnapshotError, World},
+};
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    crate::reflect::{AppTypeRegistry, ReflectComponent},
+    bevy_reflect::PartialReflect,
+};
+
+/// The list of components and resources captured by a [`WorldSnapshot`].
+///
+/// Components and resources are cloned with [`Clone`], or through reflection for the components
+/// allowed with [`SnapshotFilter::allow_reflect_component`].
+#[derive(Clone, Default)]
+pub struct SnapshotFilter {
+    parts: Vec<Arc<dyn SnapshotPart>>,
+}
+
+impl SnapshotFilter {
+    /// Creates a filter that doesn't allow any component or resource.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Captures the component `C`, and tracks the entities holding it.
+    pub fn allow_component<C: Component + Clone>(mut self) -> Self {
+        self.parts
+            .push(Arc::new(ComponentPart::<C>(core::marker::PhantomData)));
+        self
+    }
+
+    /// Captures the resource `R`.
+    pub fn allow_resource<R: Resource + Clone>(mut self) -> Self {
+        self.parts
+            .push(Arc::new(ResourcePart::<R>(core::marker::PhantomData)));
+        self
+    }
+
+    /// Captures the component with the given [`TypeId`] through reflection, and tracks the
+    /// entities holding it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type isn't registered with [`ReflectComponent`] in the `registry`.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn allow_reflect_component(mut self, type_id: TypeId, registry: &AppTypeRegistry) -> Self {
+        let reflect = registry
+            .read()
+            .get_type_data::<ReflectComponent>(type_id)
+            .unwrap_or_else(|| {
+                panic!("The type with ID {type_id:?} isn't registered with ReflectComponent")
+            })
+            .clone();
+        self.parts.push(Arc::new(ReflectComponentPart {
+            type_id,
+            reflect,
+            registry: registry.clone(),
+        }));
+        self
+    }
+}
+
+/// A snapshot of the components and resources of a [`World`] allowed by a [`SnapshotFilter`],
+/// taken by [`World::snapshot`] and restored by [`World::restore_snapshot`].
+pub struct WorldSnapshot {
+    entities: EntityHashSet,
+    allocator: EntitiesAllocatorSnapshot,
+    parts: Vec<Box<dyn CapturedPart>>,
+}
+
+impl WorldSnapshot {
+    /// Returns true if the `entity` is tracked by the snapshot.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    /// Returns an iterator over the entities tracked by the snapshot.
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.iter().copied()
+    }
+}
+
+impl World {
+    /// Captures the components and resources allowed by the `filter`.
+    ///
+    /// See the [module documentation](crate::world::snapshot) for more details.
+    pub fn snapshot(&mut self, filter: &SnapshotFilter) -> WorldSnapshot {
+        self.flush();
+        let parts = filter
+            .parts
+            .iter()
+            .map(|part| part.capture(self))
+            .collect::<Vec<_>>();
+        let mut entities = EntityHashSet::default();
+        for part in &parts {
+            part.add_entities(&mut entities);
+        }
+        WorldSnapshot {
+            entities,
+            allocator: self.entities.snapshot_allocator(),
+            parts,
+        }
+    }
+
+    /// Rolls the entities, components and resources captured by the `snapshot` back to their
+    /// state at the time it was taken.
+    ///
+    /// See the [module documentation](crate::world::snapshot) for more details.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RestoreSnapshotError`] if some despawned entities could not be respawned because
+    /// another entity is using their index. The rest of the snapshot is still restored.
+    #[track_caller]
+    pub fn restore_snapshot(
+        &mut self,
+        snapshot: &WorldSnapshot,
+    ) -> Result<(), RestoreSnapshotError> {
+        let caller = MaybeLocation::caller();
+        self.flush();
+
+        let mut stale = EntityHashSet::default();
+        for part in &snapshot.parts {
+            if let Some(component_id) = part.tracked_component(self) {
+                stale.extend(
+                    entities_with(self, component_id).filter(|entity| !snapshot.contains(*entity)),
+                );
+            }
+        }
+        for entity in stale {
+            if let Ok(entity) = self.get_entity_mut(entity) {
+                entity.despawn();
+            }
+        }
+
+        let missing = snapshot
+            .entities()
+            .filter(|entity| !self.entities.contains(*entity))
+            .map(|entity| (entity, ()))
+            .collect::<Vec<_>>();
+        let result = self
+            .insert_or_spawn_batch_with_caller(missing, caller)
+            .map_err(|entities| RestoreSnapshotError { entities });
+
+        for part in &snapshot.parts {
+            part.restore(self);
+        }
+        self.flush();
+        self.entities.restore_allocator(&snapshot.allocator);
+        result
+    }
+}
+
+/// Returns an iterator over the entities holding the component, including disabled ones.
+fn entities_with(world: &World, component_id: ComponentId) -> impl Iterator<Item = Entity> + '_ {
+    world
+        .archetypes()
+        .iter()
+        .filter(move |archetype| archetype.contains(component_id))
+        .flat_map(|archetype| archetype.entities().iter().map(ArchetypeEntity::id))
+}
+
+/// Removes the component from the entities holding it that aren't in `entities`.
+fn remove_stale(world: &mut World, component_id: ComponentId, entities: &EntityHashSet) {
+    let stale = entities_with(world, component_id)
+        .filter(|entity| !entities.contains(entity))
+        .collect::<Vec<_>>();
+    for entity in stale {
+        world.entity_mut(entity).remove_by_id(component_id);
+    }
+}
+
+/// A component or resource allowed by a [`SnapshotFilter`].
+trait SnapshotPart: Send + Sync + 'static {
+    fn capture(&self, world: &World) -> Box<dyn CapturedPart>;
+}
+
+/// A component or resource captured by a [`WorldSnapshot`].
+trait CapturedPart: Send + Sync + 'static {
+    /// Returns the component tracking the entities of the snapshot, if any.
+    fn tracked_component(&self, world: &World) -> Option<ComponentId>;
+
+    /// Adds the captured entities to `entities`.
+    fn add_entities(&self, entities: &mut EntityHashSet);
+
+    fn restore(&self, world: &mut World);
+}
+
+struct ComponentPart<C>(core::marker::PhantomData<fn() -> C>);
+
+impl<C: Component + Clone> SnapshotPart for ComponentPart<C> {
+    fn capture(&self, world: &World) -> Box<dyn CapturedPart> {
+        let mut captured = CapturedComponent::<C> {
+            entities: EntityHashSet::default(),
+            values: Vec::new(),
+        };
+        if let Some(component_id) = world.component_id::<C>() {
+            for entity in entities_with(world, component_id) {
+                let value = world.entity(entity).get_ref::<C>().unwrap();
+                let ticks = ComponentTicks {
+                    added: *value.ticks.added,
+                    changed: *value.ticks.changed,
+                };
+                captured.entities.insert(entity);
+                captured.values.push((entity, C::clone(&value), ticks));
+            }
+        }
+        Box::new(captured)
+    }
+}
+
+struct CapturedComponent<C> {
+    entities: EntityHashSet,
+    values: Vec<(Entity, C, ComponentTicks)>,
+}
+
+impl<C: Component + Clone> CapturedPart for CapturedComponent<C> {
+    fn tracked_component(&self, world: &World) -> Option<ComponentId> {
+        world.component_id::<C>()
+    }
+
+    fn add_entities(&self, entities: &mut EntityHashSet) {
+        entities.extend(self.entities.iter().copied());
+    }
+
+    fn restore(&self, world: &mut World) {
+        let component_id = world.register_component::<C>();
+        remove_stale(world, component_id, &self.entities);
+
+        for (entity, value, ticks) in &self.values {
+            let Ok(mut entity) = world.get_entity_mut(*entity) else {
+                continue;
+            };
+            let assigned = C::Mutability::MUTABLE
+                // SAFETY: `C` is mutable.
+                && unsafe { entity.get_mut_assume_mutable::<C>() }
+                    .map(|mut current| *current.bypass_change_detection() = value.clone())
+                    .is_some();
+            if !assigned {
+                entity.insert(value.clone());
+            }
+            entity.set_change_ticks_by_id(component_id, *ticks);
+        }
+    }
+}
+
+struct ResourcePart<R>(core::marker::PhantomData<fn() -> R>);
+
+impl<R: Resource + Clone> SnapshotPart for ResourcePart<R> {
+    fn capture(&self, world: &World) -> Box<dyn CapturedPart> {
+        let value = world.get_resource_ref::<R>().map(|value| {
+            let ticks = ComponentTicks {
+                added: *value.ticks.added,
+                changed: *value.ticks.changed,
+            };
+            (R::clone(&value), ticks)
+        });
+        Box::new(CapturedResource { value })
+    }
+}
+
+struct CapturedResource<R> {
+    value: Option<(R, ComponentTicks)>,
+}
+
+impl<R: Resource + Clone> CapturedPart for CapturedResource<R> {
+    fn tracked_component(&self, _world: &World) -> Option<ComponentId> {
+        None
+    }
+
+    fn add_entities(&self, _entities: &mut EntityHashSet) {}
+
+    fn restore(&self, world: &mut World) {
+        let Some((value, ticks)) = &self.value else {
+            world.remove_resource::<R>();
+            return;
+        };
+        match world.get_resource_mut::<R>() {
+            Some(mut current) => *current.bypass_change_detection() = value.clone(),
+            None => world.insert_resource(value.clone()),
+        }
+        let current = world.resource_mut::<R>();
+        *current.ticks.added = ticks.added;
+        *current.ticks.changed = ticks.changed;
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+struct ReflectComponentPart {
+    type_id: TypeId,
+    reflect: ReflectComponent,
+    registry: AppTypeRegistry,
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl SnapshotPart for ReflectComponentPart {
+    fn capture(&self, world: &World) -> Box<dyn CapturedPart> {
+        let mut captured = CapturedReflectComponent {
+            type_id: self.type_id,
+            reflect: self.reflect.clone(),
+            registry: self.registry.clone(),
+            entities: EntityHashSet::default(),
+            values: Vec::new(),
+        };
+        if let Some(component_id) = world.components().get_id(self.type_id) {
+            for entity in entities_with(world, component_id) {
+                let entity_ref = world.entity(entity);
+                let value = self.reflect.reflect(entity_ref).unwrap().clone_value();
+                let ticks = entity_ref.get_change_ticks_by_id(component_id).unwrap();
+                captured.entities.insert(entity);
+                captured.values.push((entity, value, ticks));
+            }
+        }
+        Box::new(captured)
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+struct CapturedReflectComponent {
+    type_id: TypeId,
+    reflect: ReflectComponent,
+    registry: AppTypeRegistry,
+    entities: EntityHashSet,
+    values: Vec<(Entity, Box<dyn PartialReflect>, ComponentTicks)>,
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl CapturedPart for CapturedReflectComponent {
+    fn tracked_component(&self, world: &World) -> Option<ComponentId> {
+        world.components().get_id(self.type_id)
+    }
+
+    fn add_entities(&self, entities: &mut EntityHashSet) {
+        entities.extend(self.entities.iter().copied());
+    }
+
+    fn restore(&self, world: &mut World) {
+        let component_id = self.reflect.register_component(world);
+        remove_stale(world, component_id, &self.entities);
+
+        let mutable = world.components().get_info(component_id).unwrap().mutable();
+        let registry = self.registry.read();
+        for (entity, value, ticks) in &self.values {
+            let Ok(mut entity) = world.get_entity_mut(*entity) else {
+                continue;
+            };
+            if mutable && entity.contains_id(component_id) {
+                self.reflect.apply(&mut entity, value.as_ref());
+            } else {
+                self.reflect.insert(&mut entity, value.as_ref(), &registry);
+            }
+            entity.set_change_ticks_by_id(component_id, *ticks);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::change_detection::DetectChanges;
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Component, Clone)]
+    struct Rollback;
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Velocity(i32);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    #[component(immutable)]
+    struct Team(u8);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Untracked(u8);
+
+    #[derive(Resource, Clone, Debug, PartialEq)]
+    struct Score(u32);
+
+    fn filter() -> SnapshotFilter {
+        SnapshotFilter::new()
+            .allow_component::<Rollback>()
+            .allow_component::<Velocity>()
+            .allow_component::<Team>()
+            .allow_resource::<Score>()
+    }
+
+    #[test]
+    fn restore_components_and_resources() {
+        let mut world = World::new();
+        world.insert_resource(Score(3));
+        let a = world.spawn((Rollback, Velocity(1), Team(0))).id();
+        let b = world.spawn((Rollback, Velocity(2), Untracked(0))).id();
+        let snapshot = world.snapshot(&filter());
+        assert!(snapshot.contains(a) && snapshot.contains(b));
+
+        world.entity_mut(a).insert((Velocity(10), Team(1)));
+        world.entity_mut(b).remove::<Velocity>().insert(Team(2));
+        world.entity_mut(b).get_mut::<Untracked>().unwrap().0 = 5;
+        world.remove_resource::<Score>();
+
+        world.restore_snapshot(&snapshot).unwrap();
+        assert_eq!(world.get::<Velocity>(a), Some(&Velocity(1)));
+        assert_eq!(world.get::<Team>(a), Some(&Team(0)));
+        assert_eq!(world.get::<Velocity>(b), Some(&Velocity(2)));
+        assert_eq!(world.get::<Team>(b), None);
+        assert_eq!(world.get::<Untracked>(b), Some(&Untracked(5)));
+        assert_eq!(world.get_resource::<Score>(), Some(&Score(3)));
+    }
+
+    #[test]
+    fn restore_entities() {
+        let mut world = World::new();
+        let a = world.spawn((Rollback, Velocity(1))).id();
+        let b = world.spawn(Rollback).id();
+        let untracked = world.spawn(Untracked(0)).id();
+        let snapshot = world.snapshot(&filter());
+
+        world.despawn(a);
+        let spawned = world.spawn(Velocity(3)).id();
+        let spawned_untracked = world.spawn(Untracked(1)).id();
+
+        world.restore_snapshot(&snapshot).unwrap();
+        assert_eq!(world.get::<Velocity>(a), Some(&Velocity(1)));
+        assert!(world.get_entity(b).is_ok());
+        assert!(world.get_entity(spawned).is_err());
+        assert!(world.get_entity(untracked).is_ok());
+        assert!(world.get_entity(spawned_untracked).is_ok());
+
+        // Resimulating gives the same IDs to the entities spawned after the snapshot.
+        world.despawn(a);
+        assert_eq!(world.spawn(Velocity(3)).id(), spawned);
+    }
+
+    #[test]
+    fn restore_change_ticks() {
+        let mut world = World::new();
+        world.insert_resource(Score(0));
+        let entity = world.spawn((Velocity(0), Team(0))).id();
+        let snapshot = world.snapshot(&filter());
+        let ticks = world.entity(entity).get_change_ticks::<Velocity>().unwrap();
+        let resource_ticks = world.get_resource_change_ticks::<Score>().unwrap();
+
+        world.increment_change_tick();
+        world.get_mut::<Velocity>(entity).unwrap().0 = 1;
+        world.entity_mut(entity).insert(Team(1));
+        world.resource_mut::<Score>().0 = 1;
+
+        world.restore_snapshot(&snapshot).unwrap();
+        let restored = [
+            world.entity(entity).get_change_ticks::<Velocity>(),
+            world.entity(entity).get_change_ticks::<Team>(),
+        ];
+        for restored in restored {
+            let restored = restored.unwrap();
+            assert_eq!(restored.added, ticks.added);
+            assert_eq!(restored.changed, ticks.changed);
+        }
+        let restored = world.resource_ref::<Score>();
+        assert_eq!(restored.last_changed(), resource_ticks.changed);
+    }
+
+    #[test]
+    fn restore_is_repeatable() {
+        let mut world = World::new();
+        let snapshot = world.snapshot(&filter());
+
+        let mut spawned = Vec::new();
+        for _ in 0..3 {
+            world.restore_snapshot(&snapshot).unwrap();
+            let entities = (0..4)
+                .map(|i| world.spawn(Velocity(i)).id())
+                .collect::<Vec<_>>();
+            world.despawn(entities[1]);
+            let entities = vec![entities, vec![world.spawn(Rollback).id()]];
+            spawned.push(entities);
+        }
+        assert_eq!(spawned[0], spawned[1]);
+        assert_eq!(spawned[1], spawned[2]);
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn restore_reflected_component() {
+        use bevy_reflect::Reflect;
+
+        #[derive(Component, Reflect, Debug, PartialEq)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Health>();
+        let filter =
+            SnapshotFilter::new().allow_reflect_component(TypeId::of::<Health>(), &registry);
+
+        let a = world.spawn(Health(10)).id();
+        let b = world.spawn(Health(20)).id();
+        let snapshot = world.snapshot(&filter);
+
+        world.get_mut::<Health>(a).unwrap().0 = 0;
+        world.despawn(b);
+
+        world.restore_snapshot(&snapshot).unwrap();
+        assert_eq!(world.get::<Health>(a), Some(&Health(10)));
+        assert_eq!(world.get::<Health>(b), Some(&Health(20)));
+    }
+}
@@ -889,6 +889,47 @@ impl<'w> UnsafeEntityCell<'w> {
         }
     }
 
+    /// Overwrites the change ticks of the component with the given [`ComponentId`], even if the
+    /// component is [immutable](crate::component::Immutable).
+    ///
+    /// Returns `false` if the entity doesn't have the component.
+    ///
+    /// # Safety
+    /// It is the callers responsibility to ensure that
+    /// - the [`UnsafeEntityCell`] has permission to access the component mutably
+    /// - no other references to the component exist at the same time
+    #[inline]
+    pub(crate) unsafe fn set_change_ticks_by_id(
+        self,
+        component_id: ComponentId,
+        ticks: ComponentTicks,
+    ) -> bool {
+        let Some(info) = self.world.components().get_info(component_id) else {
+            return false;
+        };
+        // SAFETY:
+        // - entity location and entity is valid
+        // - the storage type provided is correct for the component
+        // - the caller ensures there is no aliasing access to the component
+        let Some((_, cells, _)) = (unsafe {
+            get_component_and_ticks(
+                self.world,
+                component_id,
+                info.storage_type(),
+                self.entity,
+                self.location,
+            )
+        }) else {
+            return false;
+        };
+        // SAFETY: the caller ensures there is no aliasing access to the ticks.
+        unsafe {
+            *cells.added.deref_mut() = ticks.added;
+            *cells.changed.deref_mut() = ticks.changed;
+        }
+        true
+    }
+
     /// # Safety
     /// It is the callers responsibility to ensure that
     /// - the [`UnsafeEntityCell`] has permission to access the component mutably
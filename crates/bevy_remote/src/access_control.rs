@@ -0,0 +1,378 @@
+//! Access control for the Bevy Remote Protocol.
+//!
+//! Requests are checked against the [`RemoteAccessControl`] resource before their handler runs,
+//! so every transport is subject to the same rules. Transports forward the bearer token a client
+//! authenticated with in [`BrpMessage::token`].
+
+use core::time::Duration;
+
+use bevy_ecs::resource::Resource;
+use bevy_platform_support::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+
+use crate::{builtin_methods, BrpError, BrpMessage, BrpResult};
+
+#[cfg(feature = "bevy_asset")]
+use crate::asset_methods;
+
+/// The built-in methods that don't modify the [`World`](bevy_ecs::world::World), which are the
+/// only built-in methods allowed in [read-only mode](RemoteAccessControl::read_only).
+pub const READ_ONLY_METHODS: &[&str] = &[
+    builtin_methods::BRP_GET_METHOD,
+    builtin_methods::BRP_QUERY_METHOD,
+    builtin_methods::BRP_LIST_METHOD,
+    builtin_methods::BRP_GET_AND_WATCH_METHOD,
+    builtin_methods::BRP_LIST_AND_WATCH_METHOD,
+    builtin_methods::BRP_GET_RESOURCE_METHOD,
+    builtin_methods::BRP_LIST_RESOURCES_METHOD,
+    builtin_methods::BRP_REGISTRY_SCHEMA_METHOD,
+    builtin_methods::BRP_SCHEDULE_LIST_METHOD,
+    builtin_methods::BRP_SCHEDULE_GRAPH_METHOD,
+    builtin_methods::BRP_STEPPING_STATUS_METHOD,
+    builtin_methods::BRP_LIST_EVENTS_METHOD,
+    builtin_methods::BRP_EVENTS_AND_WATCH_METHOD,
+    #[cfg(feature = "bevy_asset")]
+    asset_methods::BRP_ASSET_LIST_METHOD,
+    #[cfg(feature = "bevy_asset")]
+    asset_methods::BRP_ASSET_GET_METHOD,
+];
+
+/// The rules that remote requests must follow to be processed.
+///
+/// By default, every request is allowed. When this resource is absent, no checks are done.
+///
+/// ```
+/// # use core::time::Duration;
+/// # use bevy_remote::{access_control::{RateLimit, RemoteAccessControl}, RemotePlugin};
+/// let plugin = RemotePlugin::default().with_access_control(
+///     RemoteAccessControl::default()
+///         .with_token("hunter2")
+///         .read_only(true)
+///         .with_rate_limit("bevy/query", RateLimit::new(10, Duration::from_secs(1))),
+/// );
+/// ```
+#[derive(Debug, Clone, Resource)]
+pub struct RemoteAccessControl {
+    /// The bearer token clients must provide, if any.
+    token: Option<String>,
+    /// The only methods that can be called, if restricted.
+    allowed_methods: Option<HashSet<String>>,
+    /// Whether only the methods in `read_only_methods` can be called.
+    read_only: bool,
+    /// The methods that don't modify the world.
+    read_only_methods: HashSet<String>,
+    /// The rate limit of each method, along with its current window.
+    rate_limits: HashMap<String, (RateLimit, Option<RateLimitWindow>)>,
+}
+
+impl Default for RemoteAccessControl {
+    fn default() -> Self {
+        Self {
+            token: None,
+            allowed_methods: None,
+            read_only: false,
+            read_only_methods: READ_ONLY_METHODS
+                .iter()
+                .map(|method| (*method).to_owned())
+                .collect(),
+            rate_limits: HashMap::default(),
+        }
+    }
+}
+
+impl RemoteAccessControl {
+    /// Require clients to authenticate with the given bearer token.
+    ///
+    /// For the HTTP transport, this is the value of the `Authorization: Bearer <token>` header.
+    #[must_use]
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Only allow calling the given method, along with any other method allowed this way.
+    ///
+    /// If this is never called, every method is allowed.
+    #[must_use]
+    pub fn allow_method(mut self, method: impl Into<String>) -> Self {
+        self.allowed_methods
+            .get_or_insert_with(HashSet::default)
+            .insert(method.into());
+        self
+    }
+
+    /// Enable or disable read-only mode.
+    ///
+    /// In read-only mode, only the methods in [`READ_ONLY_METHODS`] and those marked with
+    /// [`RemoteAccessControl::with_read_only_method`] can be called.
+    #[must_use]
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Mark a custom method as not modifying the world, so that it can be called in read-only
+    /// mode.
+    #[must_use]
+    pub fn with_read_only_method(mut self, method: impl Into<String>) -> Self {
+        self.read_only_methods.insert(method.into());
+        self
+    }
+
+    /// Limit how often the given method can be called, across all clients.
+    ///
+    /// Watching requests count once, when they are started.
+    #[must_use]
+    pub fn with_rate_limit(mut self, method: impl Into<String>, limit: RateLimit) -> Self {
+        self.rate_limits.insert(method.into(), (limit, None));
+        self
+    }
+
+    /// Returns true if read-only mode is enabled.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Checks whether the request in the given `message` is allowed, counting it towards the
+    /// rate limit of its method if so.
+    pub fn check(&mut self, message: &BrpMessage) -> BrpResult<()> {
+        self.check_at(message, Instant::now())
+    }
+
+    /// Checks whether a client that authenticated with the given bearer `token` is allowed to
+    /// make requests.
+    pub fn authenticate(&self, token: Option<&str>) -> BrpResult<()> {
+        authenticate(self.token.as_deref(), token)
+    }
+
+    /// Checks whether the given `methods` can all be called by an authenticated client, as the
+    /// calls of a `bevy/batch` request. The calls only count towards the rate limits of their
+    /// methods if every one of them is allowed.
+    ///
+    /// On failure, returns the index of the first call that was denied along with its error.
+    pub fn check_methods<'a>(
+        &mut self,
+        methods: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), (usize, BrpError)> {
+        self.check_methods_at(methods, Instant::now())
+    }
+
+    fn check_at(&mut self, message: &BrpMessage, now: Instant) -> BrpResult<()> {
+        self.authenticate(message.token.as_deref())?;
+        self.check_method_at(&message.method, now)
+    }
+
+    fn check_method_at(&mut self, method: &str, now: Instant) -> BrpResult<()> {
+        self.authorize(method)?;
+        if let Some((limit, window)) = self.current_window(method, now) {
+            if window.requests >= limit.requests {
+                return Err(BrpError::rate_limited(method));
+            }
+            window.requests += 1;
+        }
+        Ok(())
+    }
+
+    fn check_methods_at<'a>(
+        &mut self,
+        methods: impl IntoIterator<Item = &'a str>,
+        now: Instant,
+    ) -> Result<(), (usize, BrpError)> {
+        let methods = methods.into_iter().collect::<Vec<_>>();
+        for (index, &method) in methods.iter().enumerate() {
+            self.authorize(method).map_err(|err| (index, err))?;
+        }
+
+        // The rate limits are only charged once every call is known to fit within them.
+        let mut calls = HashMap::<&str, u32>::default();
+        for (index, &method) in methods.iter().enumerate() {
+            let count = calls.entry(method).or_default();
+            *count += 1;
+            if let Some((limit, window)) = self.current_window(method, now) {
+                if window.requests.saturating_add(*count) > limit.requests {
+                    return Err((index, BrpError::rate_limited(method)));
+                }
+            }
+        }
+        for (method, count) in calls {
+            if let Some((_, window)) = self.current_window(method, now) {
+                window.requests += count;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks whether the given `method` can be called at all, regardless of its rate limit.
+    fn authorize(&self, method: &str) -> BrpResult<()> {
+        if self
+            .allowed_methods
+            .as_ref()
+            .is_some_and(|allowed| !allowed.contains(method))
+        {
+            return Err(BrpError::method_not_allowed(method));
+        }
+        if self.read_only && !self.read_only_methods.contains(method) {
+            return Err(BrpError::method_not_allowed(method));
+        }
+        Ok(())
+    }
+
+    /// Returns the rate limit of the given `method`, if any, along with its window at `now`.
+    fn current_window(
+        &mut self,
+        method: &str,
+        now: Instant,
+    ) -> Option<(RateLimit, &mut RateLimitWindow)> {
+        let (limit, window) = self.rate_limits.get_mut(method)?;
+        if window
+            .as_ref()
+            .is_some_and(|window| now.saturating_duration_since(window.start) >= limit.period)
+        {
+            *window = None;
+        }
+        let window = window.get_or_insert(RateLimitWindow {
+            start: now,
+            requests: 0,
+        });
+        Some((*limit, window))
+    }
+
+    /// Returns the bearer token clients must provide, if any.
+    pub(crate) fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+}
+
+/// The maximum number of times a method can be called within a period of time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// The number of requests allowed per period.
+    pub requests: u32,
+    /// The length of the period.
+    pub period: Duration,
+}
+
+impl RateLimit {
+    /// Allow `requests` calls every `period`.
+    pub const fn new(requests: u32, period: Duration) -> Self {
+        Self { requests, period }
+    }
+}
+
+/// The requests made to a rate-limited method in the current period.
+#[derive(Debug, Clone, Copy)]
+struct RateLimitWindow {
+    start: Instant,
+    requests: u32,
+}
+
+/// Checks the bearer `token` a client provided against the `expected` one, if any.
+pub(crate) fn authenticate(expected: Option<&str>, token: Option<&str>) -> BrpResult<()> {
+    let Some(expected) = expected else {
+        return Ok(());
+    };
+    let authenticated =
+        token.is_some_and(|provided| constant_time_eq(provided.as_bytes(), expected.as_bytes()));
+    if authenticated {
+        Ok(())
+    } else {
+        Err(BrpError::unauthorized())
+    }
+}
+
+/// Compares two byte strings in time independent of where they differ, so that tokens can't be
+/// guessed by timing the responses.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_codes;
+
+    fn message(method: &str, token: Option<&str>) -> BrpMessage {
+        BrpMessage::new(method, None, async_channel::bounded(1).0)
+            .with_token(token.map(str::to_owned))
+    }
+
+    #[test]
+    fn tokens_and_methods() {
+        let mut access = RemoteAccessControl::default()
+            .with_token("secret")
+            .read_only(true)
+            .with_read_only_method("custom/read");
+
+        let error = access.check(&message(builtin_methods::BRP_GET_METHOD, None));
+        assert_eq!(error.unwrap_err().code, error_codes::UNAUTHORIZED);
+        let error = access.check(&message(builtin_methods::BRP_GET_METHOD, Some("secreT")));
+        assert_eq!(error.unwrap_err().code, error_codes::UNAUTHORIZED);
+
+        assert!(access
+            .check(&message(builtin_methods::BRP_GET_METHOD, Some("secret")))
+            .is_ok());
+        assert!(access
+            .check(&message("custom/read", Some("secret")))
+            .is_ok());
+        let error = access.check(&message(
+            builtin_methods::BRP_DESTROY_METHOD,
+            Some("secret"),
+        ));
+        assert_eq!(error.unwrap_err().code, error_codes::METHOD_NOT_ALLOWED);
+
+        let mut access = RemoteAccessControl::default().allow_method("bevy/list");
+        assert!(access.check(&message("bevy/list", None)).is_ok());
+        let error = access.check(&message("bevy/get", None));
+        assert_eq!(error.unwrap_err().code, error_codes::METHOD_NOT_ALLOWED);
+    }
+
+    #[test]
+    fn rate_limits() {
+        let mut access = RemoteAccessControl::default()
+            .with_rate_limit("bevy/query", RateLimit::new(2, Duration::from_secs(1)));
+        let query = message("bevy/query", None);
+        let start = Instant::now();
+
+        assert!(access.check_at(&query, start).is_ok());
+        assert!(access.check_at(&query, start).is_ok());
+        let error = access.check_at(&query, start + Duration::from_millis(500));
+        assert_eq!(error.unwrap_err().code, error_codes::RATE_LIMITED);
+        assert!(access.check_at(&message("bevy/get", None), start).is_ok());
+
+        assert!(access
+            .check_at(&query, start + Duration::from_secs(1))
+            .is_ok());
+    }
+
+    #[test]
+    fn batches_are_charged_only_when_allowed() {
+        let mut access = RemoteAccessControl::default()
+            .read_only(true)
+            .with_rate_limit("bevy/query", RateLimit::new(2, Duration::from_secs(1)))
+            .with_rate_limit("bevy/get", RateLimit::new(2, Duration::from_secs(1)));
+        let start = Instant::now();
+
+        // Neither a denied method nor going over a rate limit charges the other calls.
+        let (index, error) = access
+            .check_methods_at(["bevy/query", "bevy/insert"], start)
+            .unwrap_err();
+        assert_eq!((index, error.code), (1, error_codes::METHOD_NOT_ALLOWED));
+        let (index, error) = access
+            .check_methods_at(
+                ["bevy/get", "bevy/query", "bevy/query", "bevy/query"],
+                start,
+            )
+            .unwrap_err();
+        assert_eq!((index, error.code), (3, error_codes::RATE_LIMITED));
+
+        assert!(access
+            .check_methods_at(["bevy/get", "bevy/query", "bevy/query"], start)
+            .is_ok());
+        let query = message("bevy/query", None);
+        let error = access.check_at(&query, start);
+        assert_eq!(error.unwrap_err().code, error_codes::RATE_LIMITED);
+        assert!(access.check_at(&message("bevy/get", None), start).is_ok());
+    }
+}
@@ -0,0 +1,444 @@
+//! Built-in verbs for inspecting and editing assets through the Bevy Remote Protocol.
+//!
+//! These methods operate on every asset type registered with
+//! [`register_asset_reflect`](bevy_asset::AssetApp::register_asset_reflect), which provides the
+//! [`ReflectAsset`] type data they rely on.
+
+use core::any::TypeId;
+
+use anyhow::{anyhow, Result as AnyhowResult};
+use bevy_asset::{
+    uuid::Uuid, AssetIndex, AssetServer, LoadState, ReflectAsset, UntypedAssetId, UntypedHandle,
+};
+use bevy_ecs::{reflect::AppTypeRegistry, system::In, world::World};
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    GetPath, PartialReflect, TypeRegistration, TypeRegistry,
+};
+use serde::{de::DeserializeSeed as _, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    builtin_methods::{parse, parse_some},
+    BrpError, BrpResult,
+};
+
+/// The method path for a `bevy/asset/list` request.
+pub const BRP_ASSET_LIST_METHOD: &str = "bevy/asset/list";
+
+/// The method path for a `bevy/asset/get` request.
+pub const BRP_ASSET_GET_METHOD: &str = "bevy/asset/get";
+
+/// The method path for a `bevy/asset/mutate` request.
+pub const BRP_ASSET_MUTATE_METHOD: &str = "bevy/asset/mutate";
+
+/// The method path for a `bevy/asset/reload` request.
+pub const BRP_ASSET_RELOAD_METHOD: &str = "bevy/asset/reload";
+
+/// `bevy/asset/list`: Lists the assets currently stored in the world.
+///
+/// The server responds with a [`BrpAssetListResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct BrpAssetListParams {
+    /// The [full path] of the asset type to list.
+    ///
+    /// If this is absent, the assets of every reflectable asset type are listed.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+}
+
+/// `bevy/asset/get`: Retrieves the value of a single asset.
+///
+/// The server responds with a [`BrpAssetGetResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetGetParams {
+    /// The [full path] of the asset type.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub asset: String,
+
+    /// The asset to retrieve.
+    pub id: BrpAssetId,
+}
+
+/// `bevy/asset/mutate`: Changes a single field of an asset.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetMutateParams {
+    /// The [full path] of the asset type.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub asset: String,
+
+    /// The asset to mutate.
+    pub id: BrpAssetId,
+
+    /// The [path] of the field within the asset.
+    ///
+    /// [path]: bevy_reflect::GetPath
+    pub path: String,
+
+    /// The value to insert at `path`.
+    pub value: Value,
+}
+
+/// `bevy/asset/reload`: Reloads every asset loaded from the given path.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetReloadParams {
+    /// The asset path to reload, e.g. `materials/floor.ron`.
+    pub path: String,
+}
+
+/// Identifies a single asset of a known type.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpAssetId {
+    /// The bits of an [`AssetIndex`], as reported by `bevy/asset/list`.
+    Index(u64),
+    /// The UUID the asset was explicitly registered with.
+    Uuid(Uuid),
+    /// The path the asset was loaded from.
+    Path(String),
+}
+
+impl From<UntypedAssetId> for BrpAssetId {
+    fn from(id: UntypedAssetId) -> Self {
+        match id {
+            UntypedAssetId::Index { index, .. } => Self::Index(index.to_bits()),
+            UntypedAssetId::Uuid { uuid, .. } => Self::Uuid(uuid),
+        }
+    }
+}
+
+/// The response to a `bevy/asset/list` request.
+pub type BrpAssetListResponse = Vec<BrpAssetInfo>;
+
+/// A single asset in a [`BrpAssetListResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetInfo {
+    /// The [full path] of the asset type.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub asset: String,
+
+    /// The ID of the asset.
+    pub id: BrpAssetId,
+
+    /// The path the asset was loaded from, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// The load state of the asset, if it is tracked by the [`AssetServer`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_state: Option<BrpAssetLoadState>,
+
+    /// The error that caused the asset to fail loading, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The serialized form of a [`LoadState`].
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpAssetLoadState {
+    /// See [`LoadState::NotLoaded`].
+    NotLoaded,
+    /// See [`LoadState::Loading`].
+    Loading,
+    /// See [`LoadState::Loaded`].
+    Loaded,
+    /// See [`LoadState::Failed`].
+    Failed,
+}
+
+impl From<&LoadState> for BrpAssetLoadState {
+    fn from(state: &LoadState) -> Self {
+        match state {
+            LoadState::NotLoaded => Self::NotLoaded,
+            LoadState::Loading => Self::Loading,
+            LoadState::Loaded => Self::Loaded,
+            LoadState::Failed(_) => Self::Failed,
+        }
+    }
+}
+
+/// The response to a `bevy/asset/get` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetGetResponse {
+    /// The value of the requested asset.
+    pub value: Value,
+}
+
+/// Handles a `bevy/asset/list` request coming from a client.
+pub fn process_remote_asset_list_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let BrpAssetListParams { asset } = params.map(parse).transpose()?.unwrap_or_default();
+
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+    let asset_server = world.get_resource::<AssetServer>();
+
+    let registrations: Vec<(&TypeRegistration, &ReflectAsset)> = match &asset {
+        Some(asset_path) => {
+            vec![get_reflect_asset(&type_registry, asset_path).map_err(BrpError::asset_error)?]
+        }
+        None => type_registry
+            .iter()
+            .filter_map(|registration| Some((registration, registration.data::<ReflectAsset>()?)))
+            .collect(),
+    };
+
+    let mut response = BrpAssetListResponse::default();
+    for (registration, reflect_asset) in registrations {
+        let asset_path = registration.type_info().type_path();
+        let mut ids = reflect_asset.ids(world).collect::<Vec<_>>();
+        ids.sort();
+
+        for id in ids {
+            let load_state = asset_server.and_then(|server| server.get_load_state(id));
+            response.push(BrpAssetInfo {
+                asset: asset_path.to_owned(),
+                id: id.into(),
+                path: asset_server
+                    .and_then(|server| server.get_path(id))
+                    .map(|path| path.to_string()),
+                load_state: load_state.as_ref().map(BrpAssetLoadState::from),
+                error: match load_state {
+                    Some(LoadState::Failed(error)) => Some(error.to_string()),
+                    _ => None,
+                },
+            });
+        }
+    }
+
+    response.sort_by(|a, b| a.asset.cmp(&b.asset));
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `bevy/asset/get` request coming from a client.
+pub fn process_remote_asset_get_request(In(params): In<Option<Value>>, world: &World) -> BrpResult {
+    let BrpAssetGetParams {
+        asset: asset_path,
+        id,
+    } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+    let (registration, reflect_asset) =
+        get_reflect_asset(&type_registry, &asset_path).map_err(BrpError::asset_error)?;
+    let handle = resolve_asset_handle(world, registration.type_id(), &asset_path, &id)?;
+
+    let reflected = reflect_asset
+        .get(world, handle)
+        .ok_or_else(|| BrpError::asset_not_found(&asset_path))?;
+    let serializer = TypedReflectSerializer::new(reflected.as_partial_reflect(), &type_registry);
+    let value = serde_json::to_value(serializer).map_err(BrpError::asset_error)?;
+
+    serde_json::to_value(BrpAssetGetResponse { value }).map_err(BrpError::internal)
+}
+
+/// Handles a `bevy/asset/mutate` request coming from a client.
+///
+/// The asset is accessed mutably, so an [`AssetEvent::Modified`](bevy_asset::AssetEvent::Modified)
+/// is sent for it and systems that depend on it, such as rendering, pick up the change.
+pub fn process_remote_asset_mutate_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpAssetMutateParams {
+        asset: asset_path,
+        id,
+        path: field_path,
+        value,
+    } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+    let (registration, reflect_asset) =
+        get_reflect_asset(&type_registry, &asset_path).map_err(BrpError::asset_error)?;
+    let handle = resolve_asset_handle(world, registration.type_id(), &asset_path, &id)?;
+
+    let reflected_asset = reflect_asset
+        .get_mut(world, handle)
+        .ok_or_else(|| BrpError::asset_not_found(&asset_path))?;
+
+    // Get the type registration for the field with the given path.
+    let field_type_path = reflected_asset
+        .reflect_path(field_path.as_str())
+        .map_err(BrpError::asset_error)?
+        .reflect_type_path();
+    let value_registration = type_registry
+        .get_with_type_path(field_type_path)
+        .ok_or_else(|| {
+            BrpError::asset_error(anyhow!("Unknown asset field type: `{}`", field_type_path))
+        })?;
+
+    // Use the field's type registration to deserialize the given value.
+    let deserialized_value: Box<dyn PartialReflect> =
+        TypedReflectDeserializer::new(value_registration, &type_registry)
+            .deserialize(&value)
+            .map_err(BrpError::asset_error)?;
+
+    // Apply the value to the asset.
+    reflected_asset
+        .reflect_path_mut(field_path.as_str())
+        .map_err(BrpError::asset_error)?
+        .try_apply(&*deserialized_value)
+        .map_err(BrpError::asset_error)?;
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/asset/reload` request coming from a client.
+///
+/// The reload happens in the background; use `bevy/asset/list` to follow its load state.
+pub fn process_remote_asset_reload_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let BrpAssetReloadParams { path } = parse_some(params)?;
+
+    let asset_server = get_asset_server(world)?;
+    if asset_server.get_path_ids(path.as_str()).is_empty() {
+        return Err(BrpError::asset_not_found(&path));
+    }
+    asset_server.reload(path);
+
+    Ok(Value::Null)
+}
+
+/// Returns the [`AssetServer`], or an error if the `AssetPlugin` hasn't been added.
+fn get_asset_server(world: &World) -> Result<&AssetServer, BrpError> {
+    world
+        .get_resource::<AssetServer>()
+        .ok_or_else(|| BrpError::resource_not_present("bevy_asset::server::AssetServer"))
+}
+
+/// Converts a [`BrpAssetId`] into a weak handle to an asset of the type with the given
+/// [`TypeId`].
+fn resolve_asset_handle(
+    world: &World,
+    type_id: TypeId,
+    asset_path: &str,
+    id: &BrpAssetId,
+) -> Result<UntypedHandle, BrpError> {
+    let id = match id {
+        BrpAssetId::Index(bits) => UntypedAssetId::Index {
+            type_id,
+            index: AssetIndex::from_bits(*bits),
+        },
+        BrpAssetId::Uuid(uuid) => UntypedAssetId::Uuid {
+            type_id,
+            uuid: *uuid,
+        },
+        BrpAssetId::Path(path) => get_asset_server(world)?
+            .get_path_ids(path.as_str())
+            .into_iter()
+            .find(|id| id.type_id() == type_id)
+            .ok_or_else(|| BrpError::asset_not_found(asset_path))?,
+    };
+
+    Ok(UntypedHandle::Weak(id))
+}
+
+/// Given an asset's type path, return the associated [`TypeRegistration`] and
+/// [`ReflectAsset`] from the given `type_registry` if possible.
+fn get_reflect_asset<'r>(
+    type_registry: &'r TypeRegistry,
+    asset_path: &str,
+) -> AnyhowResult<(&'r TypeRegistration, &'r ReflectAsset)> {
+    let registration = type_registry
+        .get_with_type_path(asset_path)
+        .ok_or_else(|| anyhow!("Unknown asset type: `{}`", asset_path))?;
+    let reflect_asset = registration
+        .data::<ReflectAsset>()
+        .ok_or_else(|| anyhow!("Asset `{}` isn't reflectable", asset_path))?;
+
+    Ok((registration, reflect_asset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::{Asset, Assets};
+    use bevy_reflect::{Reflect, TypePath};
+    use serde_json::json;
+
+    #[derive(Asset, Reflect, Default, Debug, PartialEq)]
+    struct Material {
+        roughness: f32,
+    }
+
+    #[test]
+    fn list_get_and_mutate_assets() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world.init_resource::<Assets<Material>>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Material>();
+            registry.register::<f32>();
+            registry.register_type_data::<Material, ReflectAsset>();
+        }
+        let id = world
+            .resource_mut::<Assets<Material>>()
+            .add(Material { roughness: 0.5 })
+            .id();
+
+        let list = world.register_system(process_remote_asset_list_request);
+        let response = world.run_system_with(list, None).unwrap().unwrap();
+        let listed: BrpAssetListResponse = serde_json::from_value(response).unwrap();
+        assert_eq!(
+            listed,
+            [BrpAssetInfo {
+                asset: Material::type_path().to_owned(),
+                id: UntypedAssetId::from(id).into(),
+                path: None,
+                load_state: None,
+                error: None,
+            }]
+        );
+
+        let id = serde_json::to_value(&listed[0].id).unwrap();
+        let mutate = world.register_system(process_remote_asset_mutate_request);
+        world
+            .run_system_with(
+                mutate,
+                Some(json!({
+                    "asset": Material::type_path(),
+                    "id": id,
+                    "path": ".roughness",
+                    "value": 0.25,
+                })),
+            )
+            .unwrap()
+            .expect("mutate should succeed");
+
+        let get = world.register_system(process_remote_asset_get_request);
+        let response = world
+            .run_system_with(
+                get,
+                Some(json!({ "asset": Material::type_path(), "id": id })),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(response, json!({ "value": { "roughness": 0.25 } }));
+
+        let error = world
+            .run_system_with(
+                get,
+                Some(json!({ "asset": Material::type_path(), "id": { "index": 99 } })),
+            )
+            .unwrap()
+            .expect_err("getting a missing asset should fail");
+        assert_eq!(error.code, crate::error_codes::ASSET_NOT_FOUND);
+    }
+}
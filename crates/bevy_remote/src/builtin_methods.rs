@@ -1,30 +1,39 @@
 //! Built-in verbs for the Bevy Remote Protocol.
 
-use core::any::TypeId;
+use core::{any::TypeId, cmp::Ordering};
 
 use anyhow::{anyhow, Result as AnyhowResult};
 use bevy_ecs::{
-    component::ComponentId,
+    change_detection::DetectChangesMut,
+    component::{ComponentId, ComponentInfo, ComponentTicks, Tick},
     entity::Entity,
     event::EventCursor,
     hierarchy::ChildOf,
     query::QueryBuilder,
-    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectEvent, ReflectResource},
     removal_detection::RemovedComponentEntity,
+    schedule::{
+        graph::{ExportedNode, ScheduleGraphExport},
+        InternedScheduleLabel, NodeId, Schedule, Schedules, Stepping,
+    },
     system::{In, Local},
-    world::{EntityRef, EntityWorldMut, FilteredEntityRef, World},
+    world::{EntityRef, EntityWorldMut, FilteredEntityRef, Mut, World},
 };
-use bevy_platform_support::collections::HashMap;
+use bevy_platform_support::collections::{HashMap, HashSet};
 use bevy_reflect::{
-    prelude::ReflectDefault,
-    serde::{ReflectSerializer, TypedReflectDeserializer},
-    GetPath, NamedField, OpaqueInfo, PartialReflect, ReflectDeserialize, ReflectSerialize,
-    TypeInfo, TypeRegistration, TypeRegistry, VariantInfo,
+    serde::{ReflectSerializer, TypedReflectDeserializer, TypedReflectSerializer},
+    GetPath, PartialReflect, Reflect, TypeRegistration, TypeRegistry,
 };
 use serde::{de::DeserializeSeed as _, Deserialize, Serialize};
-use serde_json::{json, Map, Value};
-
-use crate::{error_codes, BrpError, BrpResult};
+use serde_json::{json, Map, Number, Value};
+
+use crate::{
+    access_control::RemoteAccessControl,
+    error_codes,
+    schemas::json_schema::{export_registry_schemas, JsonSchemaBevyType},
+    BrpError, BrpResult, CurrentRemoteWatch, RemoteMethodSystemId, RemoteMethods, RemoteWatchId,
+    RemoteWatchingRequests,
+};
 
 /// The method path for a `bevy/get` request.
 pub const BRP_GET_METHOD: &str = "bevy/get";
@@ -77,6 +86,48 @@ pub const BRP_LIST_RESOURCES_METHOD: &str = "bevy/list_resources";
 /// The method path for a `bevy/registry/schema` request.
 pub const BRP_REGISTRY_SCHEMA_METHOD: &str = "bevy/registry/schema";
 
+/// The method path for a `bevy/batch` request.
+pub const BRP_BATCH_METHOD: &str = "bevy/batch";
+
+/// The method path for a `bevy/schedule/list` request.
+pub const BRP_SCHEDULE_LIST_METHOD: &str = "bevy/schedule/list";
+
+/// The method path for a `bevy/schedule/graph` request.
+pub const BRP_SCHEDULE_GRAPH_METHOD: &str = "bevy/schedule/graph";
+
+/// The method path for a `bevy/stepping/enable` request.
+pub const BRP_STEPPING_ENABLE_METHOD: &str = "bevy/stepping/enable";
+
+/// The method path for a `bevy/stepping/disable` request.
+pub const BRP_STEPPING_DISABLE_METHOD: &str = "bevy/stepping/disable";
+
+/// The method path for a `bevy/stepping/step_system` request.
+pub const BRP_STEPPING_STEP_SYSTEM_METHOD: &str = "bevy/stepping/step_system";
+
+/// The method path for a `bevy/stepping/step_frame` request.
+pub const BRP_STEPPING_STEP_FRAME_METHOD: &str = "bevy/stepping/step_frame";
+
+/// The method path for a `bevy/stepping/set_breakpoint` request.
+pub const BRP_STEPPING_SET_BREAKPOINT_METHOD: &str = "bevy/stepping/set_breakpoint";
+
+/// The method path for a `bevy/stepping/clear_breakpoint` request.
+pub const BRP_STEPPING_CLEAR_BREAKPOINT_METHOD: &str = "bevy/stepping/clear_breakpoint";
+
+/// The method path for a `bevy/stepping/status` request.
+pub const BRP_STEPPING_STATUS_METHOD: &str = "bevy/stepping/status";
+
+/// The method path for a `bevy/send_event` request.
+pub const BRP_SEND_EVENT_METHOD: &str = "bevy/send_event";
+
+/// The method path for a `bevy/trigger_event` request.
+pub const BRP_TRIGGER_EVENT_METHOD: &str = "bevy/trigger_event";
+
+/// The method path for a `bevy/list_events` request.
+pub const BRP_LIST_EVENTS_METHOD: &str = "bevy/list_events";
+
+/// The method path for a `bevy/events+watch` request.
+pub const BRP_EVENTS_AND_WATCH_METHOD: &str = "bevy/events+watch";
+
 /// `bevy/get`: Retrieves one or more components from the entity with the given
 /// ID.
 ///
@@ -287,6 +338,112 @@ pub struct BrpMutateResourceParams {
     pub value: Value,
 }
 
+/// `bevy/batch`: Runs an ordered list of calls against the world, committing
+/// their effects only if every call succeeds.
+///
+/// The server responds with a [`BrpBatchResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpBatchParams {
+    /// The calls to run, in order.
+    ///
+    /// Any object of the form `{ "batch_entity": N }` found in the params of a
+    /// call is replaced by the entity spawned by the `N`-th call of the batch.
+    pub requests: Vec<BrpBatchRequest>,
+}
+
+/// A single call inside of a `bevy/batch` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpBatchRequest {
+    /// The method of the call, e.g. `bevy/spawn`.
+    pub method: String,
+
+    /// The parameters of the call, specific to each method.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+/// `bevy/schedule/graph`: Returns the systems, system sets, ordering graphs and ambiguities of a
+/// schedule.
+///
+/// The server responds with a [`BrpScheduleGraphResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpScheduleGraphParams {
+    /// The label of the schedule, as printed by its [`Debug`] implementation, e.g. `Update`.
+    pub schedule: String,
+}
+
+/// `bevy/stepping/enable`: Enables system stepping, optionally adding schedules to it.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BrpSteppingEnableParams {
+    /// The labels of the schedules to step through, as printed by their [`Debug`]
+    /// implementation, e.g. `Update`.
+    #[serde(default)]
+    pub schedules: Vec<String>,
+}
+
+/// `bevy/stepping/set_breakpoint` and `bevy/stepping/clear_breakpoint`: Sets or clears a
+/// breakpoint on a system.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingBreakpointParams {
+    /// The label of the schedule containing the system, as printed by its [`Debug`]
+    /// implementation, e.g. `Update`.
+    pub schedule: String,
+
+    /// The index of the system in the schedule, as reported by `bevy/schedule/graph`.
+    pub system: usize,
+}
+
+/// `bevy/send_event`: Writes an event into its `Events` resource, so that it is
+/// seen by every `EventReader` of that type.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSendEventParams {
+    /// The [full path] of the event type to send.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub event: String,
+
+    /// The serialized value of the event to be sent.
+    pub value: Value,
+}
+
+/// `bevy/trigger_event`: Triggers an event, running every observer watching for it.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpTriggerEventParams {
+    /// The [full path] of the event type to trigger.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub event: String,
+
+    /// The serialized value of the event to be triggered.
+    pub value: Value,
+
+    /// The entities to trigger the event for.
+    ///
+    /// If this is empty, the event is triggered without targets.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<Entity>,
+}
+
+/// `bevy/events+watch`: Streams every event of the given type as it is sent.
+///
+/// The server responds with a [`BrpEventsWatchingResponse`] every frame in which at least
+/// one event of that type was sent.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpEventsParams {
+    /// The [full path] of the event type to watch.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub event: String,
+}
+
 /// Describes the data that is to be fetched in a query.
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct BrpQuery {
@@ -329,6 +486,123 @@ pub struct BrpQueryFilter {
     /// [full path]: bevy_reflect::TypePath::type_path
     #[serde(default)]
     pub with: Vec<String>,
+
+    /// The [full path] of the type name of each component that must have been
+    /// added or changed since `since_tick` for the entity to be included in the results.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed: Vec<String>,
+
+    /// The [full path] of the type name of each component that must have been
+    /// added since `since_tick` for the entity to be included in the results.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<String>,
+
+    /// The change tick that `changed` and `added` are compared against.
+    ///
+    /// Defaults to the last time the `bevy/query` handler ran.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since_tick: Option<u32>,
+
+    /// Predicates on reflected component values that must all hold for the entity
+    /// to be included in the results.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub predicates: Vec<BrpQueryPredicate>,
+}
+
+/// A predicate on the value of a field of a component, evaluated server-side.
+///
+/// The field is serialized to JSON before being compared with the operand, so the operand
+/// uses the same format as the values returned by `bevy/get`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpQueryPredicate {
+    /// The [full path] of the component whose value is tested.
+    ///
+    /// Entities without this component never match.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub component: String,
+
+    /// The [path] of the field within the component. If empty, the whole component is tested.
+    ///
+    /// Entities on which the path doesn't resolve, e.g. because their component is an enum of
+    /// another variant, never match.
+    ///
+    /// [path]: bevy_reflect::GetPath
+    #[serde(default)]
+    pub path: String,
+
+    /// The comparison to perform.
+    #[serde(flatten)]
+    pub operation: BrpQueryPredicateOperation,
+}
+
+/// The comparison performed by a [`BrpQueryPredicate`], along with its operand.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpQueryPredicateOperation {
+    /// The value is equal to the operand. Numbers are compared by value.
+    Eq(Value),
+    /// The value is not equal to the operand. Numbers are compared by value.
+    Ne(Value),
+    /// The value is a number or string less than the operand.
+    Lt(Value),
+    /// The value is a number or string less than or equal to the operand.
+    Lte(Value),
+    /// The value is a number or string greater than the operand.
+    Gt(Value),
+    /// The value is a number or string greater than or equal to the operand.
+    Gte(Value),
+    /// The value is an array containing the operand, a string containing the operand as a
+    /// substring, or an object containing the operand as a key.
+    Contains(Value),
+}
+
+impl BrpQueryPredicateOperation {
+    /// Returns true if the serialized `value` satisfies this operation.
+    pub fn matches(&self, value: &Value) -> bool {
+        match self {
+            Self::Eq(operand) => {
+                compare_json(value, operand).map_or(value == operand, |ordering| ordering.is_eq())
+            }
+            Self::Ne(operand) => !Self::Eq(operand.clone()).matches(value),
+            Self::Lt(operand) => compare_json(value, operand).is_some_and(Ordering::is_lt),
+            Self::Lte(operand) => compare_json(value, operand).is_some_and(Ordering::is_le),
+            Self::Gt(operand) => compare_json(value, operand).is_some_and(Ordering::is_gt),
+            Self::Gte(operand) => compare_json(value, operand).is_some_and(Ordering::is_ge),
+            Self::Contains(operand) => match value {
+                Value::Array(items) => items
+                    .iter()
+                    .any(|item| Self::Eq(operand.clone()).matches(item)),
+                Value::String(string) => operand.as_str().is_some_and(|sub| string.contains(sub)),
+                Value::Object(map) => operand.as_str().is_some_and(|key| map.contains_key(key)),
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Orders two JSON values if they are both numbers or both strings.
+///
+/// Integers are compared exactly, and only fall back to floating point when compared with a
+/// non-integer.
+fn compare_json(a: &Value, b: &Value) -> Option<Ordering> {
+    let integer = |n: &Number| {
+        n.as_i64()
+            .map(i128::from)
+            .or_else(|| n.as_u64().map(i128::from))
+    };
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => match (integer(a), integer(b)) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        },
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
 }
 
 /// Constraints that can be placed on a query to include or exclude
@@ -433,6 +707,111 @@ pub struct BrpListWatchingResponse {
     removed: Vec<String>,
 }
 
+/// The response to a `bevy/batch` request: the result of each call, in order.
+pub type BrpBatchResponse = Vec<Value>;
+
+/// The response to a `bevy/schedule/list` request.
+pub type BrpScheduleListResponse = Vec<BrpScheduleInfo>;
+
+/// A summary of a single schedule in a `bevy/schedule/list` response.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpScheduleInfo {
+    /// The label of the schedule, as printed by its [`Debug`] implementation.
+    pub label: String,
+    /// The number of systems in the schedule.
+    pub systems: usize,
+    /// The number of system sets in the schedule.
+    pub sets: usize,
+}
+
+/// The response to a `bevy/schedule/graph` request.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BrpScheduleGraphResponse {
+    /// The systems of the schedule.
+    pub systems: Vec<BrpScheduleGraphNode>,
+    /// The system sets of the schedule.
+    pub sets: Vec<BrpScheduleGraphNode>,
+    /// The edges of the hierarchy, from a set to one of its members.
+    pub hierarchy: Vec<(BrpScheduleNodeId, BrpScheduleNodeId)>,
+    /// The edges of the dependency graph, from a node to a node that must run after it.
+    pub dependencies: Vec<(BrpScheduleNodeId, BrpScheduleNodeId)>,
+    /// The pairs of systems with conflicting access and no ordering between them.
+    ///
+    /// This is only known once the schedule has been built, i.e. after it first ran.
+    pub ambiguities: Vec<BrpScheduleAmbiguity>,
+}
+
+/// A system or system set in a [`BrpScheduleGraphResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpScheduleGraphNode {
+    /// The index of the system or system set in the schedule.
+    pub id: usize,
+    /// The name of the system, or the [`Debug`] representation of the system set.
+    pub name: String,
+    /// The names of the run conditions of the system or system set.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub conditions: Vec<String>,
+}
+
+/// A reference to a node of a schedule graph: either a system or a system set.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpScheduleNodeId {
+    /// The index of a system.
+    System(usize),
+    /// The index of a system set.
+    Set(usize),
+}
+
+impl From<NodeId> for BrpScheduleNodeId {
+    fn from(node: NodeId) -> Self {
+        match node {
+            NodeId::System(index) => Self::System(index),
+            NodeId::Set(index) => Self::Set(index),
+        }
+    }
+}
+
+/// An ambiguity between two systems in a [`BrpScheduleGraphResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpScheduleAmbiguity {
+    /// The indices of the two ambiguous systems.
+    pub systems: (usize, usize),
+    /// The names of the components both systems access in a conflicting way.
+    ///
+    /// If this is empty, the systems conflict on their access to the whole [`World`].
+    pub components: Vec<String>,
+}
+
+/// The response to a `bevy/stepping/status` request.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BrpSteppingStatusResponse {
+    /// Whether stepping is enabled.
+    pub enabled: bool,
+    /// The schedules with stepping enabled, in the order they run.
+    ///
+    /// This is `None` until every added schedule has run once.
+    pub schedules: Option<Vec<String>>,
+    /// The next system that will run when stepping, if any.
+    pub cursor: Option<BrpSteppingCursor>,
+}
+
+/// The position of the stepping cursor in a [`BrpSteppingStatusResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingCursor {
+    /// The label of the schedule containing the next system.
+    pub schedule: String,
+    /// The index of the next system in the schedule.
+    pub system: usize,
+}
+
+/// The response to a `bevy/list_events` request.
+pub type BrpListEventsResponse = Vec<String>;
+
+/// The response to a `bevy/events+watch` request: the serialized values of the events sent
+/// since the last response, in the order they were sent.
+pub type BrpEventsWatchingResponse = Vec<Value>;
+
 /// The response to a `bevy/query` request.
 pub type BrpQueryResponse = Vec<BrpQueryRow>;
 
@@ -448,10 +827,26 @@ pub struct BrpQueryRow {
     /// The boolean-only containment query results.
     #[serde(skip_serializing_if = "HashMap::is_empty", default)]
     pub has: HashMap<String, Value>,
+
+    /// The change ticks of the components from the `changed` and `added` filters.
+    ///
+    /// The largest of these can be used as the `since_tick` of the next query to only
+    /// receive newer changes.
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub ticks: HashMap<String, BrpComponentTicks>,
+}
+
+/// The change ticks of a component on an entity.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct BrpComponentTicks {
+    /// The tick at which the component was added.
+    pub added: u32,
+    /// The tick at which the component was last changed.
+    pub changed: u32,
 }
 
 /// A helper function used to parse a `serde_json::Value`.
-fn parse<T: for<'de> Deserialize<'de>>(value: Value) -> Result<T, BrpError> {
+pub(crate) fn parse<T: for<'de> Deserialize<'de>>(value: Value) -> Result<T, BrpError> {
     serde_json::from_value(value).map_err(|err| BrpError {
         code: error_codes::INVALID_PARAMS,
         message: err.to_string(),
@@ -460,7 +855,9 @@ fn parse<T: for<'de> Deserialize<'de>>(value: Value) -> Result<T, BrpError> {
 }
 
 /// A helper function used to parse a `serde_json::Value` wrapped in an `Option`.
-fn parse_some<T: for<'de> Deserialize<'de>>(value: Option<Value>) -> Result<T, BrpError> {
+pub(crate) fn parse_some<T: for<'de> Deserialize<'de>>(
+    value: Option<Value>,
+) -> Result<T, BrpError> {
     match value {
         Some(value) => parse(value),
         None => Err(BrpError {
@@ -703,7 +1100,15 @@ pub fn process_remote_query_request(In(params): In<Option<Value>>, world: &mut W
             option,
             has,
         },
-        filter: BrpQueryFilter { without, with },
+        filter:
+            BrpQueryFilter {
+                without,
+                with,
+                changed,
+                added,
+                since_tick,
+                predicates,
+            },
         strict,
     } = parse_some(params)?;
 
@@ -721,6 +1126,48 @@ pub fn process_remote_query_request(In(params): In<Option<Value>>, world: &mut W
     let with = get_component_ids(&type_registry, world, with, strict)
         .map_err(BrpError::component_error)?;
 
+    // Components that are not used in the world can't match the change filters or
+    // predicates, so the response is empty if any of them is missing.
+    let changed_count = changed.len();
+    let changed = get_component_ids(&type_registry, world, changed, strict)
+        .map_err(BrpError::component_error)?;
+    let added_count = added.len();
+    let added = get_component_ids(&type_registry, world, added, strict)
+        .map_err(BrpError::component_error)?;
+    let predicates = predicates
+        .into_iter()
+        .map(|predicate| {
+            let registration =
+                get_component_type_registration(&type_registry, &predicate.component)?;
+            let reflect_component = registration
+                .data::<ReflectComponent>()
+                .ok_or_else(|| anyhow!("Component `{}` isn't reflectable", predicate.component))?;
+            let component_id = world.components().get_id(registration.type_id());
+            if component_id.is_none() && strict {
+                return Err(anyhow!(
+                    "Component `{}` isn't used in the world",
+                    predicate.component
+                ));
+            }
+            Ok((component_id, reflect_component, predicate))
+        })
+        .collect::<AnyhowResult<Vec<_>>>()
+        .map_err(BrpError::component_error)?;
+    let predicates = predicates
+        .into_iter()
+        .map(|(component_id, reflect_component, predicate)| {
+            Some((component_id?, reflect_component, predicate))
+        })
+        .collect::<Option<Vec<_>>>();
+    let Some(predicates) =
+        predicates.filter(|_| changed.len() == changed_count && added.len() == added_count)
+    else {
+        return serde_json::to_value(BrpQueryResponse::default()).map_err(BrpError::internal);
+    };
+
+    let this_run = world.read_change_tick();
+    let last_run = since_tick.map_or(world.last_change_tick(), Tick::new);
+
     let mut query = QueryBuilder::<FilteredEntityRef>::new(world);
     for (_, component) in &components {
         query.ref_id(*component);
@@ -741,6 +1188,23 @@ pub fn process_remote_query_request(In(params): In<Option<Value>>, world: &mut W
     for (_, with) in with {
         query.with_id(with);
     }
+    for (_, component) in changed.iter().chain(&added) {
+        query.ref_id(*component);
+    }
+    for (component, ..) in &predicates {
+        query.ref_id(*component);
+    }
+
+    // The paths of the components whose ticks are reported in each row.
+    let tick_paths: Vec<(&str, ComponentId)> = changed
+        .iter()
+        .chain(&added)
+        .map(|(type_id, component_id)| {
+            reflect_component_from_id(*type_id, &type_registry)
+                .map(|(path, _)| (path, *component_id))
+        })
+        .collect::<AnyhowResult<_>>()
+        .map_err(BrpError::component_error)?;
 
     // At this point, we can safely unify `components` and `option`, since we only retrieved
     // entities that actually have all the `components` already.
@@ -763,7 +1227,38 @@ pub fn process_remote_query_request(In(params): In<Option<Value>>, world: &mut W
 
     let mut response = BrpQueryResponse::default();
     let mut query = query.build();
-    for row in query.iter(world) {
+    'rows: for row in query.iter(world) {
+        let changed_since = changed.iter().all(|(_, component_id)| {
+            row.get_change_ticks_by_id(*component_id)
+                .is_some_and(|ticks| ticks.is_changed(last_run, this_run))
+        });
+        let added_since = added.iter().all(|(_, component_id)| {
+            row.get_change_ticks_by_id(*component_id)
+                .is_some_and(|ticks| ticks.is_added(last_run, this_run))
+        });
+        if !changed_since || !added_since {
+            continue;
+        }
+
+        for (_, reflect_component, predicate) in &predicates {
+            let Some(reflected) = reflect_component.reflect(row.clone()) else {
+                continue 'rows;
+            };
+            let field = if predicate.path.is_empty() {
+                reflected.as_partial_reflect()
+            } else {
+                let Ok(field) = reflected.reflect_path(predicate.path.as_str()) else {
+                    continue 'rows;
+                };
+                field
+            };
+            let value = serde_json::to_value(TypedReflectSerializer::new(field, &type_registry))
+                .map_err(BrpError::component_error)?;
+            if !predicate.operation.matches(&value) {
+                continue 'rows;
+            }
+        }
+
         // The map of component values:
         let components_map = build_components_map(
             row.clone(),
@@ -777,10 +1272,27 @@ pub fn process_remote_query_request(In(params): In<Option<Value>>, world: &mut W
             row.clone(),
             has_paths_and_reflect_components.iter().copied(),
         );
+
+        // The map of change ticks of the filtered components:
+        let ticks = tick_paths
+            .iter()
+            .filter_map(|(path, component_id)| {
+                let ticks = row.get_change_ticks_by_id(*component_id)?;
+                Some((
+                    (*path).to_owned(),
+                    BrpComponentTicks {
+                        added: ticks.added.get(),
+                        changed: ticks.changed.get(),
+                    },
+                ))
+            })
+            .collect();
+
         response.push(BrpQueryRow {
             entity: row.id(),
             components: components_map,
             has: has_map,
+            ticks,
         });
     }
 
@@ -1151,388 +1663,910 @@ pub fn process_remote_list_watching_request(
     }
 }
 
-/// Handles a `bevy/registry/schema` request (list all registry types in form of schema) coming from a client.
-pub fn export_registry_types(In(params): In<Option<Value>>, world: &World) -> BrpResult {
-    let filter: BrpJsonSchemaQueryFilter = match params {
-        None => Default::default(),
-        Some(params) => parse(params)?,
-    };
-
-    let types = world.resource::<AppTypeRegistry>();
-    let types = types.read();
-    let schemas = types
-        .iter()
-        .map(export_type)
-        .filter(|(_, schema)| {
-            if let Some(crate_name) = &schema.crate_name {
-                if !filter.with_crates.is_empty()
-                    && !filter.with_crates.iter().any(|c| crate_name.eq(c))
-                {
-                    return false;
-                }
-                if !filter.without_crates.is_empty()
-                    && filter.without_crates.iter().any(|c| crate_name.eq(c))
-                {
-                    return false;
-                }
-            }
-            if !filter.type_limit.with.is_empty()
-                && !filter
-                    .type_limit
-                    .with
-                    .iter()
-                    .any(|c| schema.reflect_types.iter().any(|cc| c.eq(cc)))
-            {
-                return false;
-            }
-            if !filter.type_limit.without.is_empty()
-                && filter
-                    .type_limit
-                    .without
-                    .iter()
-                    .any(|c| schema.reflect_types.iter().any(|cc| c.eq(cc)))
-            {
-                return false;
-            }
-
-            true
+/// Handles a `bevy/schedule/list` request coming from a client.
+///
+/// Schedules that are currently running, such as the one processing remote requests, are
+/// absent from [`Schedules`] and therefore not listed.
+pub fn process_remote_schedule_list_request(
+    In(_params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let mut response = world
+        .get_resource::<Schedules>()
+        .into_iter()
+        .flat_map(Schedules::iter)
+        .map(|(label, schedule)| BrpScheduleInfo {
+            label: format!("{label:?}"),
+            systems: schedule.systems_len(),
+            sets: schedule.graph().system_sets().count(),
         })
-        .collect::<HashMap<String, JsonSchemaBevyType>>();
+        .collect::<BrpScheduleListResponse>();
 
-    serde_json::to_value(schemas).map_err(BrpError::internal)
+    response.sort_by(|a, b| a.label.cmp(&b.label));
+
+    serde_json::to_value(response).map_err(BrpError::internal)
 }
 
-/// Exports schema info for a given type
-fn export_type(reg: &TypeRegistration) -> (String, JsonSchemaBevyType) {
-    let t = reg.type_info();
-    let binding = t.type_path_table();
-
-    let short_path = binding.short_path();
-    let type_path = binding.path();
-    let mut typed_schema = JsonSchemaBevyType {
-        reflect_types: get_registered_reflect_types(reg),
-        short_path: short_path.to_owned(),
-        type_path: type_path.to_owned(),
-        crate_name: binding.crate_name().map(str::to_owned),
-        module_path: binding.module_path().map(str::to_owned),
-        ..Default::default()
+/// Handles a `bevy/schedule/graph` request coming from a client.
+pub fn process_remote_schedule_graph_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let BrpScheduleGraphParams { schedule } = parse_some(params)?;
+
+    let schedule = get_schedule(world, &schedule)?;
+    let export = ScheduleGraphExport::new(schedule, world.components());
+    let node = |node: ExportedNode| BrpScheduleGraphNode {
+        id: node.id.index(),
+        name: node.name,
+        conditions: node.conditions,
+    };
+    let edges = |edges: Vec<(NodeId, NodeId)>| {
+        edges
+            .into_iter()
+            .map(|(a, b)| (a.into(), b.into()))
+            .collect()
     };
-    match t {
-        TypeInfo::Struct(info) => {
-            typed_schema.properties = info
-                .iter()
-                .map(|field| (field.name().to_owned(), field.ty().ref_type()))
-                .collect::<HashMap<_, _>>();
-            typed_schema.required = info
-                .iter()
-                .filter(|field| !field.type_path().starts_with("core::option::Option"))
-                .map(|f| f.name().to_owned())
-                .collect::<Vec<_>>();
-            typed_schema.additional_properties = Some(false);
-            typed_schema.schema_type = SchemaType::Object;
-            typed_schema.kind = SchemaKind::Struct;
-        }
-        TypeInfo::Enum(info) => {
-            typed_schema.kind = SchemaKind::Enum;
 
-            let simple = info
-                .iter()
-                .all(|variant| matches!(variant, VariantInfo::Unit(_)));
-            if simple {
-                typed_schema.schema_type = SchemaType::String;
-                typed_schema.one_of = info
-                    .iter()
-                    .map(|variant| match variant {
-                        VariantInfo::Unit(v) => v.name().into(),
-                        _ => unreachable!(),
-                    })
-                    .collect::<Vec<_>>();
-            } else {
-                typed_schema.schema_type = SchemaType::Object;
-                typed_schema.one_of = info
-                .iter()
-                .map(|variant| match variant {
-                    VariantInfo::Struct(v) => json!({
-                        "type": "object",
-                        "kind": "Struct",
-                        "typePath": format!("{}::{}", type_path, v.name()),
-                        "shortPath": v.name(),
-                        "properties": v
-                            .iter()
-                            .map(|field| (field.name().to_owned(), field.ref_type()))
-                            .collect::<Map<_, _>>(),
-                        "additionalProperties": false,
-                        "required": v
-                            .iter()
-                            .filter(|field| !field.type_path().starts_with("core::option::Option"))
-                            .map(NamedField::name)
-                            .collect::<Vec<_>>(),
-                    }),
-                    VariantInfo::Tuple(v) => json!({
-                        "type": "array",
-                        "kind": "Tuple",
-                        "typePath": format!("{}::{}", type_path, v.name()),
-                        "shortPath": v.name(),
-                        "prefixItems": v
-                            .iter()
-                            .map(SchemaJsonReference::ref_type)
-                            .collect::<Vec<_>>(),
-                        "items": false,
-                    }),
-                    VariantInfo::Unit(v) => json!({
-                        "typePath": format!("{}::{}", type_path, v.name()),
-                        "shortPath": v.name(),
-                    }),
-                })
-                .collect::<Vec<_>>();
-            }
-        }
-        TypeInfo::TupleStruct(info) => {
-            typed_schema.schema_type = SchemaType::Array;
-            typed_schema.kind = SchemaKind::TupleStruct;
-            typed_schema.prefix_items = info
-                .iter()
-                .map(SchemaJsonReference::ref_type)
-                .collect::<Vec<_>>();
-            typed_schema.items = Some(false.into());
-        }
-        TypeInfo::List(info) => {
-            typed_schema.schema_type = SchemaType::Array;
-            typed_schema.kind = SchemaKind::List;
-            typed_schema.items = info.item_ty().ref_type().into();
-        }
-        TypeInfo::Array(info) => {
-            typed_schema.schema_type = SchemaType::Array;
-            typed_schema.kind = SchemaKind::Array;
-            typed_schema.items = info.item_ty().ref_type().into();
-        }
-        TypeInfo::Map(info) => {
-            typed_schema.schema_type = SchemaType::Object;
-            typed_schema.kind = SchemaKind::Map;
-            typed_schema.key_type = info.key_ty().ref_type().into();
-            typed_schema.value_type = info.value_ty().ref_type().into();
-        }
-        TypeInfo::Tuple(info) => {
-            typed_schema.schema_type = SchemaType::Array;
-            typed_schema.kind = SchemaKind::Tuple;
-            typed_schema.prefix_items = info
-                .iter()
-                .map(SchemaJsonReference::ref_type)
-                .collect::<Vec<_>>();
-            typed_schema.items = Some(false.into());
-        }
-        TypeInfo::Set(info) => {
-            typed_schema.schema_type = SchemaType::Set;
-            typed_schema.kind = SchemaKind::Set;
-            typed_schema.items = info.value_ty().ref_type().into();
-        }
-        TypeInfo::Opaque(info) => {
-            typed_schema.schema_type = info.map_json_type();
-            typed_schema.kind = SchemaKind::Value;
-        }
+    let response = BrpScheduleGraphResponse {
+        systems: export.systems.into_iter().map(node).collect(),
+        sets: export.sets.into_iter().map(node).collect(),
+        hierarchy: edges(export.hierarchy),
+        dependencies: edges(export.dependencies),
+        ambiguities: export
+            .ambiguities
+            .into_iter()
+            .map(|ambiguity| BrpScheduleAmbiguity {
+                systems: (ambiguity.systems.0.index(), ambiguity.systems.1.index()),
+                components: ambiguity.components,
+            })
+            .collect(),
     };
 
-    (t.type_path().to_owned(), typed_schema)
-}
-
-fn get_registered_reflect_types(reg: &TypeRegistration) -> Vec<String> {
-    // Vec could be moved to allow registering more types by game maker.
-    let registered_reflect_types: [(TypeId, &str); 5] = [
-        { (TypeId::of::<ReflectComponent>(), "Component") },
-        { (TypeId::of::<ReflectResource>(), "Resource") },
-        { (TypeId::of::<ReflectDefault>(), "Default") },
-        { (TypeId::of::<ReflectSerialize>(), "Serialize") },
-        { (TypeId::of::<ReflectDeserialize>(), "Deserialize") },
-    ];
-    let mut result = Vec::new();
-    for (id, name) in registered_reflect_types {
-        if reg.data_by_id(id).is_some() {
-            result.push(name.to_owned());
-        }
-    }
-    result
+    serde_json::to_value(response).map_err(BrpError::internal)
 }
 
-/// JSON Schema type for Bevy Registry Types
-/// It tries to follow this standard: <https://json-schema.org/specification>
-///
-/// To take the full advantage from info provided by Bevy registry it provides extra fields
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
-#[serde(rename_all = "camelCase")]
-pub struct JsonSchemaBevyType {
-    /// Bevy specific field, short path of the type.
-    pub short_path: String,
-    /// Bevy specific field, full path of the type.
-    pub type_path: String,
-    /// Bevy specific field, path of the module that type is part of.
-    #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub module_path: Option<String>,
-    /// Bevy specific field, name of the crate that type is part of.
-    #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub crate_name: Option<String>,
-    /// Bevy specific field, names of the types that type reflects.
-    #[serde(skip_serializing_if = "Vec::is_empty", default)]
-    pub reflect_types: Vec<String>,
-    /// Bevy specific field, [`TypeInfo`] type mapping.
-    pub kind: SchemaKind,
-    /// Bevy specific field, provided when [`SchemaKind`] `kind` field is equal to [`SchemaKind::Map`].
-    ///
-    /// It contains type info of key of the Map.
-    #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub key_type: Option<Value>,
-    /// Bevy specific field, provided when [`SchemaKind`] `kind` field is equal to [`SchemaKind::Map`].
-    ///
-    /// It contains type info of value of the Map.
-    #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub value_type: Option<Value>,
-    /// The type keyword is fundamental to JSON Schema. It specifies the data type for a schema.
-    #[serde(rename = "type")]
-    pub schema_type: SchemaType,
-    /// The behavior of this keyword depends on the presence and annotation results of "properties"
-    /// and "patternProperties" within the same schema object.
-    /// Validation with "additionalProperties" applies only to the child
-    /// values of instance names that do not appear in the annotation results of either "properties" or "patternProperties".
-    #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub additional_properties: Option<bool>,
-    /// Validation succeeds if, for each name that appears in both the instance and as a name
-    /// within this keyword's value, the child instance for that name successfully validates
-    /// against the corresponding schema.
-    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
-    pub properties: HashMap<String, Value>,
-    /// An object instance is valid against this keyword if every item in the array is the name of a property in the instance.
-    #[serde(skip_serializing_if = "Vec::is_empty", default)]
-    pub required: Vec<String>,
-    /// An instance validates successfully against this keyword if it validates successfully against exactly one schema defined by this keyword's value.
-    #[serde(skip_serializing_if = "Vec::is_empty", default)]
-    pub one_of: Vec<Value>,
-    /// Validation succeeds if each element of the instance validates against the schema at the same position, if any. This keyword does not constrain the length of the array. If the array is longer than this keyword's value, this keyword validates only the prefix of matching length.
-    ///
-    /// This keyword produces an annotation value which is the largest index to which this keyword
-    /// applied a subschema. The value MAY be a boolean true if a subschema was applied to every
-    /// index of the instance, such as is produced by the "items" keyword.
-    /// This annotation affects the behavior of "items" and "unevaluatedItems".
-    #[serde(skip_serializing_if = "Vec::is_empty", default)]
-    pub prefix_items: Vec<Value>,
-    /// This keyword applies its subschema to all instance elements at indexes greater
-    /// than the length of the "prefixItems" array in the same schema object,
-    /// as reported by the annotation result of that "prefixItems" keyword.
-    /// If no such annotation result exists, "items" applies its subschema to all
-    /// instance array elements.
-    ///
-    /// If the "items" subschema is applied to any positions within the instance array,
-    /// it produces an annotation result of boolean true, indicating that all remaining
-    /// array elements have been evaluated against this keyword's subschema.
-    #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub items: Option<Value>,
-}
-
-/// Kind of json schema, maps [`TypeInfo`] type
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
-pub enum SchemaKind {
-    /// Struct
-    #[default]
-    Struct,
-    /// Enum type
-    Enum,
-    /// A key-value map
-    Map,
-    /// Array
-    Array,
-    /// List
-    List,
-    /// Fixed size collection of items
-    Tuple,
-    /// Fixed size collection of items with named fields
-    TupleStruct,
-    /// Set of unique values
-    Set,
-    /// Single value, eg. primitive types
-    Value,
-}
-
-/// Type of json schema
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
-#[serde(rename_all = "lowercase")]
-pub enum SchemaType {
-    /// Represents a string value.
-    String,
-
-    /// Represents a floating-point number.
-    Float,
-
-    /// Represents an unsigned integer.
-    Uint,
-
-    /// Represents a signed integer.
-    Int,
-
-    /// Represents an object with key-value pairs.
-    Object,
-
-    /// Represents an array of values.
-    Array,
-
-    /// Represents a boolean value (true or false).
-    Boolean,
-
-    /// Represents a set of unique values.
-    Set,
-
-    /// Represents a null value.
-    #[default]
-    Null,
-}
-
-/// Helper trait for generating json schema reference
-trait SchemaJsonReference {
-    /// Reference to another type in schema.
-    /// The value `$ref` is a URI-reference that is resolved against the schema.
-    fn ref_type(self) -> Value;
-}
-
-/// Helper trait for mapping bevy type path into json schema type
-trait SchemaJsonType {
-    /// Bevy Reflect type path
-    fn get_type_path(&self) -> &'static str;
-
-    /// JSON Schema type keyword from Bevy reflect type path into
-    fn map_json_type(&self) -> SchemaType {
-        match self.get_type_path() {
-            "bool" => SchemaType::Boolean,
-            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => SchemaType::Uint,
-            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => SchemaType::Int,
-            "f32" | "f64" => SchemaType::Float,
-            "char" | "str" | "alloc::string::String" => SchemaType::String,
-            _ => SchemaType::Object,
-        }
+/// Handles a `bevy/stepping/enable` request coming from a client.
+pub fn process_remote_stepping_enable_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    if !cfg!(feature = "bevy_debug_stepping") {
+        return Err(BrpError::stepping_unavailable());
     }
-}
 
-impl SchemaJsonType for OpaqueInfo {
-    fn get_type_path(&self) -> &'static str {
-        self.type_path()
-    }
-}
+    let BrpSteppingEnableParams { schedules } = match params {
+        None => Default::default(),
+        Some(params) => parse(params)?,
+    };
 
-impl SchemaJsonReference for &bevy_reflect::Type {
-    fn ref_type(self) -> Value {
-        let path = self.path();
-        json!({"type": json!({ "$ref": format!("#/$defs/{path}") })})
+    let labels = schedules
+        .iter()
+        .map(|schedule| get_schedule(world, schedule).map(Schedule::label))
+        .collect::<BrpResult<Vec<_>>>()?;
+
+    let mut stepping = world.get_resource_or_init::<Stepping>();
+    for label in labels {
+        stepping.add_schedule(label);
+    }
+    stepping.enable();
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/stepping/disable` request coming from a client.
+pub fn process_remote_stepping_disable_request(
+    In(_params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    get_stepping_mut(world)?.disable();
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/stepping/step_system` request coming from a client.
+///
+/// This runs the next system in the stepping frame during the next frame.
+pub fn process_remote_stepping_step_system_request(
+    In(_params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    get_stepping_mut(world)?.step_frame();
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/stepping/step_frame` request coming from a client.
+///
+/// This runs all remaining systems in the stepping frame, up to the next breakpoint, during the
+/// next frame.
+pub fn process_remote_stepping_step_frame_request(
+    In(_params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    get_stepping_mut(world)?.continue_frame();
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/stepping/set_breakpoint` request coming from a client.
+pub fn process_remote_stepping_set_breakpoint_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSteppingBreakpointParams { schedule, system } = parse_some(params)?;
+
+    let (label, node) = get_schedule_system(world, &schedule, system)?;
+    get_stepping_mut(world)?.set_breakpoint_node(label, node);
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/stepping/clear_breakpoint` request coming from a client.
+pub fn process_remote_stepping_clear_breakpoint_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSteppingBreakpointParams { schedule, system } = parse_some(params)?;
+
+    let (label, node) = get_schedule_system(world, &schedule, system)?;
+    get_stepping_mut(world)?.clear_breakpoint_node(label, node);
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/stepping/status` request coming from a client.
+pub fn process_remote_stepping_status_request(
+    In(_params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let response = match world.get_resource::<Stepping>() {
+        None => BrpSteppingStatusResponse::default(),
+        Some(stepping) => BrpSteppingStatusResponse {
+            enabled: stepping.is_enabled(),
+            schedules: stepping
+                .schedules()
+                .ok()
+                .map(|labels| labels.iter().map(|label| format!("{label:?}")).collect()),
+            cursor: stepping.cursor().map(|(label, node)| BrpSteppingCursor {
+                schedule: format!("{label:?}"),
+                system: node.index(),
+            }),
+        },
+    };
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `bevy/send_event` request coming from a client.
+pub fn process_remote_send_event_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSendEventParams {
+        event: event_path,
+        value,
+    } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+
+    let reflected_event =
+        deserialize_event(&type_registry, &event_path, value).map_err(BrpError::event_error)?;
+    let reflect_event =
+        get_reflect_event(&type_registry, &event_path).map_err(BrpError::event_error)?;
+
+    if reflect_event
+        .send(world, &*reflected_event, &type_registry)
+        .is_none()
+    {
+        return Err(BrpError::event_not_added(&event_path));
+    }
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/trigger_event` request coming from a client.
+pub fn process_remote_trigger_event_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpTriggerEventParams {
+        event: event_path,
+        value,
+        targets,
+    } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+
+    let reflected_event =
+        deserialize_event(&type_registry, &event_path, value).map_err(BrpError::event_error)?;
+    let reflect_event =
+        get_reflect_event(&type_registry, &event_path).map_err(BrpError::event_error)?;
+
+    for &target in &targets {
+        get_entity(world, target)?;
+    }
+    reflect_event.trigger(world, &*reflected_event, &targets, &type_registry);
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/list_events` request coming from a client.
+pub fn process_remote_list_events_request(
+    In(_params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let mut response = BrpListEventsResponse::default();
+
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+
+    for registered_type in type_registry.iter() {
+        if registered_type.data::<ReflectEvent>().is_some() {
+            response.push(registered_type.type_info().type_path().to_owned());
+        }
     }
+
+    response.sort();
+
+    serde_json::to_value(response).map_err(BrpError::internal)
 }
 
-impl SchemaJsonReference for &bevy_reflect::UnnamedField {
-    fn ref_type(self) -> Value {
-        let path = self.type_path();
-        json!({"type": json!({ "$ref": format!("#/$defs/{path}") })})
+/// Handles a `bevy/events+watch` request coming from a client.
+///
+/// Each watch keeps its own cursor into the event buffer, so it sees every event sent after it
+/// started exactly once, even when events aren't updated every frame (e.g. with a fixed
+/// timestep).
+pub fn process_remote_events_watching_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+    mut cursors: Local<HashMap<Option<RemoteWatchId>, usize>>,
+) -> BrpResult<Option<Value>> {
+    let BrpEventsParams { event: event_path } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+    let reflect_event =
+        get_reflect_event(&type_registry, &event_path).map_err(BrpError::event_error)?;
+
+    // Forget the cursors of the watches that have ended.
+    if let Some(requests) = world.get_resource::<RemoteWatchingRequests>() {
+        cursors.retain(|watch, _| watch.is_none_or(|watch| requests.contains(watch)));
+    }
+
+    let Some(event_count) = reflect_event.event_count(world) else {
+        return Err(BrpError::event_not_added(&event_path));
+    };
+    let watch = world
+        .get_resource::<CurrentRemoteWatch>()
+        .and_then(|current| current.0);
+    let cursor = cursors.entry(watch).or_insert(event_count);
+    let events = reflect_event.read(world, cursor).unwrap_or_default();
+    if events.is_empty() {
+        return Ok(None);
     }
+
+    let response = events
+        .into_iter()
+        .map(|event| {
+            let serializer =
+                TypedReflectSerializer::new(event.as_partial_reflect(), &type_registry);
+            serde_json::to_value(serializer).map_err(BrpError::event_error)
+        })
+        .collect::<Result<BrpEventsWatchingResponse, _>>()?;
+
+    Ok(Some(
+        serde_json::to_value(response).map_err(BrpError::internal)?,
+    ))
 }
 
-impl SchemaJsonReference for &NamedField {
-    fn ref_type(self) -> Value {
-        let type_path = self.type_path();
-        json!({"type": json!({ "$ref": format!("#/$defs/{type_path}") }), "typePath": self.name()})
+/// Handles a `bevy/batch` request coming from a client.
+///
+/// Every call is first checked against the state the world will be in when it runs, which
+/// rejects most failing batches before anything is modified: params must be valid, types must be
+/// registered, values must deserialize, and entities must exist, counting those spawned or
+/// destroyed by earlier calls of the batch.
+///
+/// Every call is then dispatched to the handler registered in [`RemoteMethods`], in order. If a
+/// call still fails, the effects of the calls that ran before it are undone and the error is
+/// returned with the index of the failing call in its `data`. Components and resources that were
+/// already present are written back in place along with their change ticks, so that no hook or
+/// observer runs for them. Undoing a structural change, like a spawn or an added component, runs
+/// the hooks and observers of the removal. Despawns requested through `bevy/destroy` are deferred
+/// until every other call has succeeded.
+///
+/// Only the built-in methods whose effects can be undone are accepted inside a batch. If a
+/// [`RemoteAccessControl`] is present, every call must be allowed by it, or the whole batch is
+/// rejected before any call runs.
+pub fn process_remote_batch_request(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    let BrpBatchParams { requests } = parse_some(params)?;
+
+    // Reject the whole batch up-front if it contains a call that cannot be rolled back.
+    for (index, request) in requests.iter().enumerate() {
+        if !is_batchable_method(&request.method) {
+            return Err(BrpError {
+                code: error_codes::INVALID_REQUEST,
+                message: format!("Method `{}` can not be used in a batch", request.method),
+                data: Some(json!({ "index": index })),
+            });
+        }
+    }
+
+    // The access control rules apply to every call, not only to the batch itself.
+    if let Some(mut access_control) = world.get_resource_mut::<RemoteAccessControl>() {
+        access_control
+            .check_methods(requests.iter().map(|request| request.method.as_str()))
+            .map_err(|(index, err)| BrpError {
+                code: err.code,
+                message: format!(
+                    "Batch call {index} (`{}`) denied: {}",
+                    requests[index].method, err.message
+                ),
+                data: Some(json!({ "index": index })),
+            })?;
+    }
+
+    validate_batch(world, &requests)
+        .map_err(|(index, err)| batch_call_error(index, &requests[index].method, err))?;
+
+    let mut results = BrpBatchResponse::with_capacity(requests.len());
+    let mut journal = Vec::new();
+    let mut pending_despawns = Vec::new();
+
+    for (index, BrpBatchRequest { method, params }) in requests.into_iter().enumerate() {
+        let result = params
+            .map(|params| resolve_batch_entities(params, &results))
+            .transpose()
+            .and_then(|params| {
+                run_batch_request(world, &method, params, &mut journal, &mut pending_despawns)
+            });
+
+        match result {
+            Ok(value) => results.push(value),
+            Err(err) => {
+                rollback_batch(world, journal);
+                return Err(batch_call_error(index, &method, err));
+            }
+        }
     }
+
+    for entity in pending_despawns {
+        if let Ok(entity_world_mut) = world.get_entity_mut(entity) {
+            entity_world_mut.despawn();
+        }
+    }
+
+    serde_json::to_value(results).map_err(BrpError::internal)
+}
+
+/// Wraps the error of the call at `index` of a `bevy/batch` request.
+fn batch_call_error(index: usize, method: &str, err: BrpError) -> BrpError {
+    BrpError {
+        code: err.code,
+        message: format!("Batch call {index} (`{method}`) failed: {}", err.message),
+        data: Some(json!({ "index": index, "error": err.data })),
+    }
+}
+
+/// Returns true if the given method is allowed inside of a `bevy/batch` request.
+fn is_batchable_method(method: &str) -> bool {
+    matches!(
+        method,
+        BRP_GET_METHOD
+            | BRP_QUERY_METHOD
+            | BRP_LIST_METHOD
+            | BRP_GET_RESOURCE_METHOD
+            | BRP_LIST_RESOURCES_METHOD
+            | BRP_SPAWN_METHOD
+            | BRP_INSERT_METHOD
+            | BRP_REMOVE_METHOD
+            | BRP_DESTROY_METHOD
+            | BRP_REPARENT_METHOD
+            | BRP_MUTATE_COMPONENT_METHOD
+            | BRP_INSERT_RESOURCE_METHOD
+            | BRP_REMOVE_RESOURCE_METHOD
+            | BRP_MUTATE_RESOURCE_METHOD
+    )
+}
+
+/// Replaces every `{ "batch_entity": N }` object in `value` with the entity spawned by the
+/// `N`-th call of the batch.
+fn resolve_batch_entities(value: Value, results: &[Value]) -> BrpResult {
+    match value {
+        Value::Object(map) => {
+            if let (1, Some(index)) = (map.len(), map.get("batch_entity")) {
+                let entity = index
+                    .as_u64()
+                    .and_then(|index| results.get(index as usize))
+                    .and_then(|result| result.get("entity"))
+                    .ok_or_else(|| BrpError {
+                        code: error_codes::INVALID_PARAMS,
+                        message: format!(
+                            "`batch_entity` {index} does not refer to an entity spawned earlier in the batch"
+                        ),
+                        data: None,
+                    })?;
+                return Ok(entity.clone());
+            }
+
+            map.into_iter()
+                .map(|(key, value)| Ok((key, resolve_batch_entities(value, results)?)))
+                .collect::<BrpResult<Map<_, _>>>()
+                .map(Value::Object)
+        }
+        Value::Array(values) => values
+            .into_iter()
+            .map(|value| resolve_batch_entities(value, results))
+            .collect::<BrpResult<Vec<_>>>()
+            .map(Value::Array),
+        value => Ok(value),
+    }
+}
+
+/// Checks every call of a `bevy/batch` request before any of them runs.
+///
+/// Returns the index of the first call that would fail along with its error.
+fn validate_batch(world: &World, requests: &[BrpBatchRequest]) -> Result<(), (usize, BrpError)> {
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+    let mut validation = BatchValidation {
+        world,
+        type_registry: &type_registry,
+        spawned: HashSet::default(),
+        destroyed: HashSet::default(),
+    };
+
+    // Stand-ins for the results of the calls, so that `batch_entity` objects can be resolved.
+    let mut results = Vec::with_capacity(requests.len());
+    for (index, BrpBatchRequest { method, params }) in requests.iter().enumerate() {
+        let result = params
+            .clone()
+            .map(|params| resolve_batch_entities(params, &results))
+            .transpose()
+            .and_then(|params| validation.validate(index, method, params))
+            .map_err(|err| (index, err))?;
+        results.push(result);
+    }
+
+    Ok(())
+}
+
+/// The state of the world as seen by the calls of a `bevy/batch` request being validated.
+struct BatchValidation<'w> {
+    world: &'w World,
+    type_registry: &'w TypeRegistry,
+    /// The stand-ins for the entities spawned by earlier calls, which don't exist yet.
+    spawned: HashSet<Entity>,
+    /// The entities destroyed by earlier calls, which still exist until the batch is committed.
+    destroyed: HashSet<Entity>,
+}
+
+impl BatchValidation<'_> {
+    /// Checks the call at `index`, returning a stand-in for its result.
+    fn validate(&mut self, index: usize, method: &str, params: Option<Value>) -> BrpResult {
+        match method {
+            BRP_GET_METHOD => {
+                let BrpGetParams { entity, .. } = parse_some(params)?;
+                self.check_entity(entity)?;
+            }
+            BRP_LIST_METHOD => {
+                if let Some(BrpListParams { entity }) = params.map(parse).transpose()? {
+                    self.check_entity(entity)?;
+                }
+            }
+            BRP_SPAWN_METHOD => {
+                let BrpSpawnParams { components } = parse_some(params)?;
+                self.check_components(components)?;
+                // Entity indices are allocated from zero, so these won't be confused with the
+                // entities of the world.
+                let entity = Entity::from_raw(u32::MAX - index as u32);
+                self.spawned.insert(entity);
+                return Ok(json!(BrpSpawnResponse { entity }));
+            }
+            BRP_INSERT_METHOD => {
+                let BrpInsertParams { entity, components } = parse_some(params)?;
+                self.check_entity(entity)?;
+                self.check_components(components)?;
+            }
+            BRP_REMOVE_METHOD => {
+                let BrpRemoveParams { entity, components } = parse_some(params)?;
+                self.check_entity(entity)?;
+                for component in &components {
+                    get_reflect_component(self.type_registry, component)
+                        .map_err(BrpError::component_error)?;
+                }
+            }
+            BRP_DESTROY_METHOD => {
+                let BrpDestroyParams { entity } = parse_some(params)?;
+                self.check_entity(entity)?;
+                self.destroyed.insert(entity);
+            }
+            BRP_REPARENT_METHOD => {
+                let BrpReparentParams { entities, parent } = parse_some(params)?;
+                if let Some(parent) = parent {
+                    self.check_entity(parent)?;
+                }
+                for entity in entities {
+                    if parent == Some(entity) {
+                        return Err(BrpError::self_reparent(entity));
+                    }
+                    self.check_entity(entity)?;
+                }
+            }
+            BRP_MUTATE_COMPONENT_METHOD => {
+                let BrpMutateComponentParams {
+                    entity,
+                    component,
+                    path,
+                    value,
+                } = parse_some(params)?;
+                self.check_entity(entity)?;
+                let reflect_component = get_reflect_component(self.type_registry, &component)
+                    .map_err(BrpError::component_error)?;
+                // A component that isn't present yet may be inserted by an earlier call.
+                if let Some(reflected) = self
+                    .world
+                    .get_entity(entity)
+                    .ok()
+                    .and_then(|entity_ref| reflect_component.reflect(entity_ref))
+                {
+                    self.check_field(reflected, &path, &value)
+                        .map_err(BrpError::component_error)?;
+                }
+            }
+            BRP_INSERT_RESOURCE_METHOD => {
+                let BrpInsertResourceParams { resource, value } = parse_some(params)?;
+                get_reflect_resource(self.type_registry, &resource)
+                    .map_err(BrpError::resource_error)?;
+                deserialize_resource(self.type_registry, &resource, value)
+                    .map_err(BrpError::resource_error)?;
+            }
+            BRP_REMOVE_RESOURCE_METHOD => {
+                let BrpRemoveResourceParams { resource } = parse_some(params)?;
+                get_reflect_resource(self.type_registry, &resource)
+                    .map_err(BrpError::resource_error)?;
+            }
+            BRP_MUTATE_RESOURCE_METHOD => {
+                let BrpMutateResourceParams {
+                    resource,
+                    path,
+                    value,
+                } = parse_some(params)?;
+                let reflect_resource = get_reflect_resource(self.type_registry, &resource)
+                    .map_err(BrpError::resource_error)?;
+                // A resource that isn't present yet may be inserted by an earlier call.
+                if let Some(reflected) = reflect_resource.reflect(self.world) {
+                    self.check_field(reflected, &path, &value)
+                        .map_err(BrpError::resource_error)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(Value::Null)
+    }
+
+    /// Checks that `entity` will exist when the call runs.
+    fn check_entity(&self, entity: Entity) -> Result<(), BrpError> {
+        let exists = self.spawned.contains(&entity) || self.world.get_entity(entity).is_ok();
+        if exists && !self.destroyed.contains(&entity) {
+            Ok(())
+        } else {
+            Err(BrpError::entity_not_found(entity))
+        }
+    }
+
+    /// Checks that the given serialized components can be inserted.
+    fn check_components(&self, components: HashMap<String, Value>) -> Result<(), BrpError> {
+        for component in components.keys() {
+            get_reflect_component(self.type_registry, component)
+                .map_err(BrpError::component_error)?;
+        }
+        deserialize_components(self.type_registry, components)
+            .map_err(BrpError::component_error)?;
+        Ok(())
+    }
+
+    /// Checks that `value` can be applied to the field at `path` of `reflected`.
+    fn check_field(&self, reflected: &dyn Reflect, path: &str, value: &Value) -> AnyhowResult<()> {
+        let field = reflected
+            .reflect_path(path)
+            .map_err(|err| anyhow!("{err}"))?;
+        let field_type = self
+            .type_registry
+            .get_with_type_path(field.reflect_type_path())
+            .ok_or_else(|| anyhow!("Unknown field type: `{}`", field.reflect_type_path()))?;
+        TypedReflectDeserializer::new(field_type, self.type_registry).deserialize(value)?;
+        Ok(())
+    }
+}
+
+/// A record of how to undo the effects of a single call of a `bevy/batch` request.
+enum BatchUndo {
+    /// Despawn an entity that was spawned by the batch.
+    Despawn(Entity),
+    /// Restore the previous values of components, removing those that were absent.
+    Components {
+        entity: Entity,
+        components: Vec<(String, Option<BatchSnapshot>)>,
+    },
+    /// Restore the previous parents of entities.
+    Parents(Vec<(Entity, Option<Entity>)>),
+    /// Restore the previous value of a resource, removing it if it was absent.
+    Resource {
+        resource: String,
+        value: Option<BatchSnapshot>,
+    },
+}
+
+/// The value of a component or resource before a call of a `bevy/batch` request, along with its
+/// change ticks.
+type BatchSnapshot = (Box<dyn PartialReflect>, ComponentTicks);
+
+/// Runs a single call of a `bevy/batch` request, recording how to undo it in `journal`.
+fn run_batch_request(
+    world: &mut World,
+    method: &str,
+    params: Option<Value>,
+    journal: &mut Vec<BatchUndo>,
+    pending_despawns: &mut Vec<Entity>,
+) -> BrpResult {
+    if method == BRP_DESTROY_METHOD {
+        let BrpDestroyParams { entity } = parse_some(params)?;
+        get_entity(world, entity)?;
+        pending_despawns.push(entity);
+        return Ok(Value::Null);
+    }
+
+    let Some(&RemoteMethodSystemId::Instant(system_id)) =
+        world.resource::<RemoteMethods>().get(method)
+    else {
+        return Err(BrpError {
+            code: error_codes::METHOD_NOT_FOUND,
+            message: format!("Method `{method}` not found"),
+            data: None,
+        });
+    };
+
+    if let Some(undo) = params
+        .clone()
+        .and_then(|params| capture_batch_undo(world, method, params))
+    {
+        journal.push(undo);
+    }
+
+    let result = world
+        .run_system_with(system_id, params)
+        .map_err(|error| BrpError {
+            code: error_codes::INTERNAL_ERROR,
+            message: format!("Failed to run method handler: {error}"),
+            data: None,
+        })??;
+
+    if method == BRP_SPAWN_METHOD {
+        let BrpSpawnResponse { entity } = parse(result.clone())?;
+        journal.push(BatchUndo::Despawn(entity));
+    }
+
+    Ok(result)
+}
+
+/// Captures the state that the call `method` is about to modify, so that it can be restored
+/// if a later call of the batch fails.
+///
+/// Returns `None` for read-only methods and for params that the handler will reject anyway.
+fn capture_batch_undo(world: &World, method: &str, params: Value) -> Option<BatchUndo> {
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+
+    let capture_components = |entity: Entity, paths: Vec<String>| {
+        let entity_ref = world.get_entity(entity).ok()?;
+        let components = paths
+            .into_iter()
+            .filter_map(|path| {
+                let registration = get_component_type_registration(&type_registry, &path).ok()?;
+                let reflect_component = registration.data::<ReflectComponent>()?;
+                let value = reflect_component.reflect(entity_ref).and_then(|value| {
+                    let component_id = world.components().get_id(registration.type_id())?;
+                    let ticks = entity_ref.get_change_ticks_by_id(component_id)?;
+                    Some((value.clone_value(), ticks))
+                });
+                Some((path, value))
+            })
+            .collect();
+        Some(BatchUndo::Components { entity, components })
+    };
+    let capture_resource = |resource: String| {
+        let registration = get_resource_type_registration(&type_registry, &resource).ok()?;
+        let reflect_resource = registration.data::<ReflectResource>()?;
+        let value = reflect_resource.reflect(world).and_then(|value| {
+            let component_id = world.components().get_resource_id(registration.type_id())?;
+            let ticks = world.get_resource_change_ticks_by_id(component_id)?;
+            Some((value.clone_value(), ticks))
+        });
+        Some(BatchUndo::Resource { resource, value })
+    };
+
+    match method {
+        BRP_INSERT_METHOD => {
+            let BrpInsertParams { entity, components } = parse(params).ok()?;
+            capture_components(entity, components.into_keys().collect())
+        }
+        BRP_REMOVE_METHOD => {
+            let BrpRemoveParams { entity, components } = parse(params).ok()?;
+            capture_components(entity, components)
+        }
+        BRP_MUTATE_COMPONENT_METHOD => {
+            let BrpMutateComponentParams {
+                entity, component, ..
+            } = parse(params).ok()?;
+            capture_components(entity, vec![component])
+        }
+        BRP_REPARENT_METHOD => {
+            let BrpReparentParams { entities, .. } = parse(params).ok()?;
+            let parents = entities
+                .into_iter()
+                .map(|entity| {
+                    let parent = world.get::<ChildOf>(entity).map(|child_of| child_of.parent);
+                    (entity, parent)
+                })
+                .collect();
+            Some(BatchUndo::Parents(parents))
+        }
+        BRP_INSERT_RESOURCE_METHOD => {
+            let BrpInsertResourceParams { resource, .. } = parse(params).ok()?;
+            capture_resource(resource)
+        }
+        BRP_REMOVE_RESOURCE_METHOD => {
+            let BrpRemoveResourceParams { resource } = parse(params).ok()?;
+            capture_resource(resource)
+        }
+        BRP_MUTATE_RESOURCE_METHOD => {
+            let BrpMutateResourceParams { resource, .. } = parse(params).ok()?;
+            capture_resource(resource)
+        }
+        _ => None,
+    }
+}
+
+/// Undoes the effects recorded in `journal`, most recent first.
+fn rollback_batch(world: &mut World, journal: Vec<BatchUndo>) {
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+
+    for undo in journal.into_iter().rev() {
+        match undo {
+            BatchUndo::Despawn(entity) => {
+                if let Ok(entity_world_mut) = world.get_entity_mut(entity) {
+                    entity_world_mut.despawn();
+                }
+            }
+            BatchUndo::Components { entity, components } => {
+                let Ok(mut entity_world_mut) = world.get_entity_mut(entity) else {
+                    continue;
+                };
+                for (path, value) in components {
+                    let Ok(registration) = get_component_type_registration(&type_registry, &path)
+                    else {
+                        continue;
+                    };
+                    let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+                        continue;
+                    };
+                    let Some((value, ticks)) = value else {
+                        reflect_component.remove(&mut entity_world_mut);
+                        continue;
+                    };
+                    // Immutable components can only be replaced, which runs their hooks.
+                    let mutable = entity_world_mut
+                        .world()
+                        .components()
+                        .get_id(registration.type_id())
+                        .and_then(|component_id| {
+                            entity_world_mut.world().components().get_info(component_id)
+                        })
+                        .is_some_and(ComponentInfo::mutable);
+                    if !mutable || !entity_world_mut.contains_type_id(registration.type_id()) {
+                        reflect_component.insert(&mut entity_world_mut, &*value, &type_registry);
+                    }
+                    if let Some(mut component) = mutable
+                        .then(|| reflect_component.reflect_mut(&mut entity_world_mut))
+                        .flatten()
+                    {
+                        component.bypass_change_detection().apply(&*value);
+                        component.set_last_changed(ticks.changed);
+                        component.set_last_added(ticks.added);
+                    }
+                }
+            }
+            BatchUndo::Parents(parents) => {
+                for (entity, parent) in parents {
+                    let Ok(mut entity_world_mut) = world.get_entity_mut(entity) else {
+                        continue;
+                    };
+                    match parent {
+                        Some(parent) => {
+                            entity_world_mut.insert(ChildOf { parent });
+                        }
+                        None => {
+                            entity_world_mut.remove::<ChildOf>();
+                        }
+                    }
+                }
+            }
+            BatchUndo::Resource { resource, value } => {
+                let Ok(reflect_resource) = get_reflect_resource(&type_registry, &resource) else {
+                    continue;
+                };
+                let Some((value, ticks)) = value else {
+                    reflect_resource.remove(world);
+                    continue;
+                };
+                if reflect_resource.reflect(&*world).is_none() {
+                    reflect_resource.insert(world, &*value, &type_registry);
+                }
+                if let Some(mut resource) = reflect_resource.reflect_mut(&mut *world) {
+                    resource.bypass_change_detection().apply(&*value);
+                    resource.set_last_changed(ticks.changed);
+                    resource.set_last_added(ticks.added);
+                }
+            }
+        }
+    }
+}
+
+/// Handles a `bevy/registry/schema` request (list all registry types in form of schema) coming from a client.
+pub fn export_registry_types(In(params): In<Option<Value>>, world: &World) -> BrpResult {
+    let filter: BrpJsonSchemaQueryFilter = match params {
+        None => Default::default(),
+        Some(params) => parse(params)?,
+    };
+
+    let types = world.resource::<AppTypeRegistry>();
+    let types = types.read();
+    let schemas =
+        export_registry_schemas(&types, &filter).collect::<HashMap<String, JsonSchemaBevyType>>();
+
+    serde_json::to_value(schemas).map_err(BrpError::internal)
+}
+
+/// Retrieves the schedule whose label has the given [`Debug`] representation, returning an
+/// error if it isn't present in the [`Schedules`].
+fn get_schedule<'w>(world: &'w World, label: &str) -> Result<&'w Schedule, BrpError> {
+    world
+        .get_resource::<Schedules>()
+        .into_iter()
+        .flat_map(Schedules::iter)
+        .find(|(schedule_label, _)| format!("{schedule_label:?}") == label)
+        .map(|(_, schedule)| schedule)
+        .ok_or_else(|| BrpError::schedule_not_found(label))
+}
+
+/// Returns the label of the schedule with the given [`Debug`] representation along with the
+/// [`NodeId`] of its system at the given index, returning an error if either isn't present.
+fn get_schedule_system(
+    world: &World,
+    label: &str,
+    system: usize,
+) -> Result<(InternedScheduleLabel, NodeId), BrpError> {
+    let schedule = get_schedule(world, label)?;
+    let node = NodeId::System(system);
+    if !schedule
+        .systems_with_conditions()
+        .any(|(node_id, ..)| node_id == node)
+    {
+        return Err(BrpError::system_not_found(system, label));
+    }
+    Ok((schedule.label(), node))
+}
+
+/// Mutably retrieves the [`Stepping`] resource, returning an error if stepping was never
+/// enabled.
+fn get_stepping_mut(world: &mut World) -> Result<Mut<'_, Stepping>, BrpError> {
+    world
+        .get_resource_mut::<Stepping>()
+        .ok_or_else(|| BrpError::resource_not_present(core::any::type_name::<Stepping>()))
 }
 
 /// Immutably retrieves an entity from the [`World`], returning an error if the
@@ -1693,6 +2727,21 @@ fn deserialize_resource(
     Ok(reflected)
 }
 
+/// Given an event path and an associated serialized value (`value`), return the
+/// deserialized value.
+fn deserialize_event(
+    type_registry: &TypeRegistry,
+    event_path: &str,
+    value: Value,
+) -> AnyhowResult<Box<dyn PartialReflect>> {
+    let event_type = get_event_type_registration(type_registry, event_path)?;
+    let reflected: Box<dyn PartialReflect> =
+        TypedReflectDeserializer::new(event_type, type_registry)
+            .deserialize(&value)
+            .map_err(|err| anyhow!("{event_path} is invalid: {err}"))?;
+    Ok(reflected)
+}
+
 /// Given a collection `reflect_components` of reflected component values, insert them into
 /// the given entity (`entity_world_mut`).
 fn insert_reflected_components(
@@ -1757,6 +2806,30 @@ fn get_resource_type_registration<'r>(
         .ok_or_else(|| anyhow!("Unknown resource type: `{}`", resource_path))
 }
 
+/// Given an event's type path, return the associated [`ReflectEvent`] from the given
+/// `type_registry` if possible.
+fn get_reflect_event<'r>(
+    type_registry: &'r TypeRegistry,
+    event_path: &str,
+) -> AnyhowResult<&'r ReflectEvent> {
+    let event_registration = get_event_type_registration(type_registry, event_path)?;
+
+    event_registration
+        .data::<ReflectEvent>()
+        .ok_or_else(|| anyhow!("Event `{}` isn't reflectable", event_path))
+}
+
+/// Given an event's type path, return the associated [`TypeRegistration`] from the given
+/// `type_registry` if possible.
+fn get_event_type_registration<'r>(
+    type_registry: &'r TypeRegistry,
+    event_path: &str,
+) -> AnyhowResult<&'r TypeRegistration> {
+    type_registry
+        .get_with_type_path(event_path)
+        .ok_or_else(|| anyhow!("Unknown event type: `{}`", event_path))
+}
+
 #[cfg(test)]
 mod tests {
     /// A generic function that tests serialization and deserialization of any type
@@ -1778,8 +2851,18 @@ mod tests {
         );
     }
     use super::*;
-    use bevy_ecs::{component::Component, resource::Resource};
-    use bevy_reflect::Reflect;
+    use crate::{BrpMessage, BrpSender, RemotePlugin};
+    use bevy_app::{App, Update};
+    use bevy_ecs::schedule::{IntoSystemConfigs, IntoSystemSetConfigs, SystemSet};
+    use bevy_ecs::{
+        component::Component,
+        event::{Event, EventRegistry, Events, ShouldUpdateEvents},
+        observer::Trigger,
+        resource::Resource,
+        system::{Query, ResMut},
+        world::OnInsert,
+    };
+    use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 
     #[test]
     fn serialization_tests() {
@@ -1787,6 +2870,7 @@ mod tests {
             components: Default::default(),
             entity: Entity::from_raw(0),
             has: Default::default(),
+            ticks: Default::default(),
         });
         test_serialize_deserialize(BrpListWatchingResponse::default());
         test_serialize_deserialize(BrpQuery::default());
@@ -1803,192 +2887,578 @@ mod tests {
         });
     }
 
+    #[derive(Component, Reflect, Default, PartialEq, Debug, Serialize, Deserialize)]
+    #[reflect(Component, Serialize, Deserialize)]
+    struct Health {
+        current: u32,
+    }
+
+    fn remote_test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(RemotePlugin::default());
+        app.world_mut().init_resource::<AppTypeRegistry>();
+        app.world()
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Health>();
+        app
+    }
+
     #[test]
-    fn reflect_export_struct() {
-        #[derive(Reflect, Resource, Default, Deserialize, Serialize)]
-        #[reflect(Resource, Default, Serialize, Deserialize)]
-        struct Foo {
-            a: f32,
-            b: Option<f32>,
-        }
+    fn batch_commits_and_resolves_spawned_entities() {
+        let mut app = remote_test_app();
+        let world = app.world_mut();
+        let doomed = world.spawn(Health { current: 1 }).id();
+
+        let batch = world.register_system(process_remote_batch_request);
+        let results = world
+            .run_system_with(
+                batch,
+                Some(json!({
+                    "requests": [
+                        { "method": BRP_SPAWN_METHOD, "params": { "components": {} } },
+                        {
+                            "method": BRP_INSERT_METHOD,
+                            "params": {
+                                "entity": { "batch_entity": 0 },
+                                "components": {
+                                    "bevy_remote::builtin_methods::tests::Health": { "current": 7 }
+                                }
+                            }
+                        },
+                        { "method": BRP_DESTROY_METHOD, "params": { "entity": doomed } },
+                    ]
+                })),
+            )
+            .unwrap()
+            .expect("batch should succeed");
+
+        let BrpSpawnResponse { entity } = parse(results[0].clone()).unwrap();
+        assert_eq!(world.get::<Health>(entity), Some(&Health { current: 7 }));
+        assert!(world.get_entity(doomed).is_err());
+    }
 
-        let atr = AppTypeRegistry::default();
-        {
-            let mut register = atr.write();
-            register.register::<Foo>();
-        }
-        let type_registry = atr.read();
-        let foo_registration = type_registry
-            .get(TypeId::of::<Foo>())
-            .expect("SHOULD BE REGISTERED")
-            .clone();
-        let (_, schema) = export_type(&foo_registration);
-        println!("{}", &serde_json::to_string_pretty(&schema).unwrap());
+    #[test]
+    fn batch_rolls_back_on_error() {
+        let mut app = remote_test_app();
+        let world = app.world_mut();
+        let existing = world.spawn(Health { current: 10 }).id();
+        let missing = world.spawn_empty().id();
+        world.despawn(missing);
+
+        let batch = world.register_system(process_remote_batch_request);
+        let entity_count = world.entities().len();
+        let error = world
+            .run_system_with(
+                batch,
+                Some(json!({
+                    "requests": [
+                        { "method": BRP_SPAWN_METHOD, "params": { "components": {} } },
+                        {
+                            "method": BRP_MUTATE_COMPONENT_METHOD,
+                            "params": {
+                                "entity": existing,
+                                "component": "bevy_remote::builtin_methods::tests::Health",
+                                "path": ".current",
+                                "value": 3
+                            }
+                        },
+                        { "method": BRP_DESTROY_METHOD, "params": { "entity": existing } },
+                        { "method": BRP_REMOVE_METHOD, "params": { "entity": missing, "components": [] } },
+                    ]
+                })),
+            )
+            .unwrap()
+            .expect_err("batch should fail");
+
+        assert_eq!(error.code, error_codes::ENTITY_NOT_FOUND);
+        assert_eq!(error.data, Some(json!({ "index": 3, "error": null })));
+        assert_eq!(world.get::<Health>(existing), Some(&Health { current: 10 }));
+        assert_eq!(world.entities().len(), entity_count);
+    }
 
-        assert!(
-            !schema.reflect_types.contains(&"Component".to_owned()),
-            "Should not be a component"
-        );
-        assert!(
-            schema.reflect_types.contains(&"Resource".to_owned()),
-            "Should be a resource"
-        );
-        let _ = schema.properties.get("a").expect("Missing `a` field");
-        let _ = schema.properties.get("b").expect("Missing `b` field");
-        assert!(
-            schema.required.contains(&"a".to_owned()),
-            "Field a should be required"
-        );
-        assert!(
-            !schema.required.contains(&"b".to_owned()),
-            "Field b should not be required"
-        );
+    #[test]
+    fn batch_sees_destroyed_entities_as_missing() {
+        let mut app = remote_test_app();
+        let world = app.world_mut();
+        let entity = world.spawn(Health { current: 10 }).id();
+
+        let batch = world.register_system(process_remote_batch_request);
+        let error = world
+            .run_system_with(
+                batch,
+                Some(json!({
+                    "requests": [
+                        { "method": BRP_DESTROY_METHOD, "params": { "entity": entity } },
+                        {
+                            "method": BRP_INSERT_METHOD,
+                            "params": {
+                                "entity": entity,
+                                "components": {
+                                    "bevy_remote::builtin_methods::tests::Health": { "current": 7 }
+                                }
+                            }
+                        },
+                    ]
+                })),
+            )
+            .unwrap()
+            .expect_err("the entity is destroyed by the first call");
+
+        assert_eq!(error.code, error_codes::ENTITY_NOT_FOUND);
+        assert_eq!(error.data, Some(json!({ "index": 1, "error": null })));
+        assert_eq!(world.get::<Health>(entity), Some(&Health { current: 10 }));
     }
 
     #[test]
-    fn reflect_export_enum() {
-        #[derive(Reflect, Component, Default, Deserialize, Serialize)]
-        #[reflect(Component, Default, Serialize, Deserialize)]
-        enum EnumComponent {
-            ValueOne(i32),
-            ValueTwo {
-                test: i32,
+    fn batch_rollback_restores_values_in_place() {
+        #[derive(Resource, Default)]
+        struct Inserts(usize);
+
+        let mut app = remote_test_app();
+        let world = app.world_mut();
+        let existing = world.spawn(Health { current: 10 }).id();
+        let other = world.spawn_empty().id();
+        world.init_resource::<Inserts>();
+        world.add_observer(
+            |_: Trigger<OnInsert, Health>, mut inserts: ResMut<Inserts>| {
+                inserts.0 += 1;
             },
-            #[default]
-            NoValue,
-        }
-
-        let atr = AppTypeRegistry::default();
-        {
-            let mut register = atr.write();
-            register.register::<EnumComponent>();
-        }
-        let type_registry = atr.read();
-        let foo_registration = type_registry
-            .get(TypeId::of::<EnumComponent>())
-            .expect("SHOULD BE REGISTERED")
-            .clone();
-        let (_, schema) = export_type(&foo_registration);
-        assert!(
-            schema.reflect_types.contains(&"Component".to_owned()),
-            "Should be a component"
-        );
-        assert!(
-            !schema.reflect_types.contains(&"Resource".to_owned()),
-            "Should not be a resource"
         );
-        assert!(schema.properties.is_empty(), "Should not have any field");
-        assert!(schema.one_of.len() == 3, "Should have 3 possible schemas");
+        let ticks = world.entity(existing).get_change_ticks::<Health>().unwrap();
+        world.increment_change_tick();
+
+        // The last call only fails once the component inserted by the call before it exists.
+        let batch = world.register_system(process_remote_batch_request);
+        let error = world
+            .run_system_with(
+                batch,
+                Some(json!({
+                    "requests": [
+                        {
+                            "method": BRP_INSERT_METHOD,
+                            "params": {
+                                "entity": existing,
+                                "components": {
+                                    "bevy_remote::builtin_methods::tests::Health": { "current": 5 }
+                                }
+                            }
+                        },
+                        {
+                            "method": BRP_INSERT_METHOD,
+                            "params": {
+                                "entity": other,
+                                "components": {
+                                    "bevy_remote::builtin_methods::tests::Health": { "current": 1 }
+                                }
+                            }
+                        },
+                        {
+                            "method": BRP_MUTATE_COMPONENT_METHOD,
+                            "params": {
+                                "entity": other,
+                                "component": "bevy_remote::builtin_methods::tests::Health",
+                                "path": ".missing",
+                                "value": 3
+                            }
+                        },
+                    ]
+                })),
+            )
+            .unwrap()
+            .expect_err("batch should fail");
+        assert_eq!(error.data.unwrap()["index"], json!(2));
+
+        // Only the two calls inserted `Health`: the rollback didn't insert it again.
+        assert_eq!(world.resource::<Inserts>().0, 2);
+        assert_eq!(world.get::<Health>(existing), Some(&Health { current: 10 }));
+        let restored = world.entity(existing).get_change_ticks::<Health>().unwrap();
+        assert_eq!(restored.added, ticks.added);
+        assert_eq!(restored.changed, ticks.changed);
+        assert!(world.get::<Health>(other).is_none());
     }
 
     #[test]
-    fn reflect_export_struct_without_reflect_types() {
-        #[derive(Reflect, Component, Default, Deserialize, Serialize)]
-        enum EnumComponent {
-            ValueOne(i32),
-            ValueTwo {
-                test: i32,
-            },
-            #[default]
-            NoValue,
-        }
+    fn batch_calls_are_access_controlled() {
+        use crate::access_control::{RateLimit, RemoteAccessControl};
+        use core::time::Duration;
+
+        let mut app = remote_test_app();
+        let world = app.world_mut();
+        let entity = world.spawn(Health { current: 10 }).id();
+        let batch = world.register_system(process_remote_batch_request);
+        let run_batch = |world: &mut World, access_control: RemoteAccessControl| {
+            world.insert_resource(access_control);
+            world
+                .run_system_with(
+                    batch,
+                    Some(json!({
+                        "requests": [
+                            { "method": BRP_GET_METHOD, "params": { "entity": entity, "components": [] } },
+                            { "method": BRP_DESTROY_METHOD, "params": { "entity": entity } },
+                        ]
+                    })),
+                )
+                .unwrap()
+        };
 
-        let atr = AppTypeRegistry::default();
-        {
-            let mut register = atr.write();
-            register.register::<EnumComponent>();
-        }
-        let type_registry = atr.read();
-        let foo_registration = type_registry
-            .get(TypeId::of::<EnumComponent>())
-            .expect("SHOULD BE REGISTERED")
-            .clone();
-        let (_, schema) = export_type(&foo_registration);
-        assert!(
-            !schema.reflect_types.contains(&"Component".to_owned()),
-            "Should not be a component"
+        // Allowing `bevy/batch` doesn't allow the methods it calls.
+        let allowed = RemoteAccessControl::default()
+            .allow_method(BRP_BATCH_METHOD)
+            .allow_method(BRP_GET_METHOD);
+        let error = run_batch(world, allowed).expect_err("destroy is not allowed");
+        assert_eq!(error.code, error_codes::METHOD_NOT_ALLOWED);
+        assert_eq!(error.data, Some(json!({ "index": 1 })));
+        assert!(world.get_entity(entity).is_ok());
+
+        let read_only = RemoteAccessControl::default()
+            .read_only(true)
+            .with_read_only_method(BRP_BATCH_METHOD);
+        let error = run_batch(world, read_only).expect_err("destroy is not read-only");
+        assert_eq!(error.code, error_codes::METHOD_NOT_ALLOWED);
+        assert!(world.get_entity(entity).is_ok());
+
+        let rate_limited = RemoteAccessControl::default().with_rate_limit(
+            BRP_DESTROY_METHOD,
+            RateLimit::new(0, Duration::from_secs(1)),
         );
+        let error = run_batch(world, rate_limited).expect_err("destroy is rate limited");
+        assert_eq!(error.code, error_codes::RATE_LIMITED);
+        assert!(world.get_entity(entity).is_ok());
+
+        assert!(run_batch(world, RemoteAccessControl::default()).is_ok());
+        assert!(world.get_entity(entity).is_err());
+    }
+
+    #[test]
+    fn predicates_compare_integers_exactly() {
+        let eq = BrpQueryPredicateOperation::Eq(json!(u64::MAX));
+        assert!(eq.matches(&json!(u64::MAX)));
+        assert!(!eq.matches(&json!(u64::MAX - 1)));
         assert!(
-            !schema.reflect_types.contains(&"Resource".to_owned()),
-            "Should not be a resource"
+            BrpQueryPredicateOperation::Gt(json!(i64::MAX)).matches(&json!(i64::MAX as u64 + 1))
         );
-        assert!(schema.properties.is_empty(), "Should not have any field");
-        assert!(schema.one_of.len() == 3, "Should have 3 possible schemas");
+        assert!(BrpQueryPredicateOperation::Lt(json!(-1)).matches(&json!(i64::MIN)));
+        assert!(BrpQueryPredicateOperation::Lt(json!(1.5)).matches(&json!(1)));
     }
 
     #[test]
-    fn reflect_export_tuple_struct() {
-        #[derive(Reflect, Component, Default, Deserialize, Serialize)]
-        #[reflect(Component, Default, Serialize, Deserialize)]
-        struct TupleStructType(usize, i32);
-
-        let atr = AppTypeRegistry::default();
-        {
-            let mut register = atr.write();
-            register.register::<TupleStructType>();
+    fn query_predicates_skip_unresolved_paths() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        enum Shape {
+            Circle { radius: f32 },
+            Square,
         }
-        let type_registry = atr.read();
-        let foo_registration = type_registry
-            .get(TypeId::of::<TupleStructType>())
-            .expect("SHOULD BE REGISTERED")
-            .clone();
-        let (_, schema) = export_type(&foo_registration);
-        println!("{}", &serde_json::to_string_pretty(&schema).unwrap());
-        assert!(
-            schema.reflect_types.contains(&"Component".to_owned()),
-            "Should be a component"
+
+        let mut app = remote_test_app();
+        app.world()
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Shape>();
+        let world = app.world_mut();
+        let circle = world.spawn(Shape::Circle { radius: 2.0 }).id();
+        world.spawn(Shape::Square);
+
+        let query = world.register_system(process_remote_query_request);
+        let result = world
+            .run_system_with(
+                query,
+                Some(json!({
+                    "data": {},
+                    "filter": { "predicates": [{
+                        "component": "bevy_remote::builtin_methods::tests::Shape",
+                        "path": ".radius",
+                        "gt": 1.0,
+                    }] },
+                })),
+            )
+            .unwrap()
+            .expect("query should succeed");
+        let rows: BrpQueryResponse = parse(result).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].entity, circle);
+    }
+
+    #[test]
+    fn query_filters_by_change_ticks_and_predicates() {
+        let mut app = remote_test_app();
+        let world = app.world_mut();
+        let low = world.spawn(Health { current: 5 }).id();
+        let high = world.spawn(Health { current: 20 }).id();
+        let since_tick = world.change_tick().get();
+        world.increment_change_tick();
+        world.get_mut::<Health>(high).unwrap().current = 21;
+
+        let query = world.register_system(process_remote_query_request);
+        let mut run_query = |filter: Value| -> BrpQueryResponse {
+            let result = world
+                .run_system_with(query, Some(json!({ "data": {}, "filter": filter })))
+                .unwrap()
+                .expect("query should succeed");
+            parse(result).unwrap()
+        };
+
+        let health = "bevy_remote::builtin_methods::tests::Health";
+        let rows = run_query(json!({
+            "predicates": [{ "component": health, "path": ".current", "lt": 10 }]
+        }));
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].entity, low);
+
+        let rows = run_query(json!({
+            "predicates": [
+                { "component": health, "path": ".current", "gte": 5 },
+                { "component": health, "path": ".current", "ne": 21.0 },
+            ]
+        }));
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].entity, low);
+
+        let rows = run_query(json!({ "changed": [health], "since_tick": since_tick }));
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].entity, high);
+        assert!(rows[0].ticks[health].changed > since_tick);
+    }
+
+    #[test]
+    fn schedule_graph_reports_systems_and_edges() {
+        fn first() {}
+        fn second() {}
+
+        let mut app = remote_test_app();
+        app.add_systems(Update, (first, second).chain());
+        app.update();
+
+        let world = app.world_mut();
+        let graph = world.register_system(process_remote_schedule_graph_request);
+        let result = world
+            .run_system_with(graph, Some(json!({ "schedule": "Update" })))
+            .unwrap()
+            .expect("graph should succeed");
+        let response: BrpScheduleGraphResponse = parse(result).unwrap();
+
+        let id_of = |suffix: &str| {
+            response
+                .systems
+                .iter()
+                .find(|system| system.name.ends_with(suffix))
+                .map(|system| BrpScheduleNodeId::System(system.id))
+                .unwrap()
+        };
+        assert!(response
+            .dependencies
+            .contains(&(id_of("::first"), id_of("::second"))));
+
+        let error = world
+            .run_system_with(graph, Some(json!({ "schedule": "Missing" })))
+            .unwrap()
+            .expect_err("graph of a missing schedule should fail");
+        assert_eq!(error.code, error_codes::SCHEDULE_NOT_FOUND);
+    }
+
+    #[test]
+    fn schedule_graph_response_shape() {
+        #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Physics;
+
+        fn gravity() {}
+
+        let mut app = remote_test_app();
+        app.configure_sets(Update, Physics.run_if(|| true));
+        app.add_systems(Update, gravity.in_set(Physics));
+        app.update();
+
+        let world = app.world_mut();
+        let graph = world.register_system(process_remote_schedule_graph_request);
+        let result = world
+            .run_system_with(graph, Some(json!({ "schedule": "Update" })))
+            .unwrap()
+            .expect("graph should succeed");
+
+        let find = |nodes: &str, name: &dyn Fn(&str) -> bool| {
+            result[nodes]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|node| name(node["name"].as_str().unwrap()))
+                .cloned()
+                .unwrap()
+        };
+        let gravity = find("systems", &|name| name.ends_with("::gravity"));
+        let physics = find("sets", &|name| name == "Physics");
+        // The sets created for each system type are left out.
+        assert_eq!(result["sets"].as_array().unwrap().len(), 1);
+
+        // Nodes without conditions leave them out.
+        assert_eq!(
+            gravity,
+            json!({ "id": gravity["id"], "name": gravity["name"] })
         );
-        assert!(
-            !schema.reflect_types.contains(&"Resource".to_owned()),
-            "Should not be a resource"
+        assert_eq!(physics["conditions"].as_array().unwrap().len(), 1);
+
+        assert_eq!(
+            result["hierarchy"],
+            json!([[{ "set": physics["id"] }, { "system": gravity["id"] }]])
         );
-        assert!(schema.properties.is_empty(), "Should not have any field");
-        assert!(schema.prefix_items.len() == 2, "Should have 2 prefix items");
+        assert!(result["dependencies"].is_array());
+        assert!(result["ambiguities"].is_array());
     }
 
     #[test]
-    fn reflect_export_serialization_check() {
-        #[derive(Reflect, Resource, Default, Deserialize, Serialize)]
-        #[reflect(Resource, Default)]
-        struct Foo {
-            a: f32,
+    fn stepping_enable_and_breakpoints() {
+        fn stepped() {}
+
+        let mut app = remote_test_app();
+        app.add_systems(Update, stepped);
+        app.update();
+
+        let world = app.world_mut();
+        let enable = world.register_system(process_remote_stepping_enable_request);
+        let result = world
+            .run_system_with(enable, Some(json!({ "schedules": ["Update"] })))
+            .unwrap();
+        if cfg!(feature = "bevy_debug_stepping") {
+            result.expect("enable should succeed");
+            assert!(world.contains_resource::<Stepping>());
+        } else {
+            let error = result.expect_err("enable should fail without the stepping feature");
+            assert_eq!(error.code, error_codes::STEPPING_UNAVAILABLE);
+            world.init_resource::<Stepping>();
         }
 
-        let atr = AppTypeRegistry::default();
-        {
-            let mut register = atr.write();
-            register.register::<Foo>();
+        let set_breakpoint = world.register_system(process_remote_stepping_set_breakpoint_request);
+        world
+            .run_system_with(
+                set_breakpoint,
+                Some(json!({ "schedule": "Update", "system": 0 })),
+            )
+            .unwrap()
+            .expect("setting a breakpoint should succeed");
+        let error = world
+            .run_system_with(
+                set_breakpoint,
+                Some(json!({ "schedule": "Update", "system": 99 })),
+            )
+            .unwrap()
+            .expect_err("setting a breakpoint on a missing system should fail");
+        assert_eq!(error.code, error_codes::SYSTEM_NOT_FOUND);
+    }
+
+    #[test]
+    fn send_trigger_and_watch_events() {
+        #[derive(Event, Reflect, Debug, PartialEq, Serialize, Deserialize)]
+        #[reflect(Event, Serialize, Deserialize)]
+        struct Damage {
+            amount: u32,
         }
-        let type_registry = atr.read();
-        let foo_registration = type_registry
-            .get(TypeId::of::<Foo>())
-            .expect("SHOULD BE REGISTERED")
-            .clone();
-        let (_, schema) = export_type(&foo_registration);
-        let schema_as_value = serde_json::to_value(&schema).expect("Should serialize");
-        let value = json!({
-          "shortPath": "Foo",
-          "typePath": "bevy_remote::builtin_methods::tests::Foo",
-          "modulePath": "bevy_remote::builtin_methods::tests",
-          "crateName": "bevy_remote",
-          "reflectTypes": [
-            "Resource",
-            "Default",
-          ],
-          "kind": "Struct",
-          "type": "object",
-          "additionalProperties": false,
-          "properties": {
-            "a": {
-              "type": {
-                "$ref": "#/$defs/f32"
-              }
-            },
-          },
-          "required": [
-            "a"
-          ]
+
+        let mut app = remote_test_app();
+        app.add_event::<Damage>();
+        app.world()
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Damage>();
+        app.add_observer(|trigger: Trigger<Damage>, mut query: Query<&mut Health>| {
+            if let Ok(mut health) = query.get_mut(trigger.target()) {
+                health.current -= trigger.event().amount;
+            }
         });
-        assert_eq!(schema_as_value, value);
+
+        let world = app.world_mut();
+        let target = world.spawn(Health { current: 10 }).id();
+        let event_path = "bevy_remote::builtin_methods::tests::Damage";
+
+        let trigger = world.register_system(process_remote_trigger_event_request);
+        world
+            .run_system_with(
+                trigger,
+                Some(json!({ "event": event_path, "value": { "amount": 3 }, "targets": [target] })),
+            )
+            .unwrap()
+            .expect("trigger should succeed");
+        assert_eq!(world.get::<Health>(target), Some(&Health { current: 7 }));
+
+        let watch = world.register_system(process_remote_events_watching_request);
+        let watch_params = Some(json!({ "event": event_path }));
+        let response = world.run_system_with(watch, watch_params.clone()).unwrap();
+        assert_eq!(response.unwrap(), None);
+
+        let send = world.register_system(process_remote_send_event_request);
+        for amount in [1, 2] {
+            world
+                .run_system_with(
+                    send,
+                    Some(json!({ "event": event_path, "value": { "amount": amount } })),
+                )
+                .unwrap()
+                .expect("send should succeed");
+        }
+        let response = world.run_system_with(watch, watch_params.clone()).unwrap();
+        assert_eq!(
+            response.unwrap(),
+            Some(json!([{ "amount": 1 }, { "amount": 2 }]))
+        );
+
+        world.resource_mut::<Events<Damage>>().update();
+        let response = world.run_system_with(watch, watch_params).unwrap();
+        assert_eq!(response.unwrap(), None);
+
+        let error = world
+            .run_system_with(
+                send,
+                Some(json!({ "event": "bevy_remote::Unknown", "value": {} })),
+            )
+            .unwrap()
+            .expect_err("sending an unknown event should fail");
+        assert_eq!(error.code, error_codes::EVENT_ERROR);
+    }
+
+    #[test]
+    fn watched_events_are_reported_once() {
+        #[derive(Event, Reflect, Debug)]
+        #[reflect(Event)]
+        struct Ping(u32);
+
+        let mut app = remote_test_app();
+        app.add_event::<Ping>();
+        app.world()
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Ping>();
+        app.update();
+        // Events are only updated once a fixed timestep has run, which never happens here.
+        app.world_mut()
+            .resource_mut::<EventRegistry>()
+            .should_update = ShouldUpdateEvents::Waiting;
+
+        let watchers: Vec<_> = (0..2)
+            .map(|_| {
+                let (sender, receiver) = async_channel::bounded(8);
+                app.world()
+                    .resource::<BrpSender>()
+                    .force_send(BrpMessage::new(
+                        BRP_EVENTS_AND_WATCH_METHOD,
+                        Some(json!({ "event": "bevy_remote::builtin_methods::tests::Ping" })),
+                        sender,
+                    ))
+                    .unwrap();
+                receiver
+            })
+            .collect();
+        app.update();
+
+        for ping in [1, 2] {
+            app.world_mut().send_event(Ping(ping));
+            app.update();
+            for receiver in &watchers {
+                assert_eq!(receiver.try_recv().unwrap().unwrap(), json!([ping]));
+            }
+            app.update();
+            app.update();
+            for receiver in &watchers {
+                assert!(receiver.try_recv().is_err());
+            }
+        }
     }
 }
@@ -5,37 +5,76 @@
 //!
 //! Clients are expected to `POST` JSON requests to the root URL; see the `client`
 //! example for a trivial example of use.
+//!
+//! If the [`RemoteAccessControl`] requires a token, clients must send it in an
+//! `Authorization: Bearer <token>` header. This includes opening a session and
+//! `bevy/unsubscribe`, which are handled by the transport itself and check the token of the
+//! current [`RemoteAccessControl`].
+//!
+//! ## Subscriptions
+//!
+//! By default, every watching request (`bevy/get+watch`, `bevy/list+watch`, etc.) keeps its own
+//! HTTP response open and streams updates through it as [Server-Sent Events]. To follow many
+//! watches over a single connection, a client can instead open a *session*:
+//!
+//! 1. `GET` the [`SESSION_PATH`] (`/session`). The response is an event stream whose first event
+//!    is `{ "session": "<id>" }`. Session IDs are random strings, so they can't be guessed by
+//!    other clients.
+//! 2. `POST` requests as usual, with the [`SESSION_HEADER`] (`brp-session`) set to that id.
+//!    Watching requests then respond immediately with `{ "subscription": <id> }`, and can be
+//!    mixed with other requests in a batch.
+//! 3. Updates for every subscription of the session are sent on the session's event stream.
+//!    Each one is a regular response object with an extra `subscription` field.
+//! 4. Stop a subscription by sending a [`BRP_UNSUBSCRIBE_METHOD`] (`bevy/unsubscribe`) request
+//!    with the `subscription` id as a param. Closing the event stream ends every subscription
+//!    of the session.
+//!
+//! A session holds at most one pending update per subscription. If its client reads the event
+//! stream slower than updates arrive, a new update of a subscription is merged into the one
+//! that is still waiting to be sent: for every field present in both, arrays are concatenated
+//! (skipping items already present), the entries of objects are added or replaced, and any
+//! other value is replaced. An update that isn't an object, like an error, replaces the pending
+//! update as a whole. This way, a slow client receives fewer, larger updates instead of losing
+//! its session.
+//!
+//! [Server-Sent Events]: https://html.spec.whatwg.org/multipage/server-sent-events.html
 
 #![cfg(not(target_family = "wasm"))]
 
 use crate::{
+    access_control::{self, RemoteAccessControl},
     error_codes, BrpBatch, BrpError, BrpMessage, BrpRequest, BrpResponse, BrpResult, BrpSender,
+    RemoteLast, RemoteSet,
 };
 use anyhow::Result as AnyhowResult;
 use async_channel::{Receiver, Sender};
 use async_io::Async;
 use bevy_app::{App, Plugin, Startup};
 use bevy_ecs::resource::Resource;
+use bevy_ecs::schedule::IntoSystemConfigs;
 use bevy_ecs::system::Res;
 use bevy_tasks::{futures_lite::StreamExt, IoTaskPool};
 use core::{
     convert::Infallible,
     net::{IpAddr, Ipv4Addr},
     pin::Pin,
-    task::{Context, Poll},
+    task::{ready, Context, Poll},
 };
 use http_body_util::{BodyExt as _, Full};
 use hyper::{
     body::{Body, Bytes, Frame, Incoming},
     header::{HeaderName, HeaderValue},
     server::conn::http1,
-    service, Request, Response,
+    service, Request, Response, StatusCode,
 };
-use serde_json::Value;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use smol_hyper::rt::{FuturesIo, SmolTimer};
 use std::{
     collections::HashMap,
+    hash::{BuildHasher, RandomState},
     net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex, RwLock},
 };
 
 /// The default port that Bevy will listen on.
@@ -46,6 +85,30 @@ pub const DEFAULT_PORT: u16 = 15702;
 /// The default host address that Bevy will use for its server.
 pub const DEFAULT_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
 
+/// The path that clients `GET` to open a session event stream.
+///
+/// See the [module-level documentation](self) for details on sessions.
+pub const SESSION_PATH: &str = "/session";
+
+/// The header that associates a request with a session.
+///
+/// See the [module-level documentation](self) for details on sessions.
+pub const SESSION_HEADER: &str = "brp-session";
+
+/// The method path for a `bevy/unsubscribe` request.
+///
+/// This method is handled by the HTTP transport itself and requires the [`SESSION_HEADER`].
+pub const BRP_UNSUBSCRIBE_METHOD: &str = "bevy/unsubscribe";
+
+/// `bevy/unsubscribe`: Stops a subscription of the session.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpUnsubscribeParams {
+    /// The ID of the subscription to stop.
+    pub subscription: u64,
+}
+
 /// A struct that holds a collection of HTTP headers.
 ///
 /// This struct is used to store a set of HTTP headers as key-value pairs, where the keys are
@@ -120,7 +183,12 @@ impl Plugin for RemoteHttpPlugin {
         app.insert_resource(HostAddress(self.address))
             .insert_resource(HostPort(self.port))
             .insert_resource(HostHeaders(self.headers.clone()))
-            .add_systems(Startup, start_http_server);
+            .init_resource::<HostToken>()
+            .add_systems(Startup, (sync_host_token, start_http_server).chain())
+            .add_systems(
+                RemoteLast,
+                sync_host_token.before(RemoteSet::ProcessRequests),
+            );
     }
 }
 
@@ -194,12 +262,39 @@ pub struct HostPort(pub u16);
 #[derive(Debug, Resource)]
 struct HostHeaders(pub Headers);
 
+/// A resource sharing the bearer token required by the [`RemoteAccessControl`] with the server,
+/// which checks it for the requests handled by the transport itself.
+#[derive(Debug, Default, Clone, Resource)]
+struct HostToken(Arc<RwLock<Option<String>>>);
+
+impl HostToken {
+    fn get(&self) -> Option<String> {
+        self.0.read().unwrap().clone()
+    }
+
+    fn set(&self, token: Option<&str>) {
+        if self.0.read().unwrap().as_deref() != token {
+            *self.0.write().unwrap() = token.map(ToOwned::to_owned);
+        }
+    }
+}
+
+/// A system that keeps the [`HostToken`] in sync with the [`RemoteAccessControl`] resource.
+fn sync_host_token(access_control: Option<Res<RemoteAccessControl>>, token: Res<HostToken>) {
+    token.set(
+        access_control
+            .as_ref()
+            .and_then(|access_control| access_control.token()),
+    );
+}
+
 /// A system that starts up the Bevy Remote Protocol HTTP server.
 fn start_http_server(
     request_sender: Res<BrpSender>,
     address: Res<HostAddress>,
     remote_port: Res<HostPort>,
     headers: Res<HostHeaders>,
+    token: Res<HostToken>,
 ) {
     IoTaskPool::get()
         .spawn(server_main(
@@ -207,6 +302,7 @@ fn start_http_server(
             remote_port.0,
             request_sender.clone(),
             headers.0.clone(),
+            token.clone(),
         ))
         .detach();
 }
@@ -217,11 +313,13 @@ async fn server_main(
     port: u16,
     request_sender: Sender<BrpMessage>,
     headers: Headers,
+    token: HostToken,
 ) -> AnyhowResult<()> {
     listen(
         Async::<TcpListener>::bind((address, port))?,
         &request_sender,
         &headers,
+        token,
     )
     .await
 }
@@ -230,15 +328,21 @@ async fn listen(
     listener: Async<TcpListener>,
     request_sender: &Sender<BrpMessage>,
     headers: &Headers,
+    token: HostToken,
 ) -> AnyhowResult<()> {
+    let sessions = BrpSessions {
+        token,
+        ..Default::default()
+    };
     loop {
         let (client, _) = listener.accept().await?;
 
         let request_sender = request_sender.clone();
         let headers = headers.clone();
+        let sessions = sessions.clone();
         IoTaskPool::get()
             .spawn(async move {
-                let _ = handle_client(client, request_sender, headers).await;
+                let _ = handle_client(client, request_sender, headers, sessions).await;
             })
             .detach();
     }
@@ -248,13 +352,14 @@ async fn handle_client(
     client: Async<TcpStream>,
     request_sender: Sender<BrpMessage>,
     headers: Headers,
+    sessions: BrpSessions,
 ) -> AnyhowResult<()> {
     http1::Builder::new()
         .timer(SmolTimer::new())
         .serve_connection(
             FuturesIo::new(client),
             service::service_fn(|request| {
-                process_request_batch(request, &request_sender, &headers)
+                process_request(request, &request_sender, &headers, &sessions)
             }),
         )
         .await?;
@@ -262,19 +367,92 @@ async fn handle_client(
     Ok(())
 }
 
+/// A helper function for the Bevy Remote Protocol server that routes a request
+/// coming from a client either to a new session or to the batch processing.
+async fn process_request(
+    request: Request<Incoming>,
+    request_sender: &Sender<BrpMessage>,
+    headers: &Headers,
+    sessions: &BrpSessions,
+) -> AnyhowResult<Response<BrpHttpBody>> {
+    if request.method() == hyper::Method::GET && request.uri().path() == SESSION_PATH {
+        let mut response = match sessions.authenticate(bearer_token(&request).as_deref()) {
+            Ok(()) => {
+                let mut response = Response::new(BrpHttpBody::Session(sessions.open()));
+                response.headers_mut().insert(
+                    hyper::header::CONTENT_TYPE,
+                    HeaderValue::from_static("text/event-stream"),
+                );
+                response
+            }
+            Err(err) => {
+                let serialized = serde_json::to_string(&BrpResponse::new(None, Err(err)))?;
+                let mut response =
+                    Response::new(BrpHttpBody::Complete(Full::new(Bytes::from(serialized))));
+                *response.status_mut() = StatusCode::UNAUTHORIZED;
+                response.headers_mut().insert(
+                    hyper::header::CONTENT_TYPE,
+                    HeaderValue::from_static("application/json"),
+                );
+                response
+            }
+        };
+        for (key, value) in &headers.headers {
+            response.headers_mut().insert(key, value.clone());
+        }
+        return Ok(response);
+    }
+
+    let session = request
+        .headers()
+        .get(SESSION_HEADER)
+        .map(|session| {
+            session
+                .to_str()
+                .ok()
+                .filter(|session| sessions.contains(session))
+                .map(str::to_owned)
+                .ok_or_else(|| BrpError {
+                    code: error_codes::INVALID_REQUEST,
+                    message: format!("Unknown session: {session:?}"),
+                    data: None,
+                })
+        })
+        .transpose();
+    let session = session
+        .as_ref()
+        .map(|session| session.as_deref().map(|session| (sessions, session)))
+        .map_err(Clone::clone);
+
+    process_request_batch(request, request_sender, headers, session).await
+}
+
+/// Returns the bearer token of the `Authorization` header of a request, if any.
+fn bearer_token(request: &Request<Incoming>) -> Option<String> {
+    let authorization = request.headers().get(hyper::header::AUTHORIZATION)?;
+    let token = authorization.to_str().ok()?.strip_prefix("Bearer ")?;
+    Some(token.trim().to_owned())
+}
+
 /// A helper function for the Bevy Remote Protocol server that handles a batch
 /// of requests coming from a client.
 async fn process_request_batch(
     request: Request<Incoming>,
     request_sender: &Sender<BrpMessage>,
     headers: &Headers,
+    session: Result<Option<(&BrpSessions, &str)>, BrpError>,
 ) -> AnyhowResult<Response<BrpHttpBody>> {
+    let token = bearer_token(&request);
     let batch_bytes = request.into_body().collect().await?.to_bytes();
     let batch: Result<BrpBatch, _> = serde_json::from_slice(&batch_bytes);
 
-    let result = match batch {
-        Ok(BrpBatch::Single(request)) => {
-            let response = process_single_request(request, request_sender).await?;
+    let result = match (batch, session) {
+        (_, Err(err)) => {
+            BrpHttpResponse::Complete(serde_json::to_string(&BrpResponse::new(None, Err(err)))?)
+        }
+        (Ok(BrpBatch::Single(request)), Ok(session)) => {
+            let response =
+                process_single_request(request, request_sender, token.clone(), session).await?;
             match response {
                 BrpHttpResponse::Complete(res) => {
                     BrpHttpResponse::Complete(serde_json::to_string(&res)?)
@@ -282,11 +460,12 @@ async fn process_request_batch(
                 BrpHttpResponse::Stream(stream) => BrpHttpResponse::Stream(stream),
             }
         }
-        Ok(BrpBatch::Batch(requests)) => {
+        (Ok(BrpBatch::Batch(requests)), Ok(session)) => {
             let mut responses = Vec::new();
 
             for request in requests {
-                let response = process_single_request(request, request_sender).await?;
+                let response =
+                    process_single_request(request, request_sender, token.clone(), session).await?;
                 match response {
                     BrpHttpResponse::Complete(res) => responses.push(res),
                     BrpHttpResponse::Stream(BrpStream { id, .. }) => {
@@ -304,7 +483,7 @@ async fn process_request_batch(
 
             BrpHttpResponse::Complete(serde_json::to_string(&responses)?)
         }
-        Err(err) => {
+        (Err(err), Ok(_)) => {
             let err = BrpResponse::new(
                 None,
                 Err(BrpError {
@@ -346,9 +525,14 @@ async fn process_request_batch(
 
 /// A helper function for the Bevy Remote Protocol server that processes a single
 /// request coming from a client.
+///
+/// If the request belongs to a `session`, watching requests are turned into subscriptions
+/// of that session instead of being streamed in the response.
 async fn process_single_request(
     request: Value,
     request_sender: &Sender<BrpMessage>,
+    token: Option<String>,
+    session: Option<(&BrpSessions, &str)>,
 ) -> AnyhowResult<BrpHttpResponse<BrpResponse, BrpStream>> {
     // Reach in and get the request ID early so that we can report it even when parsing fails.
     let id = request.as_object().and_then(|map| map.get("id")).cloned();
@@ -378,19 +562,45 @@ async fn process_single_request(
         )));
     }
 
+    if request.method == BRP_UNSUBSCRIBE_METHOD {
+        let result = match session {
+            Some((sessions, session)) => sessions
+                .authenticate(token.as_deref())
+                .and_then(|()| unsubscribe(sessions, session, request.params)),
+            None => Err(BrpError {
+                code: error_codes::INVALID_REQUEST,
+                message: format!(
+                    "`{BRP_UNSUBSCRIBE_METHOD}` requires the `{SESSION_HEADER}` header"
+                ),
+                data: None,
+            }),
+        };
+        return Ok(BrpHttpResponse::Complete(BrpResponse::new(
+            request.id, result,
+        )));
+    }
+
     let watch = request.method.contains("+watch");
     let size = if watch { 8 } else { 1 };
     let (result_sender, result_receiver) = async_channel::bounded(size);
 
     let _ = request_sender
-        .send(BrpMessage {
-            method: request.method,
-            params: request.params,
-            sender: result_sender,
-        })
+        .send(BrpMessage::new(request.method, request.params, result_sender).with_token(token))
         .await;
 
-    if watch {
+    if let (true, Some((sessions, session))) = (watch, session) {
+        let subscription = sessions.subscribe(session, request.id.clone(), result_receiver);
+        let result = subscription
+            .map(|subscription| json!({ "subscription": subscription }))
+            .ok_or_else(|| BrpError {
+                code: error_codes::INVALID_REQUEST,
+                message: format!("Session {session} has been closed"),
+                data: None,
+            });
+        Ok(BrpHttpResponse::Complete(BrpResponse::new(
+            request.id, result,
+        )))
+    } else if watch {
         Ok(BrpHttpResponse::Stream(BrpStream {
             id: request.id,
             rx: Box::pin(result_receiver),
@@ -403,6 +613,297 @@ async fn process_single_request(
     }
 }
 
+/// Handles a `bevy/unsubscribe` request coming from a client of the given `session`.
+fn unsubscribe(sessions: &BrpSessions, session: &str, params: Option<Value>) -> BrpResult {
+    let params = params.ok_or_else(|| BrpError {
+        code: error_codes::INVALID_PARAMS,
+        message: String::from("Params not provided"),
+        data: None,
+    })?;
+    let BrpUnsubscribeParams { subscription } =
+        serde_json::from_value(params).map_err(|err| BrpError {
+            code: error_codes::INVALID_PARAMS,
+            message: err.to_string(),
+            data: None,
+        })?;
+
+    if sessions.unsubscribe(session, subscription) {
+        Ok(Value::Null)
+    } else {
+        Err(BrpError {
+            code: error_codes::INVALID_PARAMS,
+            message: format!("Subscription {subscription} not found in session {session}"),
+            data: None,
+        })
+    }
+}
+
+/// The state of all open sessions, shared between the connections of the server.
+#[derive(Clone, Default)]
+struct BrpSessions {
+    inner: Arc<Mutex<BrpSessionsInner>>,
+    /// The token required by the current access control rules, used to authenticate the
+    /// requests handled by the transport itself.
+    token: HostToken,
+}
+
+#[derive(Default)]
+struct BrpSessionsInner {
+    /// The randomly keyed hasher that session IDs are derived from.
+    ids: RandomState,
+    /// The number of sessions opened so far.
+    opened: u64,
+    sessions: HashMap<String, BrpSession>,
+}
+
+/// A single session: the channel feeding its event stream along with its subscriptions.
+struct BrpSession {
+    /// The subscriptions that have a pending update, in the order they should be sent.
+    ///
+    /// A subscription is only queued when its pending update is created, so this holds at most
+    /// one entry per subscription.
+    events: Sender<u64>,
+    /// The ID given to the next subscription of this session.
+    next_subscription: u64,
+    subscriptions: HashMap<u64, BrpSubscription>,
+}
+
+/// A subscription of a session.
+struct BrpSubscription {
+    /// The receiving end of the result channel of the watching request, used to end it.
+    results: Receiver<BrpResult>,
+    /// The ID of the request that started this subscription.
+    id: Option<Value>,
+    /// The update waiting to be sent on the event stream, if any.
+    pending: Option<BrpResult>,
+}
+
+/// A single update sent on the event stream of a session.
+#[derive(Serialize)]
+struct BrpSubscriptionResponse {
+    /// The subscription that produced this update.
+    subscription: u64,
+    #[serde(flatten)]
+    response: BrpResponse,
+}
+
+impl BrpSessions {
+    /// Checks the bearer `token` of a request handled by the transport itself.
+    fn authenticate(&self, token: Option<&str>) -> BrpResult<()> {
+        access_control::authenticate(self.token.get().as_deref(), token)
+    }
+
+    /// Opens a new session and returns its event stream.
+    fn open(&self) -> BrpSessionStream {
+        let (events, rx) = async_channel::unbounded();
+        let mut inner = self.inner.lock().unwrap();
+        // SipHash with a random key is unpredictable without that key, so the IDs of other
+        // sessions can't be guessed from the ID of one's own session.
+        let opened = inner.opened;
+        inner.opened += 1;
+        let id = format!(
+            "{:016x}{:016x}",
+            inner.ids.hash_one((opened, 0u8)),
+            inner.ids.hash_one((opened, 1u8))
+        );
+        inner.sessions.insert(
+            id.clone(),
+            BrpSession {
+                events,
+                next_subscription: 0,
+                subscriptions: HashMap::new(),
+            },
+        );
+
+        let greeting = serde_json::to_string(&json!({ "session": id })).unwrap();
+        BrpSessionStream {
+            id,
+            greeting: Some(Bytes::from(format!("data: {greeting}\n\n"))),
+            rx: Box::pin(rx),
+            sessions: self.clone(),
+        }
+    }
+
+    /// Returns true if a session with the given ID is open.
+    fn contains(&self, session: &str) -> bool {
+        self.inner.lock().unwrap().sessions.contains_key(session)
+    }
+
+    /// Closes a session, ending its event stream and all of its subscriptions.
+    fn close(&self, session: &str) {
+        let Some(session) = self.inner.lock().unwrap().sessions.remove(session) else {
+            return;
+        };
+        session.events.close();
+        for subscription in session.subscriptions.values() {
+            subscription.results.close();
+        }
+    }
+
+    /// Takes the pending update of a subscription of `session`, serialized as an event.
+    fn take_update(&self, session: &str, subscription: u64) -> Option<Bytes> {
+        let mut inner = self.inner.lock().unwrap();
+        let state = inner
+            .sessions
+            .get_mut(session)?
+            .subscriptions
+            .get_mut(&subscription)?;
+        let response = BrpSubscriptionResponse {
+            subscription,
+            response: BrpResponse::new(state.id.clone(), state.pending.take()?),
+        };
+        let serialized = serde_json::to_string(&response).ok()?;
+        Some(Bytes::from(format!("data: {serialized}\n\n")))
+    }
+
+    /// Forwards every result received on `results` to the event stream of `session`.
+    ///
+    /// Returns the ID of the new subscription, or `None` if the session is closed.
+    fn subscribe(
+        &self,
+        session: &str,
+        id: Option<Value>,
+        results: Receiver<BrpResult>,
+    ) -> Option<u64> {
+        let mut inner = self.inner.lock().unwrap();
+        let state = inner.sessions.get_mut(session)?;
+        let subscription = state.next_subscription;
+        state.next_subscription += 1;
+        state.subscriptions.insert(
+            subscription,
+            BrpSubscription {
+                results: results.clone(),
+                id,
+                pending: None,
+            },
+        );
+        // The task may start running right away, and takes the lock to forward results.
+        drop(inner);
+        let sessions = self.clone();
+        let session = session.to_owned();
+
+        IoTaskPool::get()
+            .spawn(async move {
+                while let Ok(result) = results.recv().await {
+                    if !sessions.push_update(&session, subscription, result) {
+                        break;
+                    }
+                }
+                // Closing the channel lets the `RemotePlugin` clean up the watching request.
+                results.close();
+            })
+            .detach();
+
+        Some(subscription)
+    }
+
+    /// Adds an update to the pending update of a subscription of `session`, queuing the
+    /// subscription on the event stream if it had none.
+    ///
+    /// Returns false if the subscription has ended.
+    fn push_update(&self, session: &str, subscription: u64, result: BrpResult) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(state) = inner.sessions.get_mut(session) else {
+            return false;
+        };
+        let Some(pending) = state
+            .subscriptions
+            .get_mut(&subscription)
+            .map(|subscription| &mut subscription.pending)
+        else {
+            return false;
+        };
+        match pending {
+            Some(pending) => merge_results(pending, result),
+            None => {
+                *pending = Some(result);
+                return state.events.try_send(subscription).is_ok();
+            }
+        }
+        true
+    }
+
+    /// Ends a subscription of `session`, returning false if it does not exist.
+    fn unsubscribe(&self, session: &str, subscription: u64) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(subscription) = inner
+            .sessions
+            .get_mut(session)
+            .and_then(|session| session.subscriptions.remove(&subscription))
+        else {
+            return false;
+        };
+        subscription.results.close();
+        true
+    }
+}
+
+/// Merges a new `update` of a subscription into the `pending` one that hasn't been sent yet.
+///
+/// See the [module-level documentation](self) for the rules.
+fn merge_results(pending: &mut BrpResult, update: BrpResult) {
+    match (pending, update) {
+        (Ok(Value::Object(pending)), Ok(Value::Object(update))) => {
+            for (key, value) in update {
+                match (pending.get_mut(&key), value) {
+                    (Some(Value::Array(existing)), Value::Array(items)) => {
+                        for item in items {
+                            if !existing.contains(&item) {
+                                existing.push(item);
+                            }
+                        }
+                    }
+                    (Some(Value::Object(existing)), Value::Object(entries)) => {
+                        existing.extend(entries);
+                    }
+                    (_, value) => {
+                        pending.insert(key, value);
+                    }
+                }
+            }
+        }
+        (pending, update) => *pending = update,
+    }
+}
+
+/// The event stream of a session. The session is closed when this is dropped.
+struct BrpSessionStream {
+    id: String,
+    /// The first event of the stream, telling the client the ID of its session.
+    greeting: Option<Bytes>,
+    rx: Pin<Box<Receiver<u64>>>,
+    sessions: BrpSessions,
+}
+
+impl Body for BrpSessionStream {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        if let Some(greeting) = self.greeting.take() {
+            return Poll::Ready(Some(Ok(Frame::data(greeting))));
+        }
+        loop {
+            let Some(subscription) = ready!(self.as_mut().rx.poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+            // Subscriptions that ended since they were queued have nothing to send.
+            if let Some(bytes) = self.sessions.take_update(&self.id, subscription) {
+                return Poll::Ready(Some(Ok(Frame::data(bytes))));
+            }
+        }
+    }
+}
+
+impl Drop for BrpSessionStream {
+    fn drop(&mut self) {
+        self.sessions.close(&self.id);
+    }
+}
+
 struct BrpStream {
     id: Option<Value>,
     rx: Pin<Box<Receiver<BrpResult>>>,
@@ -445,6 +946,7 @@ enum BrpHttpResponse<C, S> {
 enum BrpHttpBody {
     Complete(Full<Bytes>),
     Stream(BrpStream),
+    Session(BrpSessionStream),
 }
 
 impl Body for BrpHttpBody {
@@ -458,6 +960,363 @@ impl Body for BrpHttpBody {
         match &mut *self.get_mut() {
             BrpHttpBody::Complete(body) => Body::poll_frame(Pin::new(body), cx),
             BrpHttpBody::Stream(body) => Body::poll_frame(Pin::new(body), cx),
+            BrpHttpBody::Session(body) => Body::poll_frame(Pin::new(body), cx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_tasks::{block_on, TaskPool};
+    use core::{net::SocketAddr, time::Duration};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        thread,
+    };
+
+    /// Starts a server on a free port, with a stand-in for the world answering its requests.
+    ///
+    /// The world answers every request with its method, except watching requests, which are sent
+    /// `updates` (or an endless stream of updates if `None`) and report on the returned receiver
+    /// once their client stops listening.
+    fn serve(updates: Option<usize>, token: HostToken) -> (SocketAddr, Receiver<String>) {
+        let listener = Async::<TcpListener>::bind((DEFAULT_ADDR, 0)).unwrap();
+        let address = listener.get_ref().local_addr().unwrap();
+        let (request_sender, request_receiver) = async_channel::unbounded::<BrpMessage>();
+        let (closed_sender, closed_receiver) = async_channel::unbounded();
+
+        thread::spawn(move || {
+            IoTaskPool::get_or_init(TaskPool::new).with_local_executor(|executor| {
+                block_on(executor.run(listen(listener, &request_sender, &Headers::new(), token)))
+            })
+        });
+        thread::spawn(move || {
+            while let Ok(message) = request_receiver.recv_blocking() {
+                if !message.method.contains("+watch") {
+                    let _ = message.sender.send_blocking(Ok(json!(message.method)));
+                    continue;
+                }
+                let closed_sender = closed_sender.clone();
+                thread::spawn(move || {
+                    let payload = "x".repeat(1024);
+                    for update in 0.. {
+                        if updates.is_some_and(|updates| update >= updates) {
+                            // Keep the request open until the client stops listening.
+                            while !message.sender.is_closed() {
+                                thread::sleep(Duration::from_millis(1));
+                            }
+                            break;
+                        }
+                        let value = json!({ "update": update, "payload": payload });
+                        if message.sender.send_blocking(Ok(value)).is_err() {
+                            break;
+                        }
+                    }
+                    let _ = closed_sender.send_blocking(message.method);
+                });
+            }
+        });
+
+        (address, closed_receiver)
+    }
+
+    /// Sends a `POST` request with the given body and headers, returning the response body.
+    fn post(address: SocketAddr, headers: &[(&str, &str)], body: Value) -> Value {
+        let mut stream = TcpStream::connect(address).unwrap();
+        let body = body.to_string();
+        let mut request = format!(
+            "POST / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n",
+            body.len()
+        );
+        for (name, value) in headers {
+            request.push_str(&format!("{name}: {value}\r\n"));
+        }
+        request.push_str(&format!("\r\n{body}"));
+        stream.write_all(request.as_bytes()).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, mut body) = response.split_once("\r\n\r\n").unwrap();
+        if !head.to_lowercase().contains("transfer-encoding: chunked") {
+            return serde_json::from_str(body).unwrap();
+        }
+        let mut decoded = String::new();
+        loop {
+            let (size, rest) = body.split_once("\r\n").unwrap();
+            let size = usize::from_str_radix(size, 16).unwrap();
+            if size == 0 {
+                return serde_json::from_str(&decoded).unwrap();
+            }
+            decoded.push_str(&rest[..size]);
+            body = &rest[size + 2..];
+        }
+    }
+
+    fn request(id: u64, method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+    }
+
+    /// An open session event stream.
+    struct Session {
+        id: String,
+        stream: BufReader<TcpStream>,
+    }
+
+    impl Session {
+        fn open(address: SocketAddr) -> Self {
+            Self::open_with(address, &[])
+        }
+
+        fn open_with(address: SocketAddr, headers: &[(&str, &str)]) -> Self {
+            let mut stream = TcpStream::connect(address).unwrap();
+            let mut request = "GET /session HTTP/1.1\r\nHost: localhost\r\n".to_owned();
+            for (name, value) in headers {
+                request.push_str(&format!("{name}: {value}\r\n"));
+            }
+            request.push_str("\r\n");
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut stream = BufReader::new(stream);
+            let mut line = String::new();
+            while line != "\r\n" {
+                line.clear();
+                stream.read_line(&mut line).unwrap();
+            }
+            let mut session = Self {
+                id: String::new(),
+                stream,
+            };
+            let greeting = session.next_event().unwrap();
+            session.id = greeting["session"].as_str().unwrap().to_owned();
+            session
+        }
+
+        /// Reads the next event of the stream, or `None` once the stream has ended.
+        fn next_event(&mut self) -> Option<Value> {
+            // The body is chunked, with one event per chunk.
+            let mut size = String::new();
+            self.stream.read_line(&mut size).unwrap();
+            let size = usize::from_str_radix(size.trim(), 16).ok()?;
+            if size == 0 {
+                return None;
+            }
+            let mut chunk = vec![0; size + 2];
+            self.stream.read_exact(&mut chunk).unwrap();
+            let event = core::str::from_utf8(&chunk).unwrap();
+            let data = event.strip_prefix("data: ").unwrap().trim_end();
+            Some(serde_json::from_str(data).unwrap())
+        }
+
+        fn post(&self, address: SocketAddr, body: Value) -> Value {
+            post(address, &[(SESSION_HEADER, &self.id)], body)
+        }
+    }
+
+    #[test]
+    fn session_subscriptions() {
+        let (address, closed) = serve(Some(2), HostToken::default());
+
+        let mut session = Session::open(address);
+        assert_eq!(session.id.len(), 32);
+        assert_ne!(session.id, Session::open(address).id);
+
+        // Watching requests become subscriptions, and can be mixed with other requests.
+        let responses = session.post(
+            address,
+            json!([
+                request(1, "bevy/get+watch", Value::Null),
+                request(2, "bevy/list", Value::Null),
+            ]),
+        );
+        assert_eq!(responses[0]["result"], json!({ "subscription": 0 }));
+        assert_eq!(responses[1]["result"], json!("bevy/list"));
+
+        // Updates that the client hasn't received yet may have been merged.
+        loop {
+            let event = session.next_event().unwrap();
+            assert_eq!(event["subscription"], json!(0));
+            assert_eq!(event["id"], json!(1));
+            if event["result"]["update"] == json!(1) {
+                break;
+            }
+        }
+
+        // Unsubscribing ends the watching request.
+        let response = session.post(
+            address,
+            request(3, BRP_UNSUBSCRIBE_METHOD, json!({ "subscription": 0 })),
+        );
+        assert_eq!(response["result"], Value::Null);
+        assert_eq!(closed.recv_blocking().unwrap(), "bevy/get+watch");
+        let response = session.post(
+            address,
+            request(4, BRP_UNSUBSCRIBE_METHOD, json!({ "subscription": 0 })),
+        );
+        assert_eq!(
+            response["error"]["code"],
+            json!(error_codes::INVALID_PARAMS)
+        );
+
+        // Closing the session ends its subscriptions, and the session can't be used anymore.
+        let response = session.post(address, request(5, "bevy/list+watch", Value::Null));
+        assert_eq!(response["result"], json!({ "subscription": 1 }));
+        let id = session.id.clone();
+        drop(session);
+        assert_eq!(closed.recv_blocking().unwrap(), "bevy/list+watch");
+        let response = post(
+            address,
+            &[(SESSION_HEADER, &id)],
+            request(6, "bevy/list", Value::Null),
+        );
+        assert_eq!(
+            response["error"]["code"],
+            json!(error_codes::INVALID_REQUEST)
+        );
+    }
+
+    #[test]
+    fn unknown_sessions_are_rejected() {
+        let (address, _) = serve(Some(0), HostToken::default());
+        let session = Session::open(address);
+
+        // Knowing one session doesn't give access to the others.
+        let guess = format!(
+            "{:032x}",
+            u128::from_str_radix(&session.id, 16).unwrap() + 1
+        );
+        for id in ["0", "1", guess.as_str()] {
+            let response = post(
+                address,
+                &[(SESSION_HEADER, id)],
+                request(1, BRP_UNSUBSCRIBE_METHOD, json!({ "subscription": 0 })),
+            );
+            assert_eq!(
+                response["error"]["code"],
+                json!(error_codes::INVALID_REQUEST)
+            );
         }
     }
+
+    #[test]
+    fn slow_sessions_merge_updates() {
+        let (address, _) = serve(Some(1000), HostToken::default());
+        let mut session = Session::open(address);
+
+        // Let the updates pile up while the client doesn't read its event stream.
+        let response = session.post(address, request(1, "bevy/get+watch", Value::Null));
+        assert_eq!(response["result"], json!({ "subscription": 0 }));
+        thread::sleep(Duration::from_millis(500));
+
+        // The session is still open, and the updates that piled up were merged.
+        let mut events = 0;
+        let mut last = None;
+        while last != Some(999) {
+            let event = session.next_event().unwrap();
+            let update = event["result"]["update"].as_u64().unwrap();
+            assert!(last.is_none_or(|last| last < update));
+            last = Some(update);
+            events += 1;
+        }
+        assert!(events < 1000);
+        let response = session.post(address, request(2, "bevy/list", Value::Null));
+        assert_eq!(response["result"], json!("bevy/list"));
+    }
+
+    #[test]
+    fn merged_updates() {
+        let mut pending = Ok(json!({
+            "components": { "a": 1, "b": [1, 2] },
+            "removed": ["c"],
+            "update": 0,
+        }));
+        merge_results(
+            &mut pending,
+            Ok(json!({
+                "components": { "b": [3] },
+                "removed": ["c", "d"],
+                "errors": { "e": "error" },
+                "update": 1,
+            })),
+        );
+        assert_eq!(
+            pending.unwrap(),
+            json!({
+                "components": { "a": 1, "b": [3] },
+                "removed": ["c", "d"],
+                "errors": { "e": "error" },
+                "update": 1,
+            })
+        );
+
+        let mut pending = Ok(json!({ "removed": ["c"] }));
+        merge_results(&mut pending, Err(BrpError::internal("error")));
+        assert!(pending.is_err());
+        merge_results(&mut pending, Ok(json!({ "removed": ["d"] })));
+        assert_eq!(pending.unwrap(), json!({ "removed": ["d"] }));
+    }
+
+    #[test]
+    fn transport_requests_require_the_token() {
+        let token = HostToken::default();
+        token.set(Some("secret"));
+        let (address, closed) = serve(Some(0), token.clone());
+        let authorized = ("Authorization", "Bearer secret");
+
+        // Sessions can't be opened without the token.
+        for headers in [&[][..], &[("Authorization", "Bearer guess")][..]] {
+            let mut stream = TcpStream::connect(address).unwrap();
+            let mut request =
+                "GET /session HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n".to_owned();
+            for (name, value) in headers {
+                request.push_str(&format!("{name}: {value}\r\n"));
+            }
+            request.push_str("\r\n");
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            assert!(response.starts_with("HTTP/1.1 401"));
+            assert!(response.contains(&error_codes::UNAUTHORIZED.to_string()));
+        }
+
+        let session = Session::open_with(address, &[authorized]);
+        let response = post(
+            address,
+            &[authorized, (SESSION_HEADER, &session.id)],
+            request(1, "bevy/get+watch", Value::Null),
+        );
+        assert_eq!(response["result"], json!({ "subscription": 0 }));
+
+        // Knowing the session isn't enough to unsubscribe.
+        let unsubscribe = request(2, BRP_UNSUBSCRIBE_METHOD, json!({ "subscription": 0 }));
+        let response = session.post(address, unsubscribe.clone());
+        assert_eq!(response["error"]["code"], json!(error_codes::UNAUTHORIZED));
+        let response = post(
+            address,
+            &[authorized, (SESSION_HEADER, &session.id)],
+            unsubscribe.clone(),
+        );
+        assert_eq!(response["result"], Value::Null);
+        assert_eq!(closed.recv_blocking().unwrap(), "bevy/get+watch");
+
+        // Changing the token applies to the sessions that are already open.
+        token.set(Some("rotated"));
+        let response = post(
+            address,
+            &[authorized, (SESSION_HEADER, &session.id)],
+            unsubscribe.clone(),
+        );
+        assert_eq!(response["error"]["code"], json!(error_codes::UNAUTHORIZED));
+        let response = post(
+            address,
+            &[
+                ("Authorization", "Bearer rotated"),
+                (SESSION_HEADER, &session.id),
+            ],
+            unsubscribe,
+        );
+        assert_eq!(
+            response["error"]["code"],
+            json!(error_codes::INVALID_PARAMS)
+        );
+    }
 }
@@ -0,0 +1,356 @@
+//! Built-in verbs for running one-shot systems and calling reflected functions through the Bevy
+//! Remote Protocol.
+//!
+//! One-shot systems must be registered with a name in the [`RemoteSystems`] resource, usually via
+//! [`RemotePlugin::with_system`](crate::RemotePlugin::with_system). Reflected functions are looked
+//! up by name in the [`AppFunctionRegistry`](bevy_ecs::reflect::AppFunctionRegistry), which
+//! requires the `reflect_functions` feature.
+
+use alloc::sync::Arc;
+use core::any::TypeId;
+
+use anyhow::{anyhow, Result as AnyhowResult};
+use bevy_ecs::{
+    reflect::AppTypeRegistry,
+    resource::Resource,
+    system::{In, SystemId, SystemInput},
+    world::World,
+};
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    FromReflect, PartialReflect, Reflect, TypePath, TypeRegistration, TypeRegistry,
+};
+use serde::{de::DeserializeSeed as _, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{builtin_methods::parse_some, BrpError, BrpResult};
+
+/// The method path for a `bevy/run_system` request.
+pub const BRP_RUN_SYSTEM_METHOD: &str = "bevy/run_system";
+
+/// The method path for a `bevy/call_function` request.
+#[cfg(feature = "reflect_functions")]
+pub const BRP_CALL_FUNCTION_METHOD: &str = "bevy/call_function";
+
+/// `bevy/run_system`: Runs a one-shot system registered in the [`RemoteSystems`] resource.
+///
+/// The server responds with the serialized output of the system, or null if it returns `()`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpRunSystemParams {
+    /// The name the system was registered with.
+    pub system: String,
+
+    /// The input of the system, serialized like the input type of the system.
+    ///
+    /// This can be omitted for systems that take no input.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<Value>,
+}
+
+/// `bevy/call_function`: Calls a function registered in the
+/// [`AppFunctionRegistry`](bevy_ecs::reflect::AppFunctionRegistry).
+///
+/// The server responds with the serialized return value of the function, or null if it returns
+/// `()`.
+#[cfg(feature = "reflect_functions")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpCallFunctionParams {
+    /// The name the function was registered with.
+    pub function: String,
+
+    /// The arguments of the function, each serialized like the type of its parameter.
+    ///
+    /// Arguments taken by reference are deserialized like owned arguments.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<Value>,
+}
+
+/// Runs a type-erased one-shot system with its input, returning its serialized output.
+type RemoteSystemRunner = dyn Fn(&mut World, Option<Value>) -> BrpResult + Send + Sync;
+
+/// Holds the one-shot systems that can be run with `bevy/run_system`, keyed by name.
+///
+/// Systems are registered in the [`World`] like any other one-shot system, and their
+/// [`SystemId`] is added here along with the name clients will use to run them.
+#[derive(Resource, Default, Clone)]
+pub struct RemoteSystems(HashMap<String, Arc<RemoteSystemRunner>>);
+
+impl RemoteSystems {
+    /// Makes the one-shot system with the given `id` available to remote clients under `name`,
+    /// replacing any system previously registered with that name.
+    ///
+    /// The input of the system is deserialized with reflection, so its type must be registered
+    /// in the [`AppTypeRegistry`]. Systems taking no input, or with an [`In<T>`] input, are
+    /// supported.
+    pub fn insert<I, T, O>(&mut self, name: impl Into<String>, id: SystemId<I, O>)
+    where
+        I: SystemInput + 'static,
+        for<'a> I: SystemInput<Inner<'a> = T>,
+        T: FromReflect + TypePath,
+        O: Reflect,
+    {
+        let name = name.into();
+        let system_name = name.clone();
+        let runner = move |world: &mut World, input: Option<Value>| {
+            let input = {
+                let type_registry = world.resource::<AppTypeRegistry>().read();
+                deserialize_system_input::<T>(&system_name, input, &type_registry)?
+            };
+            let output = world
+                .run_system_with(id, input)
+                .map_err(BrpError::one_shot_system_error)?;
+
+            if output.as_partial_reflect().represents::<()>() {
+                return Ok(Value::Null);
+            }
+            let type_registry = world.resource::<AppTypeRegistry>().read();
+            serde_json::to_value(TypedReflectSerializer::new(
+                output.as_partial_reflect(),
+                &type_registry,
+            ))
+            .map_err(BrpError::one_shot_system_error)
+        };
+        self.0.insert(name, Arc::new(runner));
+    }
+
+    /// Removes the system registered under `name`, returning true if there was one.
+    ///
+    /// The system itself stays registered in the [`World`].
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.0.remove(name).is_some()
+    }
+
+    /// Returns true if a system is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    /// Returns an iterator over the names of the registered systems.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+}
+
+/// Handles a `bevy/run_system` request coming from a client.
+pub fn process_remote_run_system_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpRunSystemParams { system, input } = parse_some(params)?;
+
+    let runner = world
+        .get_resource::<RemoteSystems>()
+        .and_then(|systems| systems.0.get(&system))
+        .cloned()
+        .ok_or_else(|| BrpError::one_shot_system_not_found(&system))?;
+
+    runner(world, input)
+}
+
+/// Handles a `bevy/call_function` request coming from a client.
+#[cfg(feature = "reflect_functions")]
+pub fn process_remote_call_function_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    use bevy_ecs::reflect::AppFunctionRegistry;
+    use bevy_reflect::func::{args::Ownership, ArgList, Return};
+
+    let BrpCallFunctionParams { function, args } = parse_some(params)?;
+
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let function_registry = world
+        .get_resource::<AppFunctionRegistry>()
+        .map(|registry| registry.read());
+    let dynamic_function = function_registry
+        .as_ref()
+        .and_then(|registry| registry.get(&function))
+        .ok_or_else(|| BrpError::function_not_found(&function))?;
+
+    // Try every signature of the function until the arguments can be deserialized for one.
+    let mut error = BrpError::function_error(format!(
+        "Function `{function}` does not take {} arguments",
+        args.len()
+    ));
+    let signature = dynamic_function
+        .info()
+        .signatures()
+        .iter()
+        .filter(|signature| signature.arg_count() == args.len())
+        .find_map(|signature| {
+            let values = signature
+                .args()
+                .iter()
+                .zip(&args)
+                .map(|(arg, value)| {
+                    let registration = match arg.ownership() {
+                        Ownership::Owned => type_registry.get(arg.type_id()),
+                        // The type of reference arguments is the reference itself, so the
+                        // referenced type is found from its path.
+                        Ownership::Ref | Ownership::Mut => arg
+                            .type_path()
+                            .strip_prefix("&mut ")
+                            .or_else(|| arg.type_path().strip_prefix('&'))
+                            .and_then(|path| type_registry.get_with_type_path(path)),
+                    };
+                    deserialize_value(registration, arg.type_path(), value, &type_registry)
+                })
+                .collect::<AnyhowResult<Vec<_>>>();
+            match values {
+                Ok(values) => Some((signature, values)),
+                Err(err) => {
+                    error = BrpError::function_error(err);
+                    None
+                }
+            }
+        });
+    let Some((signature, mut values)) = signature else {
+        return Err(error);
+    };
+
+    let mut arg_list = ArgList::new();
+    for (arg, value) in signature.args().iter().zip(values.iter_mut()) {
+        match arg.ownership() {
+            Ownership::Ref => arg_list.push_ref(&**value),
+            Ownership::Mut => arg_list.push_mut(&mut **value),
+            Ownership::Owned => arg_list.push_boxed(core::mem::replace(value, Box::new(()))),
+        }
+    }
+
+    let result = dynamic_function
+        .call(arg_list)
+        .map_err(BrpError::function_error)?;
+    if result.is_unit() {
+        return Ok(Value::Null);
+    }
+    let value = match &result {
+        Return::Owned(value) => &**value,
+        Return::Ref(value) => *value,
+        Return::Mut(value) => &**value,
+    };
+    serde_json::to_value(TypedReflectSerializer::new(value, &type_registry))
+        .map_err(BrpError::function_error)
+}
+
+/// Deserializes the input of the one-shot system `system`, which is `()` if absent.
+fn deserialize_system_input<T: FromReflect + TypePath>(
+    system: &str,
+    input: Option<Value>,
+    type_registry: &TypeRegistry,
+) -> BrpResult<T> {
+    let value = match input {
+        Some(input) => deserialize_value(
+            type_registry.get(TypeId::of::<T>()),
+            T::type_path(),
+            &input,
+            type_registry,
+        )
+        .map_err(BrpError::one_shot_system_error)?,
+        None => Box::new(()),
+    };
+    T::from_reflect(&*value).ok_or_else(|| {
+        BrpError::one_shot_system_error(format!(
+            "System `{system}` requires an input of type `{}`",
+            T::type_path()
+        ))
+    })
+}
+
+/// Deserializes a value of the type with the given `registration`, failing if it is [`None`].
+fn deserialize_value(
+    registration: Option<&TypeRegistration>,
+    type_path: &str,
+    value: &Value,
+    type_registry: &TypeRegistry,
+) -> AnyhowResult<Box<dyn PartialReflect>> {
+    let registration =
+        registration.ok_or_else(|| anyhow!("Unknown type `{type_path}`, is it registered?"))?;
+    Ok(TypedReflectDeserializer::new(registration, type_registry).deserialize(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_codes;
+    use bevy_ecs::{resource::Resource, system::ResMut};
+    use serde_json::json;
+
+    #[derive(Resource, Default)]
+    struct Gold(u32);
+
+    fn give_gold(In(amount): In<u32>, mut gold: ResMut<Gold>) -> u32 {
+        gold.0 += amount;
+        gold.0
+    }
+
+    fn reset_gold(mut gold: ResMut<Gold>) {
+        gold.0 = 0;
+    }
+
+    #[test]
+    fn run_registered_systems() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world.init_resource::<Gold>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<u32>();
+
+        let give_gold = world.register_system(give_gold);
+        let reset_gold = world.register_system(reset_gold);
+        let mut systems = RemoteSystems::default();
+        systems.insert("give_gold", give_gold);
+        systems.insert("reset_gold", reset_gold);
+        world.insert_resource(systems);
+
+        let run = world.register_system(process_remote_run_system_request);
+        let mut run = |params: Value| world.run_system_with(run, Some(params)).unwrap();
+
+        let output = run(json!({ "system": "give_gold", "input": 15 }));
+        assert_eq!(output.unwrap(), json!(15));
+        let output = run(json!({ "system": "reset_gold" }));
+        assert_eq!(output.unwrap(), Value::Null);
+
+        let error = run(json!({ "system": "give_gold" })).unwrap_err();
+        assert_eq!(error.code, error_codes::ONE_SHOT_SYSTEM_ERROR);
+        let error = run(json!({ "system": "give_gold", "input": "a lot" })).unwrap_err();
+        assert_eq!(error.code, error_codes::ONE_SHOT_SYSTEM_ERROR);
+        let error = run(json!({ "system": "take_gold" })).unwrap_err();
+        assert_eq!(error.code, error_codes::ONE_SHOT_SYSTEM_NOT_FOUND);
+    }
+
+    #[cfg(feature = "reflect_functions")]
+    #[test]
+    fn call_registered_functions() {
+        use bevy_ecs::reflect::AppFunctionRegistry;
+
+        fn add(a: i32, b: &i32) -> i32 {
+            a + *b
+        }
+
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world.init_resource::<AppFunctionRegistry>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<i32>();
+        world
+            .resource::<AppFunctionRegistry>()
+            .write()
+            .register_with_name("add", add)
+            .unwrap();
+
+        let call = world.register_system(process_remote_call_function_request);
+        let mut call = |params: Value| world.run_system_with(call, Some(params)).unwrap();
+
+        let output = call(json!({ "function": "add", "args": [1, 2] }));
+        assert_eq!(output.unwrap(), json!(3));
+        let error = call(json!({ "function": "add", "args": [1] })).unwrap_err();
+        assert_eq!(error.code, error_codes::FUNCTION_ERROR);
+        let error = call(json!({ "function": "sub", "args": [1, 2] })).unwrap_err();
+        assert_eq!(error.code, error_codes::FUNCTION_NOT_FOUND);
+    }
+}
@@ -6,6 +6,12 @@
 //! over HTTP. These *remote clients* can inspect and alter the state of the
 //! entity-component system.
 //!
+//! Local tools can instead use the newline-delimited JSON-RPC transports: the
+//! [`RemoteUnixSocketPlugin`](unix::RemoteUnixSocketPlugin) listens on a Unix domain socket,
+//! and the [`RemoteStdioPlugin`](stdio::RemoteStdioPlugin) lets a parent process control the
+//! app through its standard input and output. Every transport dispatches to the same
+//! [`RemoteMethods`].
+//!
 //! The Bevy Remote Protocol is based on the JSON-RPC 2.0 protocol.
 //!
 //! ## Request objects
@@ -143,6 +149,22 @@
 //!     on entities in order for them to be included in results.
 //!   - `without` (optional): An array of fully-qualified type names of components that must *not* be
 //!     present on entities in order for them to be included in results.
+//!   - `changed` (optional): An array of fully-qualified type names of components that must have been
+//!     added or changed since `since_tick` on entities in order for them to be included in results.
+//!   - `added` (optional): An array of fully-qualified type names of components that must have been
+//!     added since `since_tick` on entities in order for them to be included in results.
+//!   - `since_tick` (optional): The change tick that `changed` and `added` are compared against.
+//!     Defaults to the last time the `bevy/query` handler ran.
+//!   - `predicates` (optional): An array of predicates on component values that must all hold for
+//!     entities to be included in results. Each predicate is an object containing:
+//!     - `component`: The fully-qualified type name of the component to test. Entities without
+//!       this component are excluded.
+//!     - `path` (optional): The path of the field within the component. See
+//!       [`GetPath`](bevy_reflect::GetPath#syntax) for more information on formatting this string.
+//!       If omitted, the whole component is tested.
+//!     - One of `eq`, `ne`, `lt`, `lte`, `gt`, `gte` or `contains`, whose value is the operand
+//!       to compare the field with, in the same format as values returned by `bevy/get`.
+//!       `contains` matches array elements, substrings and object keys.
 //!   - `strict` (optional): A flag to enable strict mode which will fail if any one of the
 //!     components is not present or can not be reflected. Defaults to false.
 //!
@@ -152,6 +174,10 @@
 //!   entity if the component is present.
 //! - `has`: A map associating each type name from `has` to a boolean value indicating whether or not the
 //!   entity has that component. If `has` was empty or omitted, this key will be omitted in the response.
+//! - `ticks`: A map associating each type name from the `changed` and `added` filters to an object
+//!   with its `added` and `changed` ticks on the entity. The largest of these ticks can be used as
+//!   the `since_tick` of a subsequent query. If there were no such filters, this key will be omitted
+//!   in the response.
 //!
 //!
 //!
@@ -321,6 +347,272 @@
 //!
 //! `result`: An array of [fully-qualified type names] of registered resource types.
 //!
+//! ### `bevy/batch`
+//!
+//! Run an ordered list of calls in a single pass over the world. Either all of the calls take
+//! effect, or none of them do: if any call fails, the effects of the calls that ran before it are
+//! rolled back and the error of the failing call is returned.
+//!
+//! Only the built-in methods above (except the `+watch` methods) can be used inside of a batch.
+//! Entities destroyed with `bevy/destroy` are despawned once every other call has succeeded.
+//!
+//! `params`:
+//! - `requests`: An array of objects, each containing:
+//!   - `method`: The method of the call, e.g. `bevy/spawn`.
+//!   - `params` (optional): The params of the call. Any object of the form
+//!     `{ "batch_entity": N }` is replaced by the ID of the entity spawned by the `N`-th
+//!     (zero-based) call of the batch.
+//!
+//! `result`: An array containing the result of each call, in order.
+//!
+//! If a call fails, the error has the `code` of the failing call and its `data` contains:
+//! - `index`: The index of the failing call.
+//! - `error`: The `data` of the error returned by the failing call.
+//!
+//! ### `bevy/schedule/list`
+//!
+//! List all schedules of the world. This method has no parameters.
+//!
+//! Schedules are identified by the [`Debug`] representation of their label, e.g. `Update`. The
+//! schedule processing remote requests is running and therefore not listed.
+//!
+//! `result`: An array, each of which is an object containing:
+//! - `label`: The label of the schedule.
+//! - `systems`: The number of systems in the schedule.
+//! - `sets`: The number of system sets in the schedule.
+//!
+//! ### `bevy/schedule/graph`
+//!
+//! Retrieve the systems, system sets, ordering graphs and ambiguities of a schedule.
+//!
+//! `params`:
+//! - `schedule`: The label of the schedule.
+//!
+//! `result`:
+//! - `systems`: An array of objects containing the `id` (the index of the system in the
+//!   schedule), the `name` and the `conditions` (an array of run condition names) of each system.
+//! - `sets`: An array of objects containing the `id`, `name` and `conditions` of each system set.
+//!   The sets created for each system type are left out: ordering relative to a system type shows
+//!   up as dependencies of its systems instead.
+//! - `hierarchy`: An array of `[parent, child]` pairs, where each node is either
+//!   `{ "system": id }` or `{ "set": id }`.
+//! - `dependencies`: An array of `[before, after]` pairs of nodes.
+//! - `ambiguities`: An array of objects containing the `systems` (a pair of system ids) and the
+//!   `components` (the names of the components they conflict on) of each ambiguity. These are only
+//!   known once the schedule has run.
+//!
+//! ### `bevy/stepping/enable`
+//!
+//! Enable [system stepping](bevy_ecs::schedule::Stepping), starting at the next frame. This requires
+//! the `bevy_debug_stepping` feature, and fails with the `STEPPING_UNAVAILABLE` error code without it.
+//!
+//! `params` (optional):
+//! - `schedules` (optional): An array of labels of schedules to step through.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/stepping/disable`
+//!
+//! Disable system stepping, resuming normal execution. This method has no parameters.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/stepping/step_system`
+//!
+//! Run the next system in the stepping frame during the next frame. This method has no parameters.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/stepping/step_frame`
+//!
+//! Run all remaining systems of the stepping frame, stopping at the next breakpoint, during the
+//! next frame. This method has no parameters.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/stepping/set_breakpoint`
+//!
+//! Stop before a system when running the stepping frame with `bevy/stepping/step_frame`.
+//!
+//! `params`:
+//! - `schedule`: The label of the schedule containing the system.
+//! - `system`: The id of the system, as reported by `bevy/schedule/graph`.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/stepping/clear_breakpoint`
+//!
+//! Remove a breakpoint set with `bevy/stepping/set_breakpoint`.
+//!
+//! `params`:
+//! - `schedule`: The label of the schedule containing the system.
+//! - `system`: The id of the system, as reported by `bevy/schedule/graph`.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/stepping/status`
+//!
+//! Report the state of system stepping. This method has no parameters.
+//!
+//! `result`:
+//! - `enabled`: Whether stepping is enabled.
+//! - `schedules`: The labels of the schedules being stepped through, in the order they run, or
+//!   null until each of them has run once.
+//! - `cursor`: An object containing the `schedule` and `system` id of the next system to run, or
+//!   null if there is none.
+//!
+//! ### `bevy/send_event`
+//!
+//! Send an event, so that it is read by every `EventReader` of its type. The event type must be
+//! registered with `#[reflect(Event)]` and added to the app with `App::add_event`.
+//!
+//! `params`:
+//! - `event`: The [fully-qualified type name] of the event to send.
+//! - `value`: The value of the event to send.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/trigger_event`
+//!
+//! Trigger an event, running every observer watching for it. The event type must be registered
+//! with `#[reflect(Event)]`.
+//!
+//! `params`:
+//! - `event`: The [fully-qualified type name] of the event to trigger.
+//! - `value`: The value of the event to trigger.
+//! - `targets` (optional): An array of entity IDs to trigger the event for. If excluded, the
+//!   event is triggered without targets.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/list_events`
+//!
+//! List all reflectable registered event types. This method has no parameters.
+//!
+//! `result`: An array of [fully-qualified type names] of registered event types.
+//!
+//! ### `bevy/events+watch`
+//!
+//! Watch every event of a given type sent through its `Events` resource.
+//!
+//! `params`:
+//! - `event`: The [fully-qualified type name] of the event to watch.
+//!
+//! `result`: An array of the values of the events sent since the previous response (or since the
+//! watch started), in the order they were sent. Frames in which no event was sent produce no
+//! response.
+//!
+//! ### `bevy/asset/list`
+//!
+//! List the assets stored in the world. Only asset types registered with
+//! `AssetApp::register_asset_reflect` are listed. Assets that are still loading are not stored
+//! in the world yet and are therefore absent. Requires the `bevy_asset` feature.
+//!
+//! `params` (optional):
+//! - `asset`: The [fully-qualified type name] of the asset type to list. If excluded, every
+//!   reflectable asset type is listed.
+//!
+//! `result`: An array of objects with the following fields:
+//! - `asset`: The fully-qualified type name of the asset type.
+//! - `id`: The ID of the asset, either `{ "index": <bits> }` or `{ "uuid": <uuid> }`.
+//! - `path` (optional): The path the asset was loaded from.
+//! - `load_state` (optional): One of `not_loaded`, `loading`, `loaded` or `failed`, if the asset
+//!   is tracked by the `AssetServer`.
+//! - `error` (optional): The error that caused the asset to fail loading.
+//!
+//! ### `bevy/asset/get`
+//!
+//! Extract the value of a single asset. Requires the `bevy_asset` feature.
+//!
+//! `params`:
+//! - `asset`: The [fully-qualified type name] of the asset type.
+//! - `id`: The ID of the asset as reported by `bevy/asset/list`, or `{ "path": <path> }` to
+//!   select the asset of that type loaded from the given path.
+//!
+//! `result`:
+//! - `value`: The value of the asset.
+//!
+//! ### `bevy/asset/mutate`
+//!
+//! Mutate a field in an asset. The asset is marked as modified, so that systems depending on it
+//! pick up the change. Requires the `bevy_asset` feature.
+//!
+//! `params`:
+//! - `asset`: The [fully-qualified type name] of the asset type.
+//! - `id`: The ID of the asset, as in `bevy/asset/get`.
+//! - `path`: The path of the field within the asset. See
+//!   [`GetPath`](bevy_reflect::GetPath#syntax) for more information on formatting this string.
+//! - `value`: The value to insert at `path`.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/asset/reload`
+//!
+//! Reload every asset loaded from a path. The reload happens in the background. Requires the
+//! `bevy_asset` feature.
+//!
+//! `params`:
+//! - `path`: The asset path to reload.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/run_system`
+//!
+//! Run a one-shot system added with [`RemotePlugin::with_system`] or to the [`RemoteSystems`]
+//! resource.
+//!
+//! `params`:
+//! - `system`: The name the system was added with.
+//! - `input` (optional): The input of the system, for systems taking an [`In<T>`] input.
+//!
+//! `result`: The output of the system, or null if it returns `()`.
+//!
+//! ### `bevy/call_function`
+//!
+//! Call a function registered in the [`AppFunctionRegistry`](bevy_ecs::reflect::AppFunctionRegistry).
+//! Requires the `reflect_functions` feature.
+//!
+//! `params`:
+//! - `function`: The name the function was registered with.
+//! - `args` (optional): An array of the arguments of the function. Arguments taken by reference
+//!   are sent like owned ones.
+//!
+//! `result`: The return value of the function, or null if it returns `()`.
+//!
+//! ### `bevy/registry/schema`
+//!
+//! Retrieve the [JSON Schema] of every type in the type registry, describing the values accepted
+//! and returned by the other methods. Schemas refer to each other with `$ref`s of the form
+//! `#/$defs/<type path>`.
+//!
+//! `params` (optional):
+//! - `with_crates`: Only include types from these crates.
+//! - `without_crates`: Exclude types from these crates.
+//! - `type_limit`: An object with `with` and `without` arrays of reflected traits (such as
+//!   `"Component"` or `"Default"`) that types must or must not reflect.
+//!
+//! `result`: A map associating each type's [fully-qualified type name] to its
+//! [`JsonSchemaBevyType`](schemas::json_schema::JsonSchemaBevyType).
+//!
+//! The same schemas can be written to a file without running the server with
+//! [`JsonSchemaDocument`](schemas::json_schema::JsonSchemaDocument).
+//!
+//! [JSON Schema]: https://json-schema.org/specification
+//!
+//! ## Access control
+//!
+//! By default, any client that can reach a transport can call any method. To restrict this, pass a
+//! [`RemoteAccessControl`] to [`RemotePlugin::with_access_control`], or insert it as a resource.
+//! It can require a bearer token, restrict the methods that can be called, reject every method
+//! that modifies the world (read-only mode) and limit how often each method can be called. The
+//! rules apply to every transport. Over HTTP, the token is sent in an `Authorization: Bearer`
+//! header; over the Unix socket and standard input transports, each request carries it in a
+//! `"token"` member.
+//!
+//! Rejected requests fail with the [`UNAUTHORIZED`](error_codes::UNAUTHORIZED),
+//! [`METHOD_NOT_ALLOWED`](error_codes::METHOD_NOT_ALLOWED) or
+//! [`RATE_LIMITED`](error_codes::RATE_LIMITED) error codes.
+//!
 //! ## Custom methods
 //!
 //! In addition to the provided methods, the Bevy Remote Protocol can be extended to include custom
@@ -364,6 +656,9 @@
 //! [fully-qualified type names]: bevy_reflect::TypePath::type_path
 //! [fully-qualified type name]: bevy_reflect::TypePath::type_path
 
+extern crate alloc;
+
+use access_control::RemoteAccessControl;
 use async_channel::{Receiver, Sender};
 use bevy_app::{prelude::*, MainScheduleOrder};
 use bevy_derive::{Deref, DerefMut};
@@ -371,18 +666,31 @@ use bevy_ecs::{
     entity::Entity,
     resource::Resource,
     schedule::{IntoSystemConfigs, IntoSystemSetConfigs, ScheduleLabel, SystemSet},
-    system::{Commands, In, IntoSystem, ResMut, System, SystemId},
+    system::{BoxedSystem, Commands, In, IntoSystem, ResMut, System, SystemId, SystemInput},
     world::World,
 };
-use bevy_platform_support::collections::HashMap;
+use bevy_platform_support::collections::{HashMap, HashSet};
+use bevy_reflect::{FromReflect, Reflect, TypePath};
 use bevy_utils::prelude::default;
+use invoke_methods::RemoteSystems;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::RwLock;
 
+pub mod access_control;
+#[cfg(feature = "bevy_asset")]
+pub mod asset_methods;
 pub mod builtin_methods;
 #[cfg(feature = "http")]
 pub mod http;
+pub mod invoke_methods;
+#[cfg(any(feature = "unix_socket", feature = "stdio"))]
+mod ndjson;
+pub mod schemas;
+#[cfg(feature = "stdio")]
+pub mod stdio;
+#[cfg(feature = "unix_socket")]
+pub mod unix;
 
 const CHANNEL_SIZE: usize = 16;
 
@@ -395,14 +703,23 @@ const CHANNEL_SIZE: usize = 16;
 pub struct RemotePlugin {
     /// The verbs that the server will recognize and respond to.
     methods: RwLock<Vec<(String, RemoteMethodHandler)>>,
+    /// The rules that requests must follow, if any.
+    access_control: Option<RemoteAccessControl>,
+    /// Registers the one-shot systems that can be run with `bevy/run_system`.
+    systems: RwLock<Vec<RemoteSystemRegistration>>,
 }
 
+/// Registers a one-shot system in the [`World`] and adds it to the [`RemoteSystems`].
+type RemoteSystemRegistration = Box<dyn FnOnce(&mut World, &mut RemoteSystems) + Send + Sync>;
+
 impl RemotePlugin {
     /// Create a [`RemotePlugin`] with the default address and port but without
     /// any associated methods.
     fn empty() -> Self {
         Self {
             methods: RwLock::new(vec![]),
+            access_control: None,
+            systems: RwLock::new(vec![]),
         }
     }
 
@@ -433,11 +750,49 @@ impl RemotePlugin {
         ));
         self
     }
+
+    /// Add a one-shot system that clients can run with `bevy/run_system` using the given `name`.
+    ///
+    /// The system can take no input or an [`In<T>`] input, which is deserialized with
+    /// reflection. Its output is serialized with reflection and sent back to the client.
+    ///
+    /// See [`RemoteSystems`] to add systems after the app has been built.
+    #[must_use]
+    pub fn with_system<I, T, O, M>(
+        self,
+        name: impl Into<String>,
+        system: impl IntoSystem<I, O, M>,
+    ) -> Self
+    where
+        I: SystemInput + 'static,
+        for<'a> I: SystemInput<Inner<'a> = T>,
+        T: FromReflect + TypePath,
+        O: Reflect,
+    {
+        let name = name.into();
+        let system: BoxedSystem<I, O> = Box::new(IntoSystem::into_system(system));
+        self.systems.write().unwrap().push(Box::new(
+            move |world: &mut World, systems: &mut RemoteSystems| {
+                let id = world.register_boxed_system(system);
+                systems.insert(name, id);
+            },
+        ));
+        self
+    }
+
+    /// Check every request against the given rules before processing it.
+    ///
+    /// See the [`access_control`] module for more information.
+    #[must_use]
+    pub fn with_access_control(mut self, access_control: RemoteAccessControl) -> Self {
+        self.access_control = Some(access_control);
+        self
+    }
 }
 
 impl Default for RemotePlugin {
     fn default() -> Self {
-        Self::empty()
+        let plugin = Self::empty()
             .with_method(
                 builtin_methods::BRP_GET_METHOD,
                 builtin_methods::process_remote_get_request,
@@ -506,6 +861,93 @@ impl Default for RemotePlugin {
                 builtin_methods::BRP_REGISTRY_SCHEMA_METHOD,
                 builtin_methods::export_registry_types,
             )
+            .with_method(
+                builtin_methods::BRP_BATCH_METHOD,
+                builtin_methods::process_remote_batch_request,
+            )
+            .with_method(
+                builtin_methods::BRP_SCHEDULE_LIST_METHOD,
+                builtin_methods::process_remote_schedule_list_request,
+            )
+            .with_method(
+                builtin_methods::BRP_SCHEDULE_GRAPH_METHOD,
+                builtin_methods::process_remote_schedule_graph_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_ENABLE_METHOD,
+                builtin_methods::process_remote_stepping_enable_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_DISABLE_METHOD,
+                builtin_methods::process_remote_stepping_disable_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_STEP_SYSTEM_METHOD,
+                builtin_methods::process_remote_stepping_step_system_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_STEP_FRAME_METHOD,
+                builtin_methods::process_remote_stepping_step_frame_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_SET_BREAKPOINT_METHOD,
+                builtin_methods::process_remote_stepping_set_breakpoint_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_CLEAR_BREAKPOINT_METHOD,
+                builtin_methods::process_remote_stepping_clear_breakpoint_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_STATUS_METHOD,
+                builtin_methods::process_remote_stepping_status_request,
+            )
+            .with_method(
+                builtin_methods::BRP_SEND_EVENT_METHOD,
+                builtin_methods::process_remote_send_event_request,
+            )
+            .with_method(
+                builtin_methods::BRP_TRIGGER_EVENT_METHOD,
+                builtin_methods::process_remote_trigger_event_request,
+            )
+            .with_method(
+                builtin_methods::BRP_LIST_EVENTS_METHOD,
+                builtin_methods::process_remote_list_events_request,
+            )
+            .with_watching_method(
+                builtin_methods::BRP_EVENTS_AND_WATCH_METHOD,
+                builtin_methods::process_remote_events_watching_request,
+            )
+            .with_method(
+                invoke_methods::BRP_RUN_SYSTEM_METHOD,
+                invoke_methods::process_remote_run_system_request,
+            );
+
+        #[cfg(feature = "reflect_functions")]
+        let plugin = plugin.with_method(
+            invoke_methods::BRP_CALL_FUNCTION_METHOD,
+            invoke_methods::process_remote_call_function_request,
+        );
+
+        #[cfg(feature = "bevy_asset")]
+        let plugin = plugin
+            .with_method(
+                asset_methods::BRP_ASSET_LIST_METHOD,
+                asset_methods::process_remote_asset_list_request,
+            )
+            .with_method(
+                asset_methods::BRP_ASSET_GET_METHOD,
+                asset_methods::process_remote_asset_get_request,
+            )
+            .with_method(
+                asset_methods::BRP_ASSET_MUTATE_METHOD,
+                asset_methods::process_remote_asset_mutate_request,
+            )
+            .with_method(
+                asset_methods::BRP_ASSET_RELOAD_METHOD,
+                asset_methods::process_remote_asset_reload_request,
+            );
+
+        plugin
     }
 }
 
@@ -528,6 +970,16 @@ impl Plugin for RemotePlugin {
             );
         }
 
+        let mut remote_systems = RemoteSystems::default();
+        for register in self.systems.write().unwrap().drain(..) {
+            register(app.main_mut().world_mut(), &mut remote_systems);
+        }
+        app.insert_resource(remote_systems);
+
+        if let Some(access_control) = &self.access_control {
+            app.insert_resource(access_control.clone());
+        }
+
         app.init_schedule(RemoteLast)
             .world_mut()
             .resource_mut::<MainScheduleOrder>()
@@ -535,6 +987,7 @@ impl Plugin for RemotePlugin {
 
         app.insert_resource(remote_methods)
             .init_resource::<RemoteWatchingRequests>()
+            .init_resource::<CurrentRemoteWatch>()
             .add_systems(PreStartup, setup_mailbox_channel)
             .configure_sets(
                 RemoteLast,
@@ -635,7 +1088,38 @@ impl RemoteMethods {
 
 /// Holds the [`BrpMessage`]'s of all ongoing watching requests along with their handlers.
 #[derive(Debug, Resource, Default)]
-pub struct RemoteWatchingRequests(Vec<(BrpMessage, RemoteWatchingMethodSystemId)>);
+pub struct RemoteWatchingRequests {
+    /// Taken out of the resource while the handlers of the requests run.
+    requests: Vec<(RemoteWatchId, BrpMessage, RemoteWatchingMethodSystemId)>,
+    /// The IDs of all ongoing requests, including while `requests` is taken out.
+    ongoing: HashSet<RemoteWatchId>,
+    next_id: u64,
+}
+
+impl RemoteWatchingRequests {
+    /// Returns true if the watching request with the given `id` is still ongoing.
+    pub fn contains(&self, id: RemoteWatchId) -> bool {
+        self.ongoing.contains(&id)
+    }
+
+    fn push(&mut self, message: BrpMessage, system_id: RemoteWatchingMethodSystemId) {
+        let id = RemoteWatchId(self.next_id);
+        self.next_id += 1;
+        self.ongoing.insert(id);
+        self.requests.push((id, message, system_id));
+    }
+}
+
+/// Identifies an ongoing watching request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RemoteWatchId(u64);
+
+/// The watching request whose handler is currently running, if any.
+///
+/// The built-in watching handlers use this to keep state for each watch, such as the event
+/// cursors of `bevy/events+watch`.
+#[derive(Debug, Resource, Default)]
+pub(crate) struct CurrentRemoteWatch(pub(crate) Option<RemoteWatchId>);
 
 /// A single request from a Bevy Remote Protocol client to the server,
 /// serialized in JSON.
@@ -814,6 +1298,146 @@ impl BrpError {
         }
     }
 
+    /// Schedule wasn't found.
+    #[must_use]
+    pub fn schedule_not_found(schedule: &str) -> Self {
+        Self {
+            code: error_codes::SCHEDULE_NOT_FOUND,
+            message: format!("Schedule `{schedule}` not found"),
+            data: None,
+        }
+    }
+
+    /// System wasn't found in a schedule.
+    #[must_use]
+    pub fn system_not_found(system: usize, schedule: &str) -> Self {
+        Self {
+            code: error_codes::SYSTEM_NOT_FOUND,
+            message: format!("System {system} not found in schedule `{schedule}`"),
+            data: None,
+        }
+    }
+
+    /// System stepping is unavailable.
+    #[must_use]
+    pub fn stepping_unavailable() -> Self {
+        Self {
+            code: error_codes::STEPPING_UNAVAILABLE,
+            message: "System stepping requires the `bevy_debug_stepping` feature".to_string(),
+            data: None,
+        }
+    }
+
+    /// The `Events` resource of an event type was not added to the world.
+    #[must_use]
+    pub fn event_not_added(event: &str) -> Self {
+        Self {
+            code: error_codes::EVENT_NOT_ADDED,
+            message: format!("Event `{event}` has not been added to the world"),
+            data: None,
+        }
+    }
+
+    /// An arbitrary event error. Possibly related to reflection.
+    #[must_use]
+    pub fn event_error<E: ToString>(error: E) -> Self {
+        Self {
+            code: error_codes::EVENT_ERROR,
+            message: error.to_string(),
+            data: None,
+        }
+    }
+
+    /// Asset wasn't found.
+    #[must_use]
+    pub fn asset_not_found(asset: &str) -> Self {
+        Self {
+            code: error_codes::ASSET_NOT_FOUND,
+            message: format!("Asset `{asset}` not found"),
+            data: None,
+        }
+    }
+
+    /// An arbitrary asset error. Possibly related to reflection.
+    #[must_use]
+    pub fn asset_error<E: ToString>(error: E) -> Self {
+        Self {
+            code: error_codes::ASSET_ERROR,
+            message: error.to_string(),
+            data: None,
+        }
+    }
+
+    /// No one-shot system was registered with the given name.
+    #[must_use]
+    pub fn one_shot_system_not_found(system: &str) -> Self {
+        Self {
+            code: error_codes::ONE_SHOT_SYSTEM_NOT_FOUND,
+            message: format!("One-shot system `{system}` not found"),
+            data: None,
+        }
+    }
+
+    /// An arbitrary one-shot system error. Possibly related to reflection.
+    #[must_use]
+    pub fn one_shot_system_error<E: ToString>(error: E) -> Self {
+        Self {
+            code: error_codes::ONE_SHOT_SYSTEM_ERROR,
+            message: error.to_string(),
+            data: None,
+        }
+    }
+
+    /// No function was registered with the given name.
+    #[must_use]
+    pub fn function_not_found(function: &str) -> Self {
+        Self {
+            code: error_codes::FUNCTION_NOT_FOUND,
+            message: format!("Function `{function}` not found"),
+            data: None,
+        }
+    }
+
+    /// An arbitrary function error. Possibly related to reflection.
+    #[must_use]
+    pub fn function_error<E: ToString>(error: E) -> Self {
+        Self {
+            code: error_codes::FUNCTION_ERROR,
+            message: error.to_string(),
+            data: None,
+        }
+    }
+
+    /// The client didn't provide the token required by the [`RemoteAccessControl`].
+    #[must_use]
+    pub fn unauthorized() -> Self {
+        Self {
+            code: error_codes::UNAUTHORIZED,
+            message: "Missing or invalid authentication token".to_string(),
+            data: None,
+        }
+    }
+
+    /// The [`RemoteAccessControl`] doesn't allow calling the method.
+    #[must_use]
+    pub fn method_not_allowed(method: &str) -> Self {
+        Self {
+            code: error_codes::METHOD_NOT_ALLOWED,
+            message: format!("Method `{method}` is not allowed"),
+            data: None,
+        }
+    }
+
+    /// The method was called more often than its rate limit allows.
+    #[must_use]
+    pub fn rate_limited(method: &str) -> Self {
+        Self {
+            code: error_codes::RATE_LIMITED,
+            message: format!("Rate limit of method `{method}` exceeded"),
+            data: None,
+        }
+    }
+
     /// Attempt to reparent an entity to itself.
     #[must_use]
     pub fn self_reparent(entity: Entity) -> Self {
@@ -864,6 +1488,48 @@ pub mod error_codes {
 
     /// Could not find resource in the world.
     pub const RESOURCE_NOT_PRESENT: i16 = -23502;
+
+    /// Could not find schedule in the world.
+    pub const SCHEDULE_NOT_FOUND: i16 = -23601;
+
+    /// Could not find system in the schedule.
+    pub const SYSTEM_NOT_FOUND: i16 = -23602;
+
+    /// System stepping is unavailable, as the `bevy_debug_stepping` feature is disabled.
+    pub const STEPPING_UNAVAILABLE: i16 = -23603;
+
+    /// Could not reflect or find event.
+    pub const EVENT_ERROR: i16 = -23701;
+
+    /// Could not find the `Events` resource of an event in the world.
+    pub const EVENT_NOT_ADDED: i16 = -23702;
+
+    /// Could not reflect or find asset.
+    pub const ASSET_ERROR: i16 = -23801;
+
+    /// Could not find asset in the world.
+    pub const ASSET_NOT_FOUND: i16 = -23802;
+
+    /// Missing or invalid authentication token.
+    pub const UNAUTHORIZED: i16 = -23901;
+
+    /// The method is not allowed by the access control rules.
+    pub const METHOD_NOT_ALLOWED: i16 = -23902;
+
+    /// The rate limit of the method was exceeded.
+    pub const RATE_LIMITED: i16 = -23903;
+
+    /// Could not run a one-shot system.
+    pub const ONE_SHOT_SYSTEM_ERROR: i16 = -24001;
+
+    /// Could not find a one-shot system registered with the given name.
+    pub const ONE_SHOT_SYSTEM_NOT_FOUND: i16 = -24002;
+
+    /// Could not call a reflected function.
+    pub const FUNCTION_ERROR: i16 = -24101;
+
+    /// Could not find a reflected function registered with the given name.
+    pub const FUNCTION_NOT_FOUND: i16 = -24102;
 }
 
 /// The result of a request.
@@ -883,8 +1549,9 @@ pub enum BrpBatch {
 
 /// A message from the Bevy Remote Protocol server thread to the main world.
 ///
-/// This is placed in the [`BrpReceiver`].
+/// This is placed in the [`BrpReceiver`]. Transports create it with [`BrpMessage::new`].
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct BrpMessage {
     /// The request method.
     pub method: String,
@@ -892,12 +1559,41 @@ pub struct BrpMessage {
     /// The request params.
     pub params: Option<Value>,
 
+    /// The bearer token the client authenticated with, if any.
+    ///
+    /// This is checked by the [`RemoteAccessControl`] resource, if present.
+    pub token: Option<String>,
+
     /// The channel on which the response is to be sent.
     ///
     /// The value sent here is serialized and sent back to the client.
     pub sender: Sender<BrpResult>,
 }
 
+impl BrpMessage {
+    /// Creates a message for a request to `method` with the given `params`, whose result is sent
+    /// on `sender`.
+    pub fn new(
+        method: impl Into<String>,
+        params: Option<Value>,
+        sender: Sender<BrpResult>,
+    ) -> Self {
+        Self {
+            method: method.into(),
+            params,
+            token: None,
+            sender,
+        }
+    }
+
+    /// Sets the bearer token the client authenticated with, if any.
+    #[must_use]
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+}
+
 /// A resource holding the matching sender for the [`BrpReceiver`]'s receiver.
 #[derive(Debug, Resource, Deref, DerefMut)]
 pub struct BrpSender(Sender<BrpMessage>);
@@ -927,6 +1623,15 @@ fn process_remote_requests(world: &mut World) {
     }
 
     while let Ok(message) = world.resource_mut::<BrpReceiver>().try_recv() {
+        // Reject the request if the access control rules don't allow it.
+        if let Some(Err(error)) = world
+            .get_resource_mut::<RemoteAccessControl>()
+            .map(|mut access_control| access_control.check(&message))
+        {
+            let _ = message.sender.force_send(Err(error));
+            continue;
+        }
+
         // Fetch the handler for the method. If there's no such handler
         // registered, return an error.
         let Some(&handler) = world.resource::<RemoteMethods>().get(&message.method) else {
@@ -957,8 +1662,7 @@ fn process_remote_requests(world: &mut World) {
             RemoteMethodSystemId::Watching(id) => {
                 world
                     .resource_mut::<RemoteWatchingRequests>()
-                    .0
-                    .push((message, id));
+                    .push(message, id);
             }
         }
     }
@@ -967,21 +1671,24 @@ fn process_remote_requests(world: &mut World) {
 /// A system that checks all ongoing watching requests for changes that should be sent
 /// and handles it if so.
 fn process_ongoing_watching_requests(world: &mut World) {
-    world.resource_scope::<RemoteWatchingRequests, ()>(|world, requests| {
-        for (message, system_id) in requests.0.iter() {
-            let handler_result = process_single_ongoing_watching_request(world, message, system_id);
-            let sender_result = match handler_result {
-                Ok(Some(value)) => message.sender.try_send(Ok(value)),
-                Err(err) => message.sender.try_send(Err(err)),
-                Ok(None) => continue,
-            };
-
-            if sender_result.is_err() {
-                // The [`remove_closed_watching_requests`] system will clean this up.
-                message.sender.close();
-            }
+    // Handlers can still tell which watches are ongoing while the requests are taken out.
+    let requests = core::mem::take(&mut world.resource_mut::<RemoteWatchingRequests>().requests);
+    for (id, message, system_id) in &requests {
+        world.resource_mut::<CurrentRemoteWatch>().0 = Some(*id);
+        let handler_result = process_single_ongoing_watching_request(world, message, system_id);
+        let sender_result = match handler_result {
+            Ok(Some(value)) => message.sender.try_send(Ok(value)),
+            Err(err) => message.sender.try_send(Err(err)),
+            Ok(None) => continue,
+        };
+
+        if sender_result.is_err() {
+            // The [`remove_closed_watching_requests`] system will clean this up.
+            message.sender.close();
         }
-    });
+    }
+    world.resource_mut::<CurrentRemoteWatch>().0 = None;
+    world.resource_mut::<RemoteWatchingRequests>().requests = requests;
 }
 
 fn process_single_ongoing_watching_request(
@@ -999,13 +1706,14 @@ fn process_single_ongoing_watching_request(
 }
 
 fn remove_closed_watching_requests(mut requests: ResMut<RemoteWatchingRequests>) {
-    for i in (0..requests.0.len()).rev() {
-        let Some((message, _)) = requests.0.get(i) else {
+    for i in (0..requests.requests.len()).rev() {
+        let Some((_, message, _)) = requests.requests.get(i) else {
             unreachable!()
         };
 
         if message.sender.is_closed() {
-            requests.0.swap_remove(i);
+            let (id, ..) = requests.requests.swap_remove(i);
+            requests.ongoing.remove(&id);
         }
     }
 }
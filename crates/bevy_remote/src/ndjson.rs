@@ -0,0 +1,315 @@
+//! Newline-delimited JSON-RPC, shared by the stream-based BRP transports.
+//!
+//! Each line sent by a client contains a single request or a batch of requests. Each line sent
+//! back contains a single response or, for batches, an array of responses. Watching requests
+//! can't be batched: they stream one response line per update until the connection is closed.
+//!
+//! Since there are no headers to authenticate with, each request can carry the token required by
+//! the [`RemoteAccessControl`](crate::access_control::RemoteAccessControl) in a `"token"` member:
+//!
+//! ```json
+//! {"jsonrpc": "2.0", "id": 0, "method": "bevy/list", "token": "hunter2"}
+//! ```
+
+use crate::{error_codes, BrpBatch, BrpError, BrpMessage, BrpRequest, BrpResponse};
+use async_channel::{Receiver, Sender};
+use bevy_tasks::{
+    futures_lite::{future, Stream, StreamExt},
+    IoTaskPool,
+};
+use core::pin::pin;
+use serde_json::Value;
+
+/// The number of lines that can wait to be written back to a client.
+///
+/// Transports should give [`serve`] a channel bounded to this for its responses: once it is full,
+/// no more requests are read until the client reads its responses. Watching requests that can't
+/// hand over their updates in the meantime are ended, just like those of a client that left.
+pub(crate) const RESPONSE_CAPACITY: usize = 64;
+
+/// Dispatches every line of the `requests` stream to the world, sending each line to be written
+/// back to the client to `responses`.
+///
+/// Returns once the `requests` stream ends or `responses` is closed, which also stops the
+/// watching requests, even those without any update to send.
+pub(crate) async fn serve<E>(
+    requests: impl Stream<Item = Result<String, E>>,
+    request_sender: Sender<BrpMessage>,
+    responses: Sender<String>,
+) {
+    // Dropping the sender when returning wakes up every watcher, so that they stop.
+    let (_connection, disconnected) = async_channel::bounded::<()>(1);
+    let connection = Connection {
+        request_sender,
+        responses,
+        disconnected,
+    };
+
+    let mut requests = pin!(requests);
+    while let Some(Ok(line)) = requests.next().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        process_line(&line, &connection).await;
+        if connection.responses.is_closed() {
+            return;
+        }
+    }
+}
+
+/// The channels of a client connection.
+struct Connection {
+    /// Sends the requests of the client to the world.
+    request_sender: Sender<BrpMessage>,
+    /// Sends the lines to write back to the client.
+    responses: Sender<String>,
+    /// Closed once the client disconnected.
+    disconnected: Receiver<()>,
+}
+
+/// Processes a single line containing a request or a batch of requests.
+async fn process_line(line: &str, connection: &Connection) {
+    let response = match serde_json::from_str::<BrpBatch>(line) {
+        Ok(BrpBatch::Single(request)) => {
+            match process_single_request(request, connection, true).await {
+                Some(response) => serde_json::to_string(&response),
+                None => return,
+            }
+        }
+        Ok(BrpBatch::Batch(requests)) => {
+            let mut batch = Vec::new();
+            for request in requests {
+                batch.extend(process_single_request(request, connection, false).await);
+            }
+            serde_json::to_string(&batch)
+        }
+        Err(err) => serde_json::to_string(&BrpResponse::new(
+            None,
+            Err(BrpError {
+                code: error_codes::PARSE_ERROR,
+                message: err.to_string(),
+                data: None,
+            }),
+        )),
+    };
+
+    if let Ok(response) = response {
+        let _ = connection.responses.send(response).await;
+    }
+}
+
+/// Processes a single request, returning its response.
+///
+/// If the request is a watching request and `allow_watch` is true, its updates are forwarded to
+/// the client in the background and [`None`] is returned.
+async fn process_single_request(
+    request: Value,
+    connection: &Connection,
+    allow_watch: bool,
+) -> Option<BrpResponse> {
+    // Reach in and get the request ID early so that we can report it even when parsing fails.
+    let id = request.as_object().and_then(|map| map.get("id")).cloned();
+    // The token isn't part of JSON-RPC, so it's also taken out of the raw request.
+    let token = request
+        .as_object()
+        .and_then(|map| map.get("token"))
+        .and_then(Value::as_str)
+        .map(str::to_owned);
+
+    let request: BrpRequest = match serde_json::from_value(request) {
+        Ok(v) => v,
+        Err(err) => {
+            return Some(BrpResponse::new(
+                id,
+                Err(BrpError {
+                    code: error_codes::INVALID_REQUEST,
+                    message: err.to_string(),
+                    data: None,
+                }),
+            ));
+        }
+    };
+
+    if request.jsonrpc != "2.0" {
+        return Some(BrpResponse::new(
+            id,
+            Err(BrpError {
+                code: error_codes::INVALID_REQUEST,
+                message: String::from("JSON-RPC request requires `\"jsonrpc\": \"2.0\"`"),
+                data: None,
+            }),
+        ));
+    }
+
+    let watch = request.method.contains("+watch");
+    if watch && !allow_watch {
+        return Some(BrpResponse::new(
+            request.id,
+            Err(BrpError {
+                code: error_codes::INVALID_REQUEST,
+                message: "Streaming can not be used in batch requests".to_string(),
+                data: None,
+            }),
+        ));
+    }
+
+    let size = if watch { 8 } else { 1 };
+    let (result_sender, result_receiver) = async_channel::bounded(size);
+
+    let _ = connection
+        .request_sender
+        .send(BrpMessage::new(request.method, request.params, result_sender).with_token(token))
+        .await;
+
+    if watch {
+        let responses = connection.responses.clone();
+        let disconnected = connection.disconnected.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                // Dropping the receiver closes the channel, which stops the watcher.
+                while let Some(result) =
+                    future::or(async { result_receiver.recv().await.ok() }, async {
+                        let _ = disconnected.recv().await;
+                        None
+                    })
+                    .await
+                {
+                    let Ok(response) =
+                        serde_json::to_string(&BrpResponse::new(request.id.clone(), result))
+                    else {
+                        continue;
+                    };
+                    if responses.send(response).await.is_err() {
+                        break;
+                    }
+                }
+            })
+            .detach();
+        None
+    } else {
+        let result = result_receiver.recv().await.ok()?;
+        Some(BrpResponse::new(request.id, result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_tasks::{
+        block_on,
+        futures_lite::{future, stream},
+        TaskPool,
+    };
+    use core::cell::Cell;
+    use serde_json::json;
+
+    #[test]
+    fn requests_and_batches() {
+        let (request_sender, request_receiver) = async_channel::bounded::<BrpMessage>(1);
+        let (response_sender, response_receiver) = async_channel::bounded(RESPONSE_CAPACITY);
+
+        // Stand in for the world: answer every request with its method.
+        let world = async move {
+            while let Ok(message) = request_receiver.recv().await {
+                let _ = message.sender.send(Ok(json!(message.method))).await;
+            }
+        };
+
+        let lines = [
+            r#"{"jsonrpc":"2.0","id":1,"method":"bevy/list"}"#,
+            "",
+            r#"[{"jsonrpc":"2.0","id":2,"method":"bevy/get"},{"jsonrpc":"2.0","id":3,"method":"bevy/get+watch"}]"#,
+            "not json",
+        ]
+        .map(|line| Ok::<_, ()>(line.to_owned()));
+        block_on(future::zip(
+            serve(stream::iter(lines), request_sender, response_sender),
+            world,
+        ));
+
+        let response =
+            || -> Value { serde_json::from_str(&response_receiver.try_recv().unwrap()).unwrap() };
+        assert_eq!(
+            response(),
+            json!({ "jsonrpc": "2.0", "id": 1, "result": "bevy/list" })
+        );
+        let batch = response();
+        assert_eq!(batch[0]["result"], json!("bevy/get"));
+        assert_eq!(batch[1]["id"], json!(3));
+        assert_eq!(
+            batch[1]["error"]["code"],
+            json!(error_codes::INVALID_REQUEST)
+        );
+        assert_eq!(response()["error"]["code"], json!(error_codes::PARSE_ERROR));
+    }
+
+    #[test]
+    fn full_responses_stop_reading() {
+        let (request_sender, request_receiver) = async_channel::bounded::<BrpMessage>(1);
+        let (response_sender, response_receiver) = async_channel::bounded(1);
+
+        let world = async move {
+            while let Ok(message) = request_receiver.recv().await {
+                let _ = message.sender.send(Ok(json!(message.method))).await;
+            }
+        };
+
+        let read = Cell::new(0);
+        let lines = [
+            r#"{"jsonrpc":"2.0","id":1,"method":"bevy/list"}"#,
+            r#"{"jsonrpc":"2.0","id":2,"method":"bevy/get"}"#,
+            r#"{"jsonrpc":"2.0","id":3,"method":"bevy/query"}"#,
+        ]
+        .map(|line| Ok::<_, ()>(line.to_owned()));
+        let lines = stream::iter(lines).inspect(|_| read.set(read.get() + 1));
+
+        block_on(async {
+            let mut serve = pin!(serve(lines, request_sender, response_sender));
+            let mut world = pin!(world);
+            for _ in 0..100 {
+                assert!(future::poll_once(&mut serve).await.is_none());
+                future::poll_once(&mut world).await;
+            }
+            // The second response is waiting for room, so the third request isn't read yet.
+            assert_eq!(read.get(), 2);
+            assert_eq!(response_receiver.len(), 1);
+
+            // Reading the responses lets the connection go on.
+            let responses = async {
+                let mut responses = Vec::new();
+                while let Ok(response) = response_receiver.recv().await {
+                    let response: Value = serde_json::from_str(&response).unwrap();
+                    responses.push(response["result"].clone());
+                }
+                responses
+            };
+            let (((), ()), responses) = future::zip(future::zip(serve, world), responses).await;
+            assert_eq!(
+                responses,
+                [json!("bevy/list"), json!("bevy/get"), json!("bevy/query")]
+            );
+        });
+        assert_eq!(read.get(), 3);
+    }
+
+    #[test]
+    fn tokens_and_disconnections() {
+        let (request_sender, request_receiver) = async_channel::bounded::<BrpMessage>(1);
+        let (response_sender, _response_receiver) = async_channel::bounded(RESPONSE_CAPACITY);
+
+        let lines = [r#"{"jsonrpc":"2.0","id":1,"method":"bevy/get+watch","token":"secret"}"#]
+            .map(|line| Ok::<_, ()>(line.to_owned()));
+        IoTaskPool::get_or_init(TaskPool::new).with_local_executor(|executor| {
+            block_on(executor.run(serve(stream::iter(lines), request_sender, response_sender)));
+            let watch = request_receiver.try_recv().unwrap();
+            assert_eq!(watch.token.as_deref(), Some("secret"));
+
+            // The watcher has no update to send, but still stops once the client is gone.
+            block_on(executor.run(async {
+                while !watch.sender.is_closed() {
+                    future::yield_now().await;
+                }
+            }));
+        });
+    }
+}
@@ -0,0 +1,958 @@
+//! Export of the types in a [`TypeRegistry`] as [JSON Schema].
+//!
+//! Each type is described by a [`JsonSchemaBevyType`]. Types refer to each other with `$ref`s of
+//! the form `#/$defs/<type path>`, so the schemas are meant to live under the `$defs` of a single
+//! document, such as the [`JsonSchemaDocument`] built by [`JsonSchemaDocument::from_registry`].
+//!
+//! The schemas describe the format produced by [`TypedReflectSerializer`] and accepted by
+//! [`TypedReflectDeserializer`](bevy_reflect::serde::TypedReflectDeserializer). Types that
+//! reflect `Serialize` are serialized with their own [`Serialize`] implementation instead, which
+//! the exported schema only describes if it has the same layout as a derived implementation.
+//!
+//! [JSON Schema]: https://json-schema.org/specification
+
+use alloc::collections::BTreeMap;
+use core::any::TypeId;
+use std::{fs, io, path::Path};
+
+use bevy_ecs::reflect::{ReflectComponent, ReflectEvent, ReflectResource};
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::{
+    attributes::CustomAttributes,
+    prelude::ReflectDefault,
+    serde::{SerializationData, TypedReflectSerializer},
+    GenericInfo, NamedField, OpaqueInfo, PartialReflect, ReflectDeserialize, ReflectSerialize,
+    TypeInfo, TypeRegistration, TypeRegistry, UnnamedField, VariantInfo,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::builtin_methods::BrpJsonSchemaQueryFilter;
+
+/// The dialect of JSON Schema that exported documents follow.
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// A self-contained JSON Schema document holding the schemas of many types under `$defs`.
+///
+/// This can be used to export the schema of an app without running the remote server:
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_ecs::reflect::AppTypeRegistry;
+/// # use bevy_remote::schemas::json_schema::JsonSchemaDocument;
+/// # let app = App::new();
+/// let registry = app.world().resource::<AppTypeRegistry>().read();
+/// JsonSchemaDocument::from_registry(&registry, &Default::default())
+///     .write_to_file("schema.json")
+///     .expect("failed to write the schema");
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct JsonSchemaDocument {
+    /// The dialect of the document, see [`JSON_SCHEMA_DIALECT`].
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    /// The schemas of the exported types, keyed by their full type path.
+    #[serde(rename = "$defs")]
+    pub defs: BTreeMap<String, JsonSchemaBevyType>,
+}
+
+impl JsonSchemaDocument {
+    /// Exports every type in the `registry` that passes the `filter`.
+    pub fn from_registry(registry: &TypeRegistry, filter: &BrpJsonSchemaQueryFilter) -> Self {
+        Self {
+            schema: JSON_SCHEMA_DIALECT.to_owned(),
+            defs: export_registry_schemas(registry, filter).collect(),
+        }
+    }
+
+    /// Writes the document to the file at `path` as pretty-printed JSON.
+    ///
+    /// The keys of every object are sorted, so exporting the same types always produces the
+    /// same file.
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let value = serde_json::to_value(self)?;
+        fs::write(path, serde_json::to_string_pretty(&value)?)
+    }
+}
+
+/// Exports the schema of every type in the `registry` that passes the `filter`, along with its
+/// full type path.
+pub fn export_registry_schemas<'a>(
+    registry: &'a TypeRegistry,
+    filter: &'a BrpJsonSchemaQueryFilter,
+) -> impl Iterator<Item = (String, JsonSchemaBevyType)> + 'a {
+    registry
+        .iter()
+        .map(|reg| export_type(reg, registry))
+        .filter(|(_, schema)| filter_matches(filter, schema))
+}
+
+/// Returns true if the `schema` passes the `filter`.
+fn filter_matches(filter: &BrpJsonSchemaQueryFilter, schema: &JsonSchemaBevyType) -> bool {
+    if let Some(crate_name) = &schema.crate_name {
+        if !filter.with_crates.is_empty() && !filter.with_crates.iter().any(|c| crate_name.eq(c)) {
+            return false;
+        }
+        if !filter.without_crates.is_empty()
+            && filter.without_crates.iter().any(|c| crate_name.eq(c))
+        {
+            return false;
+        }
+    }
+    if !filter.type_limit.with.is_empty()
+        && !filter
+            .type_limit
+            .with
+            .iter()
+            .any(|c| schema.reflect_types.iter().any(|cc| c.eq(cc)))
+    {
+        return false;
+    }
+    if !filter.type_limit.without.is_empty()
+        && filter
+            .type_limit
+            .without
+            .iter()
+            .any(|c| schema.reflect_types.iter().any(|cc| c.eq(cc)))
+    {
+        return false;
+    }
+
+    true
+}
+
+/// Exports schema info for a given type
+pub fn export_type(
+    reg: &TypeRegistration,
+    registry: &TypeRegistry,
+) -> (String, JsonSchemaBevyType) {
+    let t = reg.type_info();
+    let binding = t.type_path_table();
+
+    let short_path = binding.short_path();
+    let type_path = binding.path();
+    let mut typed_schema = JsonSchemaBevyType {
+        reflect_types: get_registered_reflect_types(reg),
+        short_path: short_path.to_owned(),
+        type_path: type_path.to_owned(),
+        crate_name: binding.crate_name().map(str::to_owned),
+        module_path: binding.module_path().map(str::to_owned),
+        generics: t.generics().iter().map(JsonSchemaGeneric::from).collect(),
+        default: reg.data::<ReflectDefault>().and_then(|reflect_default| {
+            serialize_value(reflect_default.default().as_partial_reflect(), registry)
+        }),
+        ..Default::default()
+    };
+    #[cfg(feature = "documentation")]
+    {
+        typed_schema.description = t.docs().map(str::to_owned);
+    }
+    let serialization_data = reg.data::<SerializationData>();
+    let is_serialized =
+        |index: usize| !serialization_data.is_some_and(|data| data.is_field_skipped(index));
+
+    match t {
+        TypeInfo::Struct(info) => {
+            let fields = info
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| is_serialized(*index))
+                .map(|(_, field)| field);
+            typed_schema.properties = fields
+                .clone()
+                .map(|field| (field.name().to_owned(), named_field_schema(field, registry)))
+                .collect::<HashMap<_, _>>();
+            typed_schema.required = fields
+                .filter(|field| !is_option(field.type_path()))
+                .map(|f| f.name().to_owned())
+                .collect::<Vec<_>>();
+            typed_schema.additional_properties = Some(false.into());
+            typed_schema.schema_type = Some(SchemaType::Object);
+            typed_schema.kind = SchemaKind::Struct;
+            typed_schema.custom_attributes =
+                export_custom_attributes(info.custom_attributes(), registry);
+        }
+        TypeInfo::Enum(info) if is_option(type_path) => {
+            // `Option` is serialized as either `null` or its inner value.
+            typed_schema.kind = SchemaKind::Optional;
+            typed_schema.one_of = vec![json!({ "type": "null" })];
+            if let Some(VariantInfo::Tuple(some)) = info.variant("Some") {
+                typed_schema
+                    .one_of
+                    .extend(some.field_at(0).map(SchemaJsonReference::ref_type));
+            }
+        }
+        TypeInfo::Enum(info) => {
+            typed_schema.kind = SchemaKind::Enum;
+            typed_schema.custom_attributes =
+                export_custom_attributes(info.custom_attributes(), registry);
+
+            let unit_variants = info
+                .iter()
+                .filter(|variant| matches!(variant, VariantInfo::Unit(_)))
+                .count();
+            // Unit variants are serialized as strings and the others as objects, so enums mixing
+            // both kinds can't be given a single type.
+            typed_schema.schema_type = if unit_variants == info.variant_len() {
+                Some(SchemaType::String)
+            } else if unit_variants == 0 {
+                Some(SchemaType::Object)
+            } else {
+                None
+            };
+            typed_schema.one_of = info
+                .iter()
+                .map(|variant| variant_schema(variant, type_path, registry))
+                .collect::<Vec<_>>();
+        }
+        TypeInfo::TupleStruct(info) => {
+            typed_schema.kind = SchemaKind::TupleStruct;
+            typed_schema.custom_attributes =
+                export_custom_attributes(info.custom_attributes(), registry);
+            if info.field_len() == 1 && serialization_data.is_none() {
+                // Newtype structs are serialized as their only field.
+                typed_schema.reference =
+                    info.field_at(0).map(|field| definition(field.type_path()));
+            } else {
+                typed_schema.schema_type = Some(SchemaType::Array);
+                typed_schema.prefix_items = info
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| is_serialized(*index))
+                    .map(|(_, field)| unnamed_field_schema(field, registry))
+                    .collect::<Vec<_>>();
+                typed_schema.items = Some(false.into());
+                typed_schema.min_items = Some(typed_schema.prefix_items.len());
+                typed_schema.max_items = Some(typed_schema.prefix_items.len());
+            }
+        }
+        TypeInfo::List(info) => {
+            typed_schema.schema_type = Some(SchemaType::Array);
+            typed_schema.kind = SchemaKind::List;
+            typed_schema.items = info.item_ty().ref_type().into();
+        }
+        TypeInfo::Array(info) => {
+            typed_schema.schema_type = Some(SchemaType::Array);
+            typed_schema.kind = SchemaKind::Array;
+            typed_schema.items = info.item_ty().ref_type().into();
+            typed_schema.min_items = Some(info.capacity());
+            typed_schema.max_items = Some(info.capacity());
+        }
+        TypeInfo::Map(info) => {
+            typed_schema.schema_type = Some(SchemaType::Object);
+            typed_schema.kind = SchemaKind::Map;
+            typed_schema.key_type = info.key_ty().ref_type().into();
+            typed_schema.value_type = info.value_ty().ref_type().into();
+            typed_schema.additional_properties = info.value_ty().ref_type().into();
+        }
+        TypeInfo::Tuple(info) => {
+            typed_schema.schema_type = Some(SchemaType::Array);
+            typed_schema.kind = SchemaKind::Tuple;
+            typed_schema.prefix_items = info
+                .iter()
+                .map(|field| unnamed_field_schema(field, registry))
+                .collect::<Vec<_>>();
+            typed_schema.items = Some(false.into());
+            typed_schema.min_items = Some(info.field_len());
+            typed_schema.max_items = Some(info.field_len());
+        }
+        TypeInfo::Set(info) => {
+            typed_schema.schema_type = Some(SchemaType::Array);
+            typed_schema.kind = SchemaKind::Set;
+            typed_schema.items = info.value_ty().ref_type().into();
+            typed_schema.unique_items = Some(true);
+        }
+        TypeInfo::Opaque(info) => {
+            typed_schema.schema_type = info.map_json_type();
+            typed_schema.kind = SchemaKind::Value;
+            (typed_schema.minimum, typed_schema.maximum) = integer_bounds(info.type_path());
+        }
+    };
+
+    (t.type_path().to_owned(), typed_schema)
+}
+
+/// Builds the schema of a single enum variant.
+///
+/// Unit variants are serialized as their name, while the others are serialized as an object
+/// with their name as the only key.
+fn variant_schema(variant: &VariantInfo, type_path: &str, registry: &TypeRegistry) -> Value {
+    let (kind, mut schema) = match variant {
+        VariantInfo::Unit(v) => ("Unit", json!({ "type": "string", "const": v.name() })),
+        VariantInfo::Struct(v) => (
+            "Struct",
+            json!({
+                "type": "object",
+                "properties": v
+                    .iter()
+                    .map(|field| (field.name().to_owned(), named_field_schema(field, registry)))
+                    .collect::<Map<_, _>>(),
+                "additionalProperties": false,
+                "required": v
+                    .iter()
+                    .filter(|field| !is_option(field.type_path()))
+                    .map(NamedField::name)
+                    .collect::<Vec<_>>(),
+            }),
+        ),
+        VariantInfo::Tuple(v) if v.field_len() == 1 => (
+            "Tuple",
+            v.field_at(0)
+                .map(|field| unnamed_field_schema(field, registry))
+                .unwrap_or_default(),
+        ),
+        VariantInfo::Tuple(v) => (
+            "Tuple",
+            json!({
+                "type": "array",
+                "prefixItems": v
+                    .iter()
+                    .map(|field| unnamed_field_schema(field, registry))
+                    .collect::<Vec<_>>(),
+                "items": false,
+                "minItems": v.field_len(),
+                "maxItems": v.field_len(),
+            }),
+        ),
+    };
+
+    if !matches!(variant, VariantInfo::Unit(_)) {
+        schema = json!({
+            "type": "object",
+            "properties": { variant.name(): schema },
+            "required": [variant.name()],
+            "additionalProperties": false,
+        });
+    }
+
+    let object = schema.as_object_mut().expect("variant schemas are objects");
+    object.insert("kind".into(), kind.into());
+    object.insert(
+        "typePath".into(),
+        format!("{}::{}", type_path, variant.name()).into(),
+    );
+    object.insert("shortPath".into(), variant.name().into());
+    let custom_attributes = export_custom_attributes(variant.custom_attributes(), registry);
+    if !custom_attributes.is_empty() {
+        object.insert("customAttributes".into(), json!(custom_attributes));
+    }
+    #[cfg(feature = "documentation")]
+    if let Some(docs) = variant.docs() {
+        object.insert("description".into(), docs.into());
+    }
+
+    schema
+}
+
+/// Builds the schema of a named field: a reference to its type along with its attributes.
+fn named_field_schema(field: &NamedField, registry: &TypeRegistry) -> Value {
+    let mut schema = field.ref_type();
+    let object = schema.as_object_mut().expect("references are objects");
+    let custom_attributes = export_custom_attributes(field.custom_attributes(), registry);
+    if !custom_attributes.is_empty() {
+        object.insert("customAttributes".into(), json!(custom_attributes));
+    }
+    #[cfg(feature = "documentation")]
+    if let Some(docs) = field.docs() {
+        object.insert("description".into(), docs.into());
+    }
+    schema
+}
+
+/// Builds the schema of an unnamed field: a reference to its type along with its attributes.
+fn unnamed_field_schema(field: &UnnamedField, registry: &TypeRegistry) -> Value {
+    let mut schema = field.ref_type();
+    let object = schema.as_object_mut().expect("references are objects");
+    let custom_attributes = export_custom_attributes(field.custom_attributes(), registry);
+    if !custom_attributes.is_empty() {
+        object.insert("customAttributes".into(), json!(custom_attributes));
+    }
+    #[cfg(feature = "documentation")]
+    if let Some(docs) = field.docs() {
+        object.insert("description".into(), docs.into());
+    }
+    schema
+}
+
+/// Serializes every custom attribute that can be serialized, keyed by the type path of the
+/// attribute.
+fn export_custom_attributes(
+    attributes: &CustomAttributes,
+    registry: &TypeRegistry,
+) -> HashMap<String, Value> {
+    attributes
+        .iter()
+        .filter_map(|(_, attribute)| {
+            let value = serialize_value(attribute.as_partial_reflect(), registry)?;
+            Some((attribute.reflect_type_path().to_owned(), value))
+        })
+        .collect()
+}
+
+/// Serializes a reflected value the way the remote protocol does, if possible.
+fn serialize_value(value: &dyn PartialReflect, registry: &TypeRegistry) -> Option<Value> {
+    serde_json::to_value(TypedReflectSerializer::new(value, registry)).ok()
+}
+
+/// Returns true if the given type path is the path of an [`Option`].
+fn is_option(type_path: &str) -> bool {
+    type_path.starts_with("core::option::Option")
+}
+
+/// Returns the `$ref` value pointing at the schema of the given type.
+fn definition(type_path: &str) -> String {
+    format!("#/$defs/{type_path}")
+}
+
+/// Returns the smallest and largest values of the integer type with the given path, if it is
+/// one.
+fn integer_bounds(type_path: &str) -> (Option<Value>, Option<Value>) {
+    macro_rules! bounds {
+        ($($ty:ident),*) => {
+            match type_path {
+                $(stringify!($ty) => (Some($ty::MIN.into()), Some($ty::MAX.into())),)*
+                "u128" | "usize" => (Some(0.into()), None),
+                _ => (None, None),
+            }
+        };
+    }
+    bounds!(u8, u16, u32, u64, i8, i16, i32, i64)
+}
+
+fn get_registered_reflect_types(reg: &TypeRegistration) -> Vec<String> {
+    // Vec could be moved to allow registering more types by game maker.
+    let registered_reflect_types: [(TypeId, &str); 6] = [
+        { (TypeId::of::<ReflectComponent>(), "Component") },
+        { (TypeId::of::<ReflectResource>(), "Resource") },
+        { (TypeId::of::<ReflectEvent>(), "Event") },
+        { (TypeId::of::<ReflectDefault>(), "Default") },
+        { (TypeId::of::<ReflectSerialize>(), "Serialize") },
+        { (TypeId::of::<ReflectDeserialize>(), "Deserialize") },
+    ];
+    let mut result = Vec::new();
+    for (id, name) in registered_reflect_types {
+        if reg.data_by_id(id).is_some() {
+            result.push(name.to_owned());
+        }
+    }
+    result
+}
+
+/// JSON Schema type for Bevy Registry Types
+/// It tries to follow this standard: <https://json-schema.org/specification>
+///
+/// To take the full advantage from info provided by Bevy registry it provides extra fields
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonSchemaBevyType {
+    /// Bevy specific field, short path of the type.
+    pub short_path: String,
+    /// Bevy specific field, full path of the type.
+    pub type_path: String,
+    /// Bevy specific field, path of the module that type is part of.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub module_path: Option<String>,
+    /// Bevy specific field, name of the crate that type is part of.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub crate_name: Option<String>,
+    /// Bevy specific field, names of the types that type reflects.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub reflect_types: Vec<String>,
+    /// Bevy specific field, [`TypeInfo`] type mapping.
+    pub kind: SchemaKind,
+    /// Bevy specific field, the generic parameters the type was instantiated with.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub generics: Vec<JsonSchemaGeneric>,
+    /// Bevy specific field, the serialized values of the custom attributes of the type, keyed by
+    /// the type path of each attribute.
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub custom_attributes: HashMap<String, Value>,
+    /// Bevy specific field, provided when [`SchemaKind`] `kind` field is equal to [`SchemaKind::Map`].
+    ///
+    /// It contains type info of key of the Map.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub key_type: Option<Value>,
+    /// Bevy specific field, provided when [`SchemaKind`] `kind` field is equal to [`SchemaKind::Map`].
+    ///
+    /// It contains type info of value of the Map.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub value_type: Option<Value>,
+    /// The documentation of the type.
+    ///
+    /// Only provided when the `documentation` feature is enabled.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub description: Option<String>,
+    /// The type keyword is fundamental to JSON Schema. It specifies the data type for a schema.
+    ///
+    /// It is absent when values of the type can have different data types, such as enums
+    /// mixing unit and non-unit variants, or when the data type is unknown.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none", default)]
+    pub schema_type: Option<SchemaType>,
+    /// A reference to the schema that values of this type must validate against.
+    ///
+    /// Provided for newtype structs, which are serialized as their only field.
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none", default)]
+    pub reference: Option<String>,
+    /// The value of the type when it is created with its reflected [`Default`] implementation.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub default: Option<Value>,
+    /// The behavior of this keyword depends on the presence and annotation results of "properties"
+    /// and "patternProperties" within the same schema object.
+    /// Validation with "additionalProperties" applies only to the child
+    /// values of instance names that do not appear in the annotation results of either "properties" or "patternProperties".
+    ///
+    /// This is `false` for structs and the schema of the values for maps.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub additional_properties: Option<Value>,
+    /// Validation succeeds if, for each name that appears in both the instance and as a name
+    /// within this keyword's value, the child instance for that name successfully validates
+    /// against the corresponding schema.
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub properties: HashMap<String, Value>,
+    /// An object instance is valid against this keyword if every item in the array is the name of a property in the instance.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub required: Vec<String>,
+    /// An instance validates successfully against this keyword if it validates successfully against exactly one schema defined by this keyword's value.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub one_of: Vec<Value>,
+    /// Validation succeeds if each element of the instance validates against the schema at the same position, if any. This keyword does not constrain the length of the array. If the array is longer than this keyword's value, this keyword validates only the prefix of matching length.
+    ///
+    /// This keyword produces an annotation value which is the largest index to which this keyword
+    /// applied a subschema. The value MAY be a boolean true if a subschema was applied to every
+    /// index of the instance, such as is produced by the "items" keyword.
+    /// This annotation affects the behavior of "items" and "unevaluatedItems".
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub prefix_items: Vec<Value>,
+    /// This keyword applies its subschema to all instance elements at indexes greater
+    /// than the length of the "prefixItems" array in the same schema object,
+    /// as reported by the annotation result of that "prefixItems" keyword.
+    /// If no such annotation result exists, "items" applies its subschema to all
+    /// instance array elements.
+    ///
+    /// If the "items" subschema is applied to any positions within the instance array,
+    /// it produces an annotation result of boolean true, indicating that all remaining
+    /// array elements have been evaluated against this keyword's subschema.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub items: Option<Value>,
+    /// An array instance is valid against this keyword if its size is greater than, or equal
+    /// to, the value of this keyword.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub min_items: Option<usize>,
+    /// An array instance is valid against this keyword if its size is less than, or equal to,
+    /// the value of this keyword.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub max_items: Option<usize>,
+    /// If this keyword is `true`, an array instance is valid only if all of its elements are
+    /// unique.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub unique_items: Option<bool>,
+    /// A numeric instance is valid only if it is greater than or equal to this value.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub minimum: Option<Value>,
+    /// A numeric instance is valid only if it is less than or equal to this value.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub maximum: Option<Value>,
+}
+
+/// A generic parameter of a type in a [`JsonSchemaBevyType`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonSchemaGeneric {
+    /// The name of the parameter, e.g. `T`.
+    pub name: String,
+    /// A reference to the schema of the type the parameter was instantiated with.
+    ///
+    /// For const parameters, this is the type of the constant.
+    #[serde(rename = "$ref")]
+    pub reference: String,
+    /// Whether the parameter is a const parameter.
+    #[serde(skip_serializing_if = "core::ops::Not::not", default)]
+    pub is_const: bool,
+}
+
+impl From<&GenericInfo> for JsonSchemaGeneric {
+    fn from(info: &GenericInfo) -> Self {
+        Self {
+            name: info.name().to_string(),
+            reference: definition(info.type_path()),
+            is_const: info.is_const(),
+        }
+    }
+}
+
+/// Kind of json schema, maps [`TypeInfo`] type
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub enum SchemaKind {
+    /// Struct
+    #[default]
+    Struct,
+    /// Enum type
+    Enum,
+    /// An [`Option`], serialized as either null or its inner value
+    Optional,
+    /// A key-value map
+    Map,
+    /// Array
+    Array,
+    /// List
+    List,
+    /// Fixed size collection of items
+    Tuple,
+    /// Fixed size collection of items with named fields
+    TupleStruct,
+    /// Set of unique values
+    Set,
+    /// Single value, eg. primitive types
+    Value,
+}
+
+/// Type of json schema
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaType {
+    /// Represents a string value.
+    String,
+
+    /// Represents a floating-point number.
+    Number,
+
+    /// Represents an integer, see the `minimum` and `maximum` of the schema for its range.
+    Integer,
+
+    /// Represents an object with key-value pairs.
+    Object,
+
+    /// Represents an array of values.
+    Array,
+
+    /// Represents a boolean value (true or false).
+    Boolean,
+
+    /// Represents a null value.
+    Null,
+}
+
+/// Helper trait for generating json schema reference
+trait SchemaJsonReference {
+    /// Reference to another type in schema.
+    /// The value `$ref` is a URI-reference that is resolved against the schema.
+    fn ref_type(self) -> Value;
+}
+
+/// Helper trait for mapping bevy type path into json schema type
+trait SchemaJsonType {
+    /// Bevy Reflect type path
+    fn get_type_path(&self) -> &'static str;
+
+    /// JSON Schema type keyword from Bevy reflect type path into
+    fn map_json_type(&self) -> Option<SchemaType> {
+        match self.get_type_path() {
+            "bool" => Some(SchemaType::Boolean),
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64"
+            | "i128" | "isize" => Some(SchemaType::Integer),
+            "f32" | "f64" => Some(SchemaType::Number),
+            "char"
+            | "str"
+            | "alloc::string::String"
+            | "alloc::borrow::Cow<str>"
+            | "std::path::PathBuf" => Some(SchemaType::String),
+            _ => None,
+        }
+    }
+}
+
+impl SchemaJsonType for OpaqueInfo {
+    fn get_type_path(&self) -> &'static str {
+        self.type_path()
+    }
+}
+
+impl SchemaJsonReference for &bevy_reflect::Type {
+    fn ref_type(self) -> Value {
+        json!({ "$ref": definition(self.path()) })
+    }
+}
+
+impl SchemaJsonReference for &UnnamedField {
+    fn ref_type(self) -> Value {
+        json!({ "$ref": definition(self.type_path()) })
+    }
+}
+
+impl SchemaJsonReference for &NamedField {
+    fn ref_type(self) -> Value {
+        json!({ "$ref": definition(self.type_path()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{component::Component, reflect::AppTypeRegistry, resource::Resource};
+    use bevy_reflect::{prelude::ReflectDefault, Reflect};
+
+    fn export<T: bevy_reflect::GetTypeRegistration>() -> JsonSchemaBevyType {
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<T>();
+        }
+        let type_registry = atr.read();
+        let registration = type_registry
+            .get(TypeId::of::<T>())
+            .expect("SHOULD BE REGISTERED");
+        export_type(registration, &type_registry).1
+    }
+
+    #[test]
+    fn reflect_export_struct() {
+        #[derive(Reflect, Resource, Default, Deserialize, Serialize)]
+        #[reflect(Resource, Default, Serialize, Deserialize)]
+        struct Foo {
+            a: f32,
+            b: Option<f32>,
+        }
+
+        let schema = export::<Foo>();
+        assert!(
+            !schema.reflect_types.contains(&"Component".to_owned()),
+            "Should not be a component"
+        );
+        assert!(
+            schema.reflect_types.contains(&"Resource".to_owned()),
+            "Should be a resource"
+        );
+        let _ = schema.properties.get("a").expect("Missing `a` field");
+        let _ = schema.properties.get("b").expect("Missing `b` field");
+        assert!(
+            schema.required.contains(&"a".to_owned()),
+            "Field a should be required"
+        );
+        assert!(
+            !schema.required.contains(&"b".to_owned()),
+            "Field b should not be required"
+        );
+    }
+
+    #[test]
+    fn reflect_export_enum() {
+        #[derive(Reflect, Component, Default, Deserialize, Serialize)]
+        #[reflect(Component, Default, Serialize, Deserialize)]
+        enum EnumComponent {
+            ValueOne(i32),
+            ValueTwo {
+                test: i32,
+            },
+            #[default]
+            NoValue,
+        }
+
+        let schema = export::<EnumComponent>();
+        assert!(
+            schema.reflect_types.contains(&"Component".to_owned()),
+            "Should be a component"
+        );
+        assert!(
+            !schema.reflect_types.contains(&"Resource".to_owned()),
+            "Should not be a resource"
+        );
+        assert!(schema.properties.is_empty(), "Should not have any field");
+        assert!(schema.one_of.len() == 3, "Should have 3 possible schemas");
+        assert_eq!(schema.schema_type, None, "Mixes strings and objects");
+        assert_eq!(schema.default, Some(json!("NoValue")));
+        assert_eq!(
+            schema.one_of[0]["properties"]["ValueOne"],
+            json!({ "$ref": "#/$defs/i32" }),
+            "Newtype variants should be tagged with their name"
+        );
+        assert_eq!(
+            schema.one_of[1]["properties"]["ValueTwo"]["properties"]["test"],
+            json!({ "$ref": "#/$defs/i32" }),
+        );
+        assert_eq!(schema.one_of[2]["const"], json!("NoValue"));
+    }
+
+    #[test]
+    fn reflect_export_struct_without_reflect_types() {
+        #[derive(Reflect, Component, Default, Deserialize, Serialize)]
+        enum EnumComponent {
+            ValueOne(i32),
+            ValueTwo {
+                test: i32,
+            },
+            #[default]
+            NoValue,
+        }
+
+        let schema = export::<EnumComponent>();
+        assert!(
+            !schema.reflect_types.contains(&"Component".to_owned()),
+            "Should not be a component"
+        );
+        assert!(
+            !schema.reflect_types.contains(&"Resource".to_owned()),
+            "Should not be a resource"
+        );
+        assert!(schema.properties.is_empty(), "Should not have any field");
+        assert!(schema.one_of.len() == 3, "Should have 3 possible schemas");
+        assert!(schema.default.is_none(), "Default isn't reflected");
+    }
+
+    #[test]
+    fn reflect_export_tuple_struct() {
+        #[derive(Reflect, Component, Default, Deserialize, Serialize)]
+        #[reflect(Component, Default, Serialize, Deserialize)]
+        struct TupleStructType(usize, i32);
+
+        #[derive(Reflect)]
+        struct NewtypeStruct(u8);
+
+        let schema = export::<TupleStructType>();
+        assert!(
+            schema.reflect_types.contains(&"Component".to_owned()),
+            "Should be a component"
+        );
+        assert!(
+            !schema.reflect_types.contains(&"Resource".to_owned()),
+            "Should not be a resource"
+        );
+        assert!(schema.properties.is_empty(), "Should not have any field");
+        assert!(schema.prefix_items.len() == 2, "Should have 2 prefix items");
+
+        let schema = export::<NewtypeStruct>();
+        assert_eq!(schema.reference.as_deref(), Some("#/$defs/u8"));
+        assert_eq!(schema.schema_type, None);
+    }
+
+    #[test]
+    fn reflect_export_generics_options_and_collections() {
+        #[derive(Reflect)]
+        struct Wrapper<T> {
+            value: T,
+        }
+
+        let schema = export::<Wrapper<f32>>();
+        assert_eq!(
+            schema.generics,
+            [JsonSchemaGeneric {
+                name: "T".to_owned(),
+                reference: "#/$defs/f32".to_owned(),
+                is_const: false,
+            }]
+        );
+
+        let schema = export::<Option<f32>>();
+        assert_eq!(schema.kind, SchemaKind::Optional);
+        assert_eq!(
+            schema.one_of,
+            [json!({ "type": "null" }), json!({ "$ref": "#/$defs/f32" })]
+        );
+
+        let schema = export::<HashMap<String, u8>>();
+        assert_eq!(schema.schema_type, Some(SchemaType::Object));
+        assert_eq!(
+            schema.additional_properties,
+            Some(json!({ "$ref": "#/$defs/u8" }))
+        );
+
+        let schema = export::<[u16; 3]>();
+        assert_eq!((schema.min_items, schema.max_items), (Some(3), Some(3)));
+
+        let schema = export::<u8>();
+        assert_eq!(schema.schema_type, Some(SchemaType::Integer));
+        assert_eq!(
+            (schema.minimum, schema.maximum),
+            (Some(json!(0)), Some(json!(255)))
+        );
+    }
+
+    #[test]
+    fn reflect_export_custom_attributes() {
+        #[derive(Reflect)]
+        struct Range {
+            max: f32,
+        }
+
+        #[derive(Reflect)]
+        #[reflect(@Range { max: 1.0 })]
+        struct Volume {
+            #[reflect(@Range { max: 10.0 })]
+            level: f32,
+        }
+
+        let schema = export::<Volume>();
+        let range_path = "bevy_remote::schemas::json_schema::tests::Range";
+        assert_eq!(
+            schema.custom_attributes.get(range_path),
+            Some(&json!({ "max": 1.0 }))
+        );
+        assert_eq!(
+            schema.properties["level"]["customAttributes"][range_path],
+            json!({ "max": 10.0 })
+        );
+    }
+
+    #[test]
+    fn reflect_export_document() {
+        #[derive(Reflect, Resource, Default)]
+        #[reflect(Resource, Default)]
+        struct Foo {
+            a: f32,
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Foo>();
+        let document = JsonSchemaDocument::from_registry(&registry, &Default::default());
+        assert_eq!(document.schema, JSON_SCHEMA_DIALECT);
+        assert!(document.defs.contains_key("f32"));
+
+        let dir = std::env::temp_dir().join(format!(
+            "bevy_remote_reflect_export_document_{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("schemas.json");
+        document.write_to_file(&path).unwrap();
+        let written: JsonSchemaDocument =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(written, document);
+    }
+
+    #[test]
+    fn reflect_export_serialization_check() {
+        #[derive(Reflect, Resource, Default, Deserialize, Serialize)]
+        #[reflect(Resource, Default)]
+        struct Foo {
+            a: f32,
+        }
+
+        let schema = export::<Foo>();
+        let schema_as_value = serde_json::to_value(&schema).expect("Should serialize");
+        let value = json!({
+          "shortPath": "Foo",
+          "typePath": "bevy_remote::schemas::json_schema::tests::Foo",
+          "modulePath": "bevy_remote::schemas::json_schema::tests",
+          "crateName": "bevy_remote",
+          "reflectTypes": [
+            "Resource",
+            "Default",
+          ],
+          "kind": "Struct",
+          "type": "object",
+          "default": {
+            "a": 0.0
+          },
+          "additionalProperties": false,
+          "properties": {
+            "a": {
+              "$ref": "#/$defs/f32"
+            },
+          },
+          "required": [
+            "a"
+          ]
+        });
+        assert_eq!(schema_as_value, value);
+    }
+}
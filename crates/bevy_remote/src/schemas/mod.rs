@@ -0,0 +1,3 @@
+//! Module with schemas used for various BRP endpoints
+
+pub mod json_schema;
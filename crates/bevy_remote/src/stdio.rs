@@ -0,0 +1,81 @@
+//! The BRP transport using newline-delimited JSON-RPC over the standard input and output of the
+//! process.
+//!
+//! Adding the [`RemoteStdioPlugin`] to your [`App`] lets the parent process of your app control
+//! it through pipes, without opening a TCP port.
+//!
+//! The parent writes one JSON-RPC request (or batch of requests) per line to the standard input
+//! of the app, and reads one response (or batch of responses) per line from its standard output.
+//! Watching requests (`bevy/get+watch`, `bevy/list+watch`, etc.) write a response line for every
+//! update until the standard input is closed.
+//!
+//! Nothing else should write to the standard output while this transport is in use. Note that
+//! the `LogPlugin` writes to the standard error instead.
+//!
+//! If the [`RemoteAccessControl`](crate::access_control::RemoteAccessControl) requires a token,
+//! each request must carry it in a `"token"` member next to its `"method"`.
+
+#![cfg(not(target_family = "wasm"))]
+
+use crate::{ndjson, BrpSender};
+use bevy_app::{App, Plugin, Startup};
+use bevy_ecs::system::Res;
+use bevy_tasks::{futures_lite::StreamExt, IoTaskPool};
+use core::convert::Infallible;
+use std::{
+    io::{self, BufRead, Write},
+    thread,
+};
+
+/// Add this plugin to your [`App`] to allow the parent process to inspect and modify entities
+/// through the standard input and output.
+/// It requires the [`RemotePlugin`](super::RemotePlugin).
+///
+/// This BRP transport cannot be used when targeting WASM.
+#[derive(Default)]
+pub struct RemoteStdioPlugin;
+
+impl Plugin for RemoteStdioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, start_stdio_server);
+    }
+}
+
+/// A system that starts up the Bevy Remote Protocol stdio server.
+fn start_stdio_server(request_sender: Res<BrpSender>) {
+    // The standard input is only read once the previous line has been taken.
+    let (line_sender, line_receiver) = async_channel::bounded::<String>(1);
+    let (response_sender, response_receiver) =
+        async_channel::bounded::<String>(ndjson::RESPONSE_CAPACITY);
+
+    // The standard streams can't be polled asynchronously on every platform, so they are read and
+    // written on dedicated threads.
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if line_sender.send_blocking(line).is_err() {
+                break;
+            }
+        }
+    });
+    thread::spawn(move || {
+        let mut stdout = io::stdout();
+        while let Ok(response) = response_receiver.recv_blocking() {
+            if writeln!(stdout, "{response}")
+                .and_then(|()| stdout.flush())
+                .is_err()
+            {
+                break;
+            }
+        }
+        response_receiver.close();
+    });
+
+    IoTaskPool::get()
+        .spawn(ndjson::serve(
+            line_receiver.map(Ok::<_, Infallible>),
+            request_sender.clone(),
+            response_sender,
+        ))
+        .detach();
+}
@@ -0,0 +1,175 @@
+//! The BRP transport using newline-delimited JSON-RPC over a Unix domain socket.
+//!
+//! Adding the [`RemoteUnixSocketPlugin`] to your [`App`] causes Bevy to accept connections on a
+//! Unix domain socket while your app is running, without opening a TCP port.
+//!
+//! Clients write one JSON-RPC request (or batch of requests) per line, and read one response
+//! (or batch of responses) per line. Watching requests (`bevy/get+watch`, `bevy/list+watch`,
+//! etc.) write a response line for every update until the connection is closed.
+//!
+//! If the [`RemoteAccessControl`](crate::access_control::RemoteAccessControl) requires a token,
+//! each request must carry it in a `"token"` member next to its `"method"`. The permissions of
+//! the socket file can also be used to restrict who can connect.
+
+#![cfg(unix)]
+
+use crate::{ndjson, BrpMessage, BrpSender};
+use alloc::sync::Arc;
+use anyhow::Result as AnyhowResult;
+use async_channel::Sender;
+use async_io::Async;
+use bevy_app::{App, Plugin, Startup};
+use bevy_ecs::{resource::Resource, system::Res};
+use bevy_tasks::{
+    futures_lite::{io::BufReader, AsyncBufReadExt, AsyncWriteExt},
+    IoTaskPool,
+};
+use std::{
+    env, fs, io,
+    os::unix::{
+        fs::FileTypeExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::PathBuf,
+};
+
+/// The default name of the socket file, created in the runtime directory of the user.
+pub const DEFAULT_SOCKET_NAME: &str = "bevy_remote.sock";
+
+/// Add this plugin to your [`App`] to allow remote connections over a Unix domain socket to
+/// inspect and modify entities.
+/// It requires the [`RemotePlugin`](super::RemotePlugin).
+///
+/// This BRP transport is only available on Unix platforms.
+///
+/// By default, the socket is created at [`DEFAULT_SOCKET_NAME`] in `$XDG_RUNTIME_DIR`, or in the
+/// temporary directory if that variable isn't set. A socket left behind at the path by a previous
+/// run is replaced, but the server refuses to start if any other kind of file is there.
+pub struct RemoteUnixSocketPlugin {
+    /// The path of the socket file.
+    path: PathBuf,
+}
+
+impl Default for RemoteUnixSocketPlugin {
+    fn default() -> Self {
+        let directory = env::var_os("XDG_RUNTIME_DIR")
+            .filter(|directory| !directory.is_empty())
+            .map_or_else(env::temp_dir, PathBuf::from);
+        Self {
+            path: directory.join(DEFAULT_SOCKET_NAME),
+        }
+    }
+}
+
+impl Plugin for RemoteUnixSocketPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(UnixSocketPath(self.path.clone()))
+            .add_systems(Startup, start_unix_socket_server);
+    }
+}
+
+impl RemoteUnixSocketPlugin {
+    /// Set the path of the socket file that the server will listen on.
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = path.into();
+        self
+    }
+}
+
+/// A resource containing the path of the socket file that Bevy will listen on.
+///
+/// Currently, changing this while the application is running has no effect; this merely
+/// reflects the path that is set during the setup of the [`RemoteUnixSocketPlugin`].
+#[derive(Debug, Resource)]
+pub struct UnixSocketPath(pub PathBuf);
+
+/// A system that starts up the Bevy Remote Protocol Unix socket server.
+fn start_unix_socket_server(request_sender: Res<BrpSender>, path: Res<UnixSocketPath>) {
+    let path = path.0.clone();
+    let request_sender = request_sender.clone();
+    IoTaskPool::get()
+        .spawn(async move {
+            if let Err(error) = server_main(path.clone(), request_sender).await {
+                log::error!(
+                    "BRP Unix socket server at {} stopped: {error}",
+                    path.display()
+                );
+            }
+        })
+        .detach();
+}
+
+/// The Bevy Remote Protocol Unix socket server main loop.
+async fn server_main(path: PathBuf, request_sender: Sender<BrpMessage>) -> AnyhowResult<()> {
+    // Remove the socket left behind by a previous run, if any, but never another kind of file.
+    match fs::symlink_metadata(&path) {
+        Ok(metadata) if metadata.file_type().is_socket() => fs::remove_file(&path)?,
+        Ok(_) => anyhow::bail!("{} exists and is not a socket", path.display()),
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+        Err(_) => {}
+    }
+    let listener = Async::<UnixListener>::bind(&path)?;
+
+    loop {
+        let (client, _) = listener.accept().await?;
+
+        let request_sender = request_sender.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let _ = handle_client(client, request_sender).await;
+            })
+            .detach();
+    }
+}
+
+async fn handle_client(
+    client: Async<UnixStream>,
+    request_sender: Sender<BrpMessage>,
+) -> AnyhowResult<()> {
+    let client = Arc::new(client);
+    let (response_sender, response_receiver) =
+        async_channel::bounded::<String>(ndjson::RESPONSE_CAPACITY);
+
+    // Write the responses on their own task, so that watching requests can stream updates while
+    // other requests are read.
+    let writer = Arc::clone(&client);
+    let writer = IoTaskPool::get().spawn(async move {
+        let mut writer = &*writer;
+        while let Ok(mut response) = response_receiver.recv().await {
+            response.push('\n');
+            if writer.write_all(response.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+        response_receiver.close();
+    });
+
+    ndjson::serve(
+        BufReader::new(&*client).lines(),
+        request_sender,
+        response_sender,
+    )
+    .await;
+
+    // The client disconnected, which stopped the watchers: let the writer finish.
+    writer.await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_tasks::block_on;
+
+    #[test]
+    fn only_sockets_are_replaced() {
+        let path = env::temp_dir().join(format!("bevy_remote_not_a_socket_{}", std::process::id()));
+        fs::write(&path, "precious").unwrap();
+
+        let (request_sender, _request_receiver) = async_channel::unbounded();
+        assert!(block_on(server_main(path.clone(), request_sender)).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "precious");
+        fs::remove_file(&path).unwrap();
+    }
+}